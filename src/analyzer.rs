@@ -9,7 +9,7 @@ pub(crate) struct Analyzer<'run, 'src> {
   recipes: Vec<&'run Recipe<'src, UnresolvedDependency<'src>>>,
   sets: Table<'src, Set<'src>>,
   unexports: BTreeSet<String>,
-  warnings: Vec<Warning>,
+  warnings: Vec<Warning<'src>>,
 }
 
 impl<'run, 'src> Analyzer<'run, 'src> {
@@ -57,6 +57,7 @@ impl<'run, 'src> Analyzer<'run, 'src> {
   ) -> CompileResult<'src, Justfile<'src>> {
     let mut absent_modules = BTreeSet::new();
     let mut definitions = HashMap::new();
+    let mut disabled = Vec::new();
     let mut imports = HashSet::new();
     let mut list_features = Vec::new();
     let mut module_docs: Vec<(&str, Expression)> = Vec::new();
@@ -73,6 +74,7 @@ impl<'run, 'src> Analyzer<'run, 'src> {
 
       for item in &ast.items {
         if !item.is_enabled() {
+          disabled.push(item);
           continue;
         }
 
@@ -277,6 +279,14 @@ impl<'run, 'src> Analyzer<'run, 'src> {
       )?
     };
 
+    for set in self.sets.values() {
+      if set.value.is_redundant() && !set.attributes.allows(Lint::RedundantSetting) {
+        self
+          .warnings
+          .push(Warning::RedundantSetting { setting: set.name });
+      }
+    }
+
     let settings = evaluator.evaluate_sets(self.sets)?;
 
     if !settings.lists {
@@ -346,14 +356,18 @@ impl<'run, 'src> Analyzer<'run, 'src> {
           continued = !comment && line.is_continuation();
         }
 
-        for attribute in [AttributeKind::Cache, AttributeKind::Extension] {
-          if let Some(attribute) = recipe.attributes.get(attribute) {
-            return Err(recipe.name.error(InvalidShellRecipeAttribute {
-              attribute: Box::new(attribute.clone()),
-              recipe: recipe.name.lexeme(),
-            }));
-          }
+        if let Some(attribute) = recipe.attributes.get(AttributeKind::Extension) {
+          return Err(recipe.name.error(InvalidShellRecipeAttribute {
+            attribute: Box::new(attribute.clone()),
+            recipe: recipe.name.lexeme(),
+          }));
         }
+      } else if let Some(Attribute::Retry { resume: true, .. }) =
+        recipe.attributes.get(AttributeKind::Retry)
+      {
+        return Err(recipe.name.error(RetryResumeScriptRecipe {
+          recipe: recipe.name.lexeme(),
+        }));
       }
     }
 
@@ -464,6 +478,32 @@ impl<'run, 'src> Analyzer<'run, 'src> {
         })
     });
 
+    self.warnings.extend(Linter::lint(
+      &assignments,
+      default.as_ref(),
+      &disabled,
+      &functions,
+      &ast.module_path,
+      &mut self.modules,
+      &recipe_aliases,
+      &recipes,
+      &settings,
+      &variable_references,
+      &variable_resolver,
+    ));
+
+    self
+      .warnings
+      .sort_by_key(|warning| warning.context().map(|token| (token.path, token.offset)));
+
+    let platform_disabled_recipes = disabled
+      .iter()
+      .filter_map(|item| match item {
+        Item::Recipe(recipe) if !recipes.contains_key(recipe.name()) => Some(recipe.name()),
+        _ => None,
+      })
+      .collect();
+
     Ok(Justfile {
       absent_modules,
       assignment_references,
@@ -480,6 +520,7 @@ impl<'run, 'src> Analyzer<'run, 'src> {
       module_path: ast.module_path.clone(),
       modules: self.modules,
       name,
+      platform_disabled_recipes,
       private,
       recipe_aliases,
       recipes,
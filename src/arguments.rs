@@ -48,6 +48,13 @@ pub struct Arguments {
     num_args = 1..,
   )]
   pub(crate) arguments: Vec<String>,
+  #[arg(
+    add = ArgValueCompleter::new(PathCompleter::dir()),
+    env = "JUST_CACHE_DIR",
+    help = "Store cached recipe entries and outputs in <CACHE_DIR>.",
+    long = "cache-dir",
+  )]
+  pub(crate) cache_directory: Option<PathBuf>,
   #[arg(
     add = ArgValueCompleter::new(PathCompleter::dir()),
     env = "JUST_CEILING",
@@ -69,6 +76,16 @@ pub struct Arguments {
     long
   )]
   pub(crate) chooser: Option<PathBuf>,
+  #[arg(
+    default_value = "auto",
+    env = "JUST_CI_FORMAT",
+    help = "Fold the output of each recipe into a section in the logs of a CI service, detected \
+            from the environment by default",
+    long,
+    value_enum,
+    value_name = "FORMAT"
+  )]
+  pub(crate) ci_format: CiFormat,
   #[arg(help = "Clear shell arguments", long, overrides_with = "shell_arg")]
   pub(crate) clear_shell_args: bool,
   #[arg(
@@ -106,6 +123,12 @@ pub struct Arguments {
     long
   )]
   pub(crate) default_list: bool,
+  #[arg(
+    env = "JUST_DENY_WARNINGS",
+    help = "Treat warnings about the justfile as errors",
+    long
+  )]
+  pub(crate) deny_warnings: bool,
   #[arg(
     conflicts_with = "dotenv_filename",
     conflicts_with = "dotenv_path",
@@ -156,12 +179,33 @@ pub struct Arguments {
     value_name = "FORMAT"
   )]
   pub(crate) evaluate_format: EvaluateFormat,
+  #[arg(
+    env = "JUST_EVENTS",
+    help = "Write run events to standard error in <FORMAT>",
+    long,
+    value_enum,
+    value_name = "FORMAT"
+  )]
+  pub(crate) events: Option<EventsFormat>,
+  #[arg(
+    add = ArgValueCompleter::new(PathCompleter::file()),
+    env = "JUST_EVENTS_FILE",
+    help = "Write run events to <EVENTS-FILE> as newline-delimited JSON",
+    long,
+  )]
+  pub(crate) events_file: Option<PathBuf>,
   #[arg(
     env = "JUST_EXPLAIN",
     help = "Print recipe doc comment before running it",
     long
   )]
   pub(crate) explain: bool,
+  #[arg(
+    env = "JUST_EXPLAIN_CACHE",
+    help = "Print why cached recipes miss the cache",
+    long
+  )]
+  pub(crate) explain_cache: bool,
   #[arg(
     conflicts_with = "justfile",
     conflicts_with = "working_directory",
@@ -198,6 +242,13 @@ pub struct Arguments {
     value_name = "N"
   )]
   pub(crate) jobs: Option<NonZeroU64>,
+  #[arg(
+    add = ArgValueCompleter::new(PathCompleter::file()),
+    env = "JUST_JUNIT",
+    help = "Write a JUnit XML report of recipe runs to <JUNIT>",
+    long
+  )]
+  pub(crate) junit: Option<PathBuf>,
   #[arg(
     add = ArgValueCompleter::new(PathCompleter::file()),
     env = "JUST_JUSTFILE",
@@ -215,6 +266,14 @@ pub struct Arguments {
     value_name = "NAME"
   )]
   pub(crate) justfile_names: Option<Vec<String>>,
+  #[arg(
+    env = "JUST_KEEP_GOING",
+    help = "Keep running recipes whose dependencies succeeded after a recipe fails",
+    long
+  )]
+  pub(crate) keep_going: bool,
+  #[arg(env = "JUST_LINT", help = "Print warnings about the justfile", long)]
+  pub(crate) lint: bool,
   #[arg(
     default_value = Self::DEFAULT_LIST_HEADING,
     env = "JUST_LIST_HEADING",
@@ -238,6 +297,13 @@ pub struct Arguments {
     requires = "list"
   )]
   pub(crate) list_submodules: bool,
+  #[arg(
+    help = "Evict least recently used cache entries until the cache is no larger than <SIZE>",
+    long,
+    requires = "cache_prune",
+    value_name = "SIZE"
+  )]
+  pub(crate) max_size: Option<Size>,
   #[arg(env = "JUST_NO_ALIASES", help = "Don't show aliases in list", long)]
   pub(crate) no_aliases: bool,
   #[arg(env = "JUST_NO_CACHE", help = "Bypass recipe cache", long)]
@@ -258,12 +324,33 @@ pub struct Arguments {
     overrides_with = "highlight"
   )]
   pub(crate) no_highlight: bool,
+  #[arg(
+    help = "Evict cache entries which have not been used within <DURATION>",
+    long,
+    requires = "cache_prune",
+    value_name = "DURATION"
+  )]
+  pub(crate) older_than: Option<Timespan>,
   #[arg(
     env = "JUST_ONE",
     help = "Forbid multiple recipes from being invoked on the command line",
     long
   )]
   pub(crate) one: bool,
+  #[arg(
+    env = "JUST_OUTPUT",
+    help = "Write output of recipe commands in <MODE>, overriding the `output` setting",
+    long,
+    value_enum,
+    value_name = "MODE"
+  )]
+  pub(crate) output: Option<OutputMode>,
+  #[arg(
+    env = "JUST_PARALLEL",
+    help = "Run recipes invoked on the command line in parallel",
+    long
+  )]
+  pub(crate) parallel: bool,
   #[arg(
     conflicts_with = "dry_run",
     env = "JUST_QUIET",
@@ -306,6 +393,14 @@ pub struct Arguments {
   pub(crate) tempdir: Option<PathBuf>,
   #[arg(env = "JUST_TIME", help = "Print recipe execution time", long)]
   pub(crate) time: bool,
+  #[arg(
+    env = "JUST_TIMEOUT",
+    help = "Terminate recipes which run for longer than <DURATION>, unless overridden with \
+            `[timeout]`",
+    long,
+    value_name = "DURATION"
+  )]
+  pub(crate) timeout: Option<Timespan>,
   #[arg(env = "JUST_TIMESTAMP", help = "Print recipe command timestamps", long)]
   pub(crate) timestamp: bool,
   #[arg(
@@ -315,6 +410,13 @@ pub struct Arguments {
     long
   )]
   pub(crate) timestamp_format: String,
+  #[arg(
+    add = ArgValueCompleter::new(PathCompleter::file()),
+    env = "JUST_TRACE",
+    help = "Write a timeline of recipe runs to <TRACE> in Chrome Trace Event Format",
+    long
+  )]
+  pub(crate) trace: Option<PathBuf>,
   #[arg(
     env = "JUST_UNSORTED",
     help = "Return list and summary entries in source order",
@@ -337,6 +439,36 @@ pub struct Arguments {
     short = 'v',
   )]
   pub(crate) verbose: u8,
+  #[arg(
+    conflicts_with = "Subcommand",
+    help = "Re-run recipes when their cache inputs, stale sources, or `--watch-path` files change",
+    long
+  )]
+  pub(crate) watch: bool,
+  #[arg(
+    default_value = Self::DEFAULT_WATCH_DEBOUNCE,
+    help = "Wait for changes to stop for <DURATION> before re-running recipes",
+    long,
+    requires = "watch",
+    value_name = "DURATION"
+  )]
+  pub(crate) watch_debounce: Timespan,
+  #[arg(
+    default_value = Self::DEFAULT_WATCH_INTERVAL,
+    help = "Check for changes every <DURATION>",
+    long,
+    requires = "watch",
+    value_name = "DURATION"
+  )]
+  pub(crate) watch_interval: Timespan,
+  #[arg(
+    add = ArgValueCompleter::new(PathCompleter::any()),
+    help = "Watch <PATH> for changes. May be a file or directory, and may be given multiple times",
+    long,
+    requires = "watch",
+    value_name = "PATH"
+  )]
+  pub(crate) watch_path: Vec<PathBuf>,
   #[arg(
     add = ArgValueCompleter::new(PathCompleter::dir()),
     env = "JUST_WORKING_DIRECTORY",
@@ -353,6 +485,25 @@ pub struct Arguments {
 #[derive(Args, Debug, Default)]
 #[group(multiple = false)]
 pub(crate) struct Subcommand {
+  #[arg(
+    help = "List cached recipe invocations",
+    help_heading = Self::HEADING,
+    long,
+  )]
+  pub(crate) cache_list: bool,
+  #[arg(
+    help = "Evict cache entries selected by `--older-than` and `--max-size` and remove \
+            unreferenced output files",
+    help_heading = Self::HEADING,
+    long,
+  )]
+  pub(crate) cache_prune: bool,
+  #[arg(
+    help = "Print cache entry count and disk usage",
+    help_heading = Self::HEADING,
+    long,
+  )]
+  pub(crate) cache_stats: bool,
   #[arg(
     help = "Print changelog",
     help_heading = Self::HEADING,
@@ -453,6 +604,12 @@ pub(crate) struct Subcommand {
     value_name = "MODULE",
   )]
   pub(crate) list: Option<Vec<String>>,
+  #[arg(
+    help = "Run language server, communicating over standard input and output",
+    help_heading = Self::HEADING,
+    long,
+  )]
+  pub(crate) lsp: bool,
   #[arg(
     help = "Print man page",
     help_heading = Self::HEADING,
@@ -507,6 +664,8 @@ impl Arguments {
   pub(crate) const DEFAULT_LIST_HEADING: &str = "Available recipes:\n";
   pub(crate) const DEFAULT_LIST_PREFIX: &str = "    ";
   pub(crate) const DEFAULT_TIMESTAMP_FORMAT: &str = "%H:%M:%S";
+  pub(crate) const DEFAULT_WATCH_DEBOUNCE: &str = "100ms";
+  pub(crate) const DEFAULT_WATCH_INTERVAL: &str = "500ms";
 }
 
 impl Subcommand {
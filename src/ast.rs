@@ -9,7 +9,7 @@ pub(crate) struct Ast<'src> {
   pub(crate) list_features: Vec<(ListFeature, Token<'src>)>,
   pub(crate) module_path: Modulepath,
   pub(crate) unstable_features: BTreeSet<UnstableFeature>,
-  pub(crate) warnings: Vec<Warning>,
+  pub(crate) warnings: Vec<Warning<'src>>,
   pub(crate) working_directory: PathBuf,
 }
 
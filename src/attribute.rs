@@ -7,9 +7,10 @@ use super::*;
 #[strum(serialize_all = "kebab-case")]
 #[serde(rename_all = "kebab-case")]
 #[strum_discriminants(name(AttributeKind))]
-#[strum_discriminants(derive(EnumString, Ord, PartialOrd))]
+#[strum_discriminants(derive(EnumIter, EnumString, IntoStaticStr, Ord, PartialOrd))]
 #[strum_discriminants(strum(serialize_all = "kebab-case"))]
 pub(crate) enum Attribute<'src> {
+  Allow(BTreeSet<Lint>),
   Android,
   Arg {
     #[serde(skip)]
@@ -38,8 +39,12 @@ pub(crate) enum Attribute<'src> {
     value: Option<Expression<'src>>,
   },
   Cache {
+    env: Option<Expression<'src>>,
+    exclude: Option<Expression<'src>>,
     extra: Option<Expression<'src>>,
+    gitignore: bool,
     inputs: Option<Expression<'src>>,
+    no_exports: bool,
     outputs: Option<Expression<'src>>,
   },
   Confirm(Option<Expression<'src>>),
@@ -53,6 +58,11 @@ pub(crate) enum Attribute<'src> {
   Freebsd,
   Group(StringLiteral<'src>),
   Linux,
+  Lock {
+    global: bool,
+    name: StringLiteral<'src>,
+    shared: bool,
+  },
   Macos,
   Metadata(Vec<StringLiteral<'src>>),
   Netbsd,
@@ -63,8 +73,20 @@ pub(crate) enum Attribute<'src> {
   Parallel,
   PositionalArguments,
   Private,
+  Retry {
+    attempts: u64,
+    backoff: Option<StringLiteral<'src>>,
+    delay: Option<StringLiteral<'src>>,
+    on: BTreeSet<i32>,
+    resume: bool,
+  },
   Script(Option<Interpreter<StringLiteral<'src>>>),
   Shell,
+  Stale {
+    sources: Option<Expression<'src>>,
+    targets: Expression<'src>,
+  },
+  Timeout(StringLiteral<'src>),
   Timestamp(Option<Expression<'src>>),
   Unix,
   Windows,
@@ -80,7 +102,10 @@ impl AttributeKind {
   }
 
   pub(crate) fn accepts_keyword_arguments(self) -> bool {
-    matches!(self, Self::Arg | Self::Cache)
+    matches!(
+      self,
+      Self::Arg | Self::Cache | Self::Lock | Self::Retry | Self::Stale
+    )
   }
 
   pub(crate) fn is_enabler(self) -> bool {
@@ -117,13 +142,19 @@ impl AttributeKind {
       | Self::PositionalArguments
       | Self::Private
       | Self::Shell
+      | Self::Stale
       | Self::Unix
       | Self::Windows => 0..=0,
-      Self::Confirm | Self::Doc | Self::Timestamp => 0..=1,
+      Self::Confirm | Self::Doc | Self::Retry | Self::Timestamp => 0..=1,
       Self::Continue | Self::Script => 0..=usize::MAX,
-      Self::Arg | Self::Extension | Self::Group | Self::WorkingDirectory => 1..=1,
+      Self::Arg
+      | Self::Extension
+      | Self::Group
+      | Self::Lock
+      | Self::Timeout
+      | Self::WorkingDirectory => 1..=1,
       Self::Env => 2..=2,
-      Self::Metadata => 1..=usize::MAX,
+      Self::Allow | Self::Metadata => 1..=usize::MAX,
     }
   }
 }
@@ -225,13 +256,31 @@ impl<'src> Attribute<'src> {
       .collect::<CompileResult<Vec<StringLiteral>>>()?;
 
     let attribute = match kind {
+      AttributeKind::Allow => Self::Allow(
+        arguments
+          .into_iter()
+          .map(|literal| {
+            literal.cooked.parse().map_err(|_| {
+              literal.token.error(CompileErrorKind::UnknownLint {
+                lint: literal.cooked.clone(),
+              })
+            })
+          })
+          .collect::<CompileResult<BTreeSet<Lint>>>()?,
+      ),
       AttributeKind::Arg => Self::new_arg(name, arguments, &mut keyword_arguments)?,
       AttributeKind::Android => Self::Android,
       AttributeKind::Cache => Self::Cache {
+        env: Self::remove_required(&mut keyword_arguments, "env")?
+          .map(|(_key, expression)| expression),
+        exclude: Self::remove_required(&mut keyword_arguments, "exclude")?
+          .map(|(_key, expression)| expression),
         extra: Self::remove_required(&mut keyword_arguments, "extra")?
           .map(|(_key, expression)| expression),
+        gitignore: Self::remove_flag(&mut keyword_arguments, "gitignore")?,
         inputs: Self::remove_required(&mut keyword_arguments, "inputs")?
           .map(|(_key, expression)| expression),
+        no_exports: Self::remove_flag(&mut keyword_arguments, "no-exports")?,
         outputs: Self::remove_required(&mut keyword_arguments, "outputs")?
           .map(|(_key, expression)| expression),
       },
@@ -261,6 +310,11 @@ impl<'src> Attribute<'src> {
       AttributeKind::Freebsd => Self::Freebsd,
       AttributeKind::Group => Self::Group(arguments.into_iter().next().unwrap()),
       AttributeKind::Linux => Self::Linux,
+      AttributeKind::Lock => Self::Lock {
+        global: Self::remove_flag(&mut keyword_arguments, "global")?,
+        name: arguments.into_iter().next().unwrap(),
+        shared: Self::remove_flag(&mut keyword_arguments, "shared")?,
+      },
       AttributeKind::Macos => Self::Macos,
       AttributeKind::Metadata => Self::Metadata(arguments),
       AttributeKind::Netbsd => Self::Netbsd,
@@ -271,6 +325,7 @@ impl<'src> Attribute<'src> {
       AttributeKind::Parallel => Self::Parallel,
       AttributeKind::PositionalArguments => Self::PositionalArguments,
       AttributeKind::Private => Self::Private,
+      AttributeKind::Retry => Self::new_retry(name, arguments, &mut keyword_arguments)?,
       AttributeKind::Script => Self::Script({
         let mut arguments = arguments.into_iter();
         arguments.next().map(|command| Interpreter {
@@ -279,6 +334,27 @@ impl<'src> Attribute<'src> {
         })
       }),
       AttributeKind::Shell => Self::Shell,
+      AttributeKind::Stale => Self::Stale {
+        sources: Self::remove_required(&mut keyword_arguments, "sources")?
+          .map(|(_key, expression)| expression),
+        targets: Self::remove_required(&mut keyword_arguments, "targets")?
+          .map(|(_key, expression)| expression)
+          .ok_or_else(|| {
+            name.error(CompileErrorKind::AttributeKeyRequired {
+              attribute: name,
+              key: "targets",
+            })
+          })?,
+      },
+      AttributeKind::Timeout => {
+        let literal = arguments.into_iter().next().unwrap();
+        literal.cooked.parse::<Timespan>().map_err(|message| {
+          literal
+            .token
+            .error(CompileErrorKind::InvalidDuration { message })
+        })?;
+        Self::Timeout(literal)
+      }
       AttributeKind::Unix => Self::Unix,
       AttributeKind::Windows => Self::Windows,
     };
@@ -470,6 +546,136 @@ impl<'src> Attribute<'src> {
     })
   }
 
+  fn new_retry(
+    name: Name<'src>,
+    arguments: Vec<StringLiteral<'src>>,
+    keyword_arguments: &mut BTreeMap<&'src str, (Name<'src>, Option<Expression<'src>>)>,
+  ) -> CompileResult<'src, Self> {
+    static ATTEMPTS: LazyLock<Regex> = LazyLock::new(|| Regex::new("^[1-9][0-9]*$").unwrap());
+
+    let attempts = match (
+      arguments.into_iter().next(),
+      Self::remove_required(keyword_arguments, "attempts")?,
+    ) {
+      (Some(_literal), Some((key, _expression))) => {
+        return Err(key.error(CompileErrorKind::DuplicateAttributeKey {
+          attribute: name.lexeme(),
+          key: key.lexeme(),
+        }));
+      }
+      (Some(literal), None) => (name, literal),
+      (None, Some((key, expression))) => {
+        (key, Self::require_string_literal(name, key, expression)?)
+      }
+      (None, None) => {
+        return Err(name.error(CompileErrorKind::AttributeKeyRequired {
+          attribute: name,
+          key: "attempts",
+        }));
+      }
+    };
+
+    let attempts = {
+      let (key, literal) = attempts;
+
+      if !ATTEMPTS.is_match(&literal.cooked) {
+        return Err(literal.token.error(CompileErrorKind::ArgumentCountValue {
+          key,
+          value: literal.cooked.clone(),
+        }));
+      }
+
+      literal.cooked.parse::<u64>().map_err(|source| {
+        literal.token.error(CompileErrorKind::ArgumentCountParse {
+          key,
+          value: literal.cooked.clone(),
+          source,
+        })
+      })?
+    };
+
+    let backoff = Self::remove_required(keyword_arguments, "backoff")?
+      .map(|(key, expression)| {
+        let literal = Self::require_string_literal(name, key, expression)?;
+
+        if !literal
+          .cooked
+          .parse::<f64>()
+          .is_ok_and(|backoff| backoff.is_finite() && backoff >= 1.0)
+        {
+          return Err(literal.token.error(CompileErrorKind::AttributeKeyValue {
+            key,
+            value: literal.cooked.clone(),
+          }));
+        }
+
+        Ok(literal)
+      })
+      .transpose()?;
+
+    let delay = Self::remove_required(keyword_arguments, "delay")?
+      .map(|(key, expression)| {
+        let literal = Self::require_string_literal(name, key, expression)?;
+
+        literal.cooked.parse::<Timespan>().map_err(|message| {
+          literal
+            .token
+            .error(CompileErrorKind::InvalidDuration { message })
+        })?;
+
+        Ok(literal)
+      })
+      .transpose()?;
+
+    let on = Self::remove_required(keyword_arguments, "on")?
+      .map(|(key, expression)| {
+        let literals = match expression {
+          Expression::List { elements, .. } => elements
+            .into_iter()
+            .map(|element| Self::require_string_literal(name, key, element))
+            .collect::<CompileResult<Vec<StringLiteral>>>()?,
+          expression => vec![Self::require_string_literal(name, key, expression)?],
+        };
+
+        literals
+          .into_iter()
+          .map(|literal| {
+            literal.cooked.parse::<i32>().map_err(|_| {
+              literal.token.error(CompileErrorKind::AttributeKeyValue {
+                key,
+                value: literal.cooked.clone(),
+              })
+            })
+          })
+          .collect::<CompileResult<BTreeSet<i32>>>()
+      })
+      .transpose()?
+      .unwrap_or_default();
+
+    Ok(Self::Retry {
+      attempts,
+      backoff,
+      delay,
+      on,
+      resume: Self::remove_flag(keyword_arguments, "resume")?,
+    })
+  }
+
+  fn remove_flag(
+    keyword_arguments: &mut BTreeMap<&'src str, (Name<'src>, Option<Expression<'src>>)>,
+    key: &'src str,
+  ) -> CompileResult<'src, bool> {
+    let Some((key, expression)) = keyword_arguments.remove(key) else {
+      return Ok(false);
+    };
+
+    if expression.is_some() {
+      return Err(key.error(CompileErrorKind::AttributeKeyTakesNoValue { key }));
+    }
+
+    Ok(true)
+  }
+
   fn remove_required(
     keyword_arguments: &mut BTreeMap<&'src str, (Name<'src>, Option<Expression<'src>>)>,
     key: &'src str,
@@ -507,7 +713,11 @@ impl<'src> Attribute<'src> {
   pub(crate) fn repeatable(&self) -> bool {
     matches!(
       self,
-      Attribute::Arg { .. } | Attribute::Env(_, _) | Attribute::Group(_) | Attribute::Metadata(_),
+      Attribute::Arg { .. }
+        | Attribute::Env(_, _)
+        | Attribute::Group(_)
+        | Attribute::Lock { .. }
+        | Attribute::Metadata(_),
     )
   }
 }
@@ -602,17 +812,33 @@ impl Display for Attribute<'_> {
       | Self::Unix
       | Self::Windows => {}
       Self::Cache {
+        env,
+        exclude,
         extra,
+        gitignore,
         inputs,
+        no_exports,
         outputs,
       } => {
         let mut arguments = Vec::new();
+        if let Some(env) = env {
+          arguments.push(format!("env={env}"));
+        }
+        if let Some(exclude) = exclude {
+          arguments.push(format!("exclude={exclude}"));
+        }
         if let Some(extra) = extra {
           arguments.push(format!("extra={extra}"));
         }
+        if *gitignore {
+          arguments.push("gitignore".into());
+        }
         if let Some(inputs) = inputs {
           arguments.push(format!("inputs={inputs}"));
         }
+        if *no_exports {
+          arguments.push("no-exports".into());
+        }
         if let Some(outputs) = outputs {
           arguments.push(format!("outputs={outputs}"));
         }
@@ -620,15 +846,80 @@ impl Display for Attribute<'_> {
           write!(f, "({})", arguments.join(", "))?;
         }
       }
+      Self::Lock {
+        global,
+        name,
+        shared,
+      } => {
+        write!(f, "({name}")?;
+        if *global {
+          write!(f, ", global")?;
+        }
+        if *shared {
+          write!(f, ", shared")?;
+        }
+        write!(f, ")")?;
+      }
+      Self::Retry {
+        attempts,
+        backoff,
+        delay,
+        on,
+        resume,
+      } => {
+        write!(f, "('{attempts}'")?;
+        if let Some(backoff) = backoff {
+          write!(f, ", backoff={backoff}")?;
+        }
+        if let Some(delay) = delay {
+          write!(f, ", delay={delay}")?;
+        }
+        if !on.is_empty() {
+          write!(f, ", on=")?;
+          if on.len() > 1 {
+            write!(f, "[")?;
+          }
+          for (i, code) in on.iter().enumerate() {
+            if i > 0 {
+              write!(f, ", ")?;
+            }
+            write!(f, "'{code}'")?;
+          }
+          if on.len() > 1 {
+            write!(f, "]")?;
+          }
+        }
+        if *resume {
+          write!(f, ", resume")?;
+        }
+        write!(f, ")")?;
+      }
+      Self::Stale { sources, targets } => {
+        write!(f, "(")?;
+        if let Some(sources) = sources {
+          write!(f, "sources={sources}, ")?;
+        }
+        write!(f, "targets={targets})")?;
+      }
       Self::Confirm(Some(argument))
       | Self::Doc(Some(argument))
       | Self::Timestamp(Some(argument))
       | Self::WorkingDirectory(argument) => {
         write!(f, "({argument})")?;
       }
-      Self::Extension(argument) | Self::Group(argument) => {
+      Self::Extension(argument) | Self::Group(argument) | Self::Timeout(argument) => {
         write!(f, "({argument})")?;
       }
+      Self::Allow(lints) => {
+        write!(f, "(")?;
+        for (i, lint) in lints.iter().enumerate() {
+          if i > 0 {
+            write!(f, ", ")?;
+          }
+          write!(f, "'{lint}'")?;
+        }
+        write!(f, ")")?;
+      }
       Self::Continue(signals) => {
         if !signals.is_empty() {
           write!(f, "(")?;
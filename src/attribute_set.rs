@@ -4,6 +4,13 @@ use super::*;
 pub(crate) struct AttributeSet<'src>(BTreeMap<Attribute<'src>, Name<'src>>);
 
 impl<'src> AttributeSet<'src> {
+  pub(crate) fn allows(&self, lint: Lint) -> bool {
+    self
+      .0
+      .keys()
+      .any(|attribute| matches!(attribute, Attribute::Allow(lints) if lints.contains(&lint)))
+  }
+
   pub(crate) fn len(&self) -> usize {
     self.0.len()
   }
@@ -87,15 +94,17 @@ impl<'src> AttributeSet<'src> {
     item_token: Token<'src>,
   ) -> Result<(), CompileError<'src>> {
     let valid = match item_kind {
-      ItemKind::Alias | ItemKind::Assignment => [AttributeKind::Private].as_slice(),
+      ItemKind::Alias => [AttributeKind::Private].as_slice(),
+      ItemKind::Assignment => &[AttributeKind::Allow, AttributeKind::Private],
       ItemKind::Comment | ItemKind::Newline => unreachable!(),
-      ItemKind::Function | ItemKind::Import | ItemKind::Setting | ItemKind::Unexport => &[],
+      ItemKind::Function | ItemKind::Import | ItemKind::Unexport => &[],
       ItemKind::Module => &[
         AttributeKind::Doc,
         AttributeKind::Group,
         AttributeKind::Private,
       ],
       ItemKind::Recipe => return Ok(()),
+      ItemKind::Setting => &[AttributeKind::Allow],
     };
 
     for attribute in self.0.keys() {
@@ -2,30 +2,37 @@ use super::*;
 
 const DIR: &str = ".justcache";
 
+pub(crate) const GLOB_METACHARACTERS: [char; 4] = ['*', '?', '[', '{'];
+
 pub(crate) struct Cache {
   initialized: Mutex<bool>,
   path: PathBuf,
+  root: Option<String>,
+  watched: Mutex<BTreeSet<PathBuf>>,
 }
 
 impl Cache {
+  pub(crate) const BLOBS: &str = "blobs";
+  const LOCKS: &str = "locks";
+  const ROOT: &str = "{{justfile_directory()}}";
+
   pub(crate) fn status(
     &self,
     config: &Config,
+    arguments: &[String],
     key: CacheKey,
-    outputs: &BTreeMap<String, PathBuf>,
+    outputs: BTreeMap<String, PathBuf>,
+    working_directory: PathBuf,
   ) -> RunResult<'static, CacheStatus> {
-    let mut hasher = blake3::Hasher::new();
-
-    serde_json::to_writer(&mut hasher, &key)
-      .map_err(|source| Error::CacheKeySerialize { source })?;
-
-    let hash = hasher.finalize();
+    let (hash, value) = self.key(&key)?;
 
     let path = self.entry(hash)?;
 
     if config.verbosity.grandiloquent() {
-      let json =
-        serde_json::to_string_pretty(&key).map_err(|source| Error::CacheKeySerialize { source })?;
+      let json = self.relativize(
+        &serde_json::to_string_pretty(&key)
+          .map_err(|source| Error::CacheKeySerialize { source })?,
+      );
       let stderr = config.color.stderr();
       eprintln!(
         "{}",
@@ -39,7 +46,7 @@ impl Cache {
       source,
     };
 
-    let file = fs::OpenOptions::new()
+    let mut file = fs::OpenOptions::new()
       .create(true)
       .read(true)
       .truncate(false)
@@ -49,34 +56,838 @@ impl Cache {
 
     file.lock().map_err(context)?;
 
-    if file.metadata().map_err(context)?.len() == 0 {
-      return Ok(CacheStatus::Miss(CacheLock {
+    let explain = config.explain_cache || config.verbosity.grandiloquent();
+
+    let miss = |file, outputs, restore_failed| {
+      if explain {
+        if restore_failed {
+          eprintln!(
+            "{}",
+            config.color.stderr().banner().paint(&format!(
+              "===> cache miss for recipe `{}`, outputs could not be restored",
+              key.recipe,
+            )),
+          );
+        } else {
+          self.explain(config, key.recipe, arguments, &path, &value)?;
+        }
+      }
+
+      Events::emit(Event::Cache {
+        hit: false,
+        key: hash.to_string(),
+        recipe: key.recipe,
+      });
+
+      Ok(CacheStatus::Miss(CacheLock {
+        arguments: arguments.into(),
         file,
-        path,
+        hash,
+        key: value.clone(),
+        outputs,
+        path: path.clone(),
         recipe: key.recipe.clone(),
-      }));
+        working_directory,
+      }))
+    };
+
+    let mut json = String::new();
+
+    (&file).read_to_string(&mut json).map_err(context)?;
+
+    if json.is_empty() {
+      return miss(file, outputs, false);
+    }
+
+    // Entries which cannot be parsed, for example those written by a different
+    // version of `just`, are treated as misses and overwritten.
+    let Ok(mut entry) = serde_json::from_str::<CacheEntry>(&json) else {
+      return miss(file, outputs, false);
+    };
+
+    if self.restore_outputs(config, &entry, &outputs)? {
+      entry.last_hit = Some(Self::now());
+      entry.write(&mut file, &path)?;
+      Events::emit(Event::Cache {
+        hit: true,
+        key: hash.to_string(),
+        recipe: key.recipe,
+      });
+      Ok(CacheStatus::Hit(CacheRecord {
+        hit: true,
+        key: hash,
+        outputs: entry.outputs,
+      }))
+    } else {
+      miss(file, outputs, true)
+    }
+  }
+
+  // Print the differences between `key` and the key of the most recently
+  // created entry for the same recipe and arguments.
+  fn explain(
+    &self,
+    config: &Config,
+    recipe: &Modulepath,
+    arguments: &[String],
+    path: &Path,
+    key: &serde_json::Value,
+  ) -> RunResult<'static> {
+    let stderr = config.color.stderr();
+
+    let Some(previous) = self.previous_key(recipe, arguments, path)? else {
+      eprintln!(
+        "{}",
+        stderr.banner().paint(&format!(
+          "===> cache miss for recipe `{recipe}`, no previous entry"
+        )),
+      );
+      return Ok(());
+    };
+
+    eprintln!(
+      "{}",
+      stderr
+        .banner()
+        .paint(&format!("===> cache miss for recipe `{recipe}`:")),
+    );
+
+    let null = serde_json::Value::Null;
+
+    let fields = previous
+      .as_object()
+      .into_iter()
+      .chain(key.as_object())
+      .flat_map(serde_json::Map::keys)
+      .collect::<BTreeSet<&String>>();
+
+    for field in fields {
+      let old = previous.get(field).unwrap_or(&null);
+      let new = key.get(field).unwrap_or(&null);
+
+      if old == new {
+        continue;
+      }
+
+      match field.as_str() {
+        "body" => {
+          eprintln!("  body changed:");
+          Self::explain_body(config, old, new);
+        }
+        "dependencies" => Self::explain_dependencies(old, new),
+        "environment" => Self::explain_map("environment variable", old, new, true),
+        "inputs" => Self::explain_map("input", old, new, false),
+        _ => eprintln!("  `{field}` changed from `{old}` to `{new}`"),
+      }
+    }
+
+    Ok(())
+  }
+
+  fn explain_body(config: &Config, old: &serde_json::Value, new: &serde_json::Value) {
+    use similar::{ChangeTag, TextDiff};
+
+    let lines = |body: &serde_json::Value| {
+      let mut lines = String::new();
+      for line in body.as_array().into_iter().flatten() {
+        if let Some(line) = line.as_str() {
+          lines.push_str(line);
+          lines.push('\n');
+        }
+      }
+      lines
+    };
+
+    let old = lines(old);
+    let new = lines(new);
+
+    let diff = TextDiff::configure()
+      .algorithm(similar::Algorithm::Patience)
+      .diff_lines(&old, &new);
+
+    for op in diff.ops() {
+      for change in diff.iter_changes(op) {
+        let (symbol, color) = match change.tag() {
+          ChangeTag::Delete => ("-", config.color.stderr().diff_deleted()),
+          ChangeTag::Equal => (" ", config.color.stderr()),
+          ChangeTag::Insert => ("+", config.color.stderr().diff_added()),
+        };
+
+        eprint!("    {}{symbol}{change}{}", color.prefix(), color.suffix());
+      }
+    }
+  }
+
+  // Dependencies are recorded as an array of fingerprints, so index them by
+  // recipe and arguments before comparing.
+  fn explain_dependencies(old: &serde_json::Value, new: &serde_json::Value) {
+    let index = |dependencies: &serde_json::Value| {
+      dependencies
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|dependency| {
+          let mut name = dependency["recipe"].as_str().unwrap_or_default().to_owned();
+          for argument in dependency["arguments"].as_array().into_iter().flatten() {
+            for element in argument
+              .as_array()
+              .map_or(slice::from_ref(argument), Vec::as_slice)
+            {
+              name.push(' ');
+              match element.as_str() {
+                Some(element) => name.push_str(element),
+                None => name.push_str(&element.to_string()),
+              }
+            }
+          }
+          (name, dependency.clone())
+        })
+        .collect::<serde_json::Map<String, serde_json::Value>>()
+        .into()
+    };
+
+    Self::explain_map("dependency", &index(old), &index(new), false);
+  }
+
+  fn explain_map(noun: &str, old: &serde_json::Value, new: &serde_json::Value, values: bool) {
+    let empty = serde_json::Map::new();
+    let old = old.as_object().unwrap_or(&empty);
+    let new = new.as_object().unwrap_or(&empty);
+
+    for name in old.keys().chain(new.keys()).collect::<BTreeSet<&String>>() {
+      match (old.get(name), new.get(name)) {
+        (None, Some(_)) => eprintln!("  {noun} `{name}` added"),
+        (Some(_), None) => eprintln!("  {noun} `{name}` removed"),
+        (Some(old), Some(new)) if old != new => {
+          if values {
+            eprintln!("  {noun} `{name}` changed from `{old}` to `{new}`");
+          } else {
+            eprintln!("  {noun} `{name}` changed");
+          }
+        }
+        _ => {}
+      }
+    }
+  }
+
+  // Find the key of the most recently created entry for `recipe` invoked with
+  // `arguments`, other than the entry at `current`. Entries are ordered by
+  // their recorded creation time, since hits rewrite entries and update their
+  // modification times. Entries which cannot be read, for example because
+  // they are being written, are skipped.
+  fn previous_key(
+    &self,
+    recipe: &Modulepath,
+    arguments: &[String],
+    current: &Path,
+  ) -> RunResult<'static, Option<serde_json::Value>> {
+    let context = |source| Error::FilesystemIo {
+      source,
+      path: self.path.clone(),
+    };
+
+    let mut previous = None;
+
+    for entry in fs::read_dir(&self.path).map_err(context)? {
+      let path = entry.map_err(context)?.path();
+
+      if path == current || path.extension().is_none_or(|extension| extension != "json") {
+        continue;
+      }
+
+      let Ok(json) = fs::read_to_string(&path) else {
+        continue;
+      };
+
+      let Ok(CacheEntry {
+        arguments: entry_arguments,
+        created,
+        key: Some(key),
+        recipe: entry_recipe,
+        ..
+      }) = serde_json::from_str(&json)
+      else {
+        continue;
+      };
+
+      if entry_recipe != *recipe || entry_arguments != arguments {
+        continue;
+      }
+
+      if previous
+        .as_ref()
+        .is_none_or(|(previous, _)| created > *previous)
+      {
+        previous = Some((created, key));
+      }
+    }
+
+    Ok(previous.map(|(_, key)| key))
+  }
+
+  // Restore recorded files for each declared output, returning false if any
+  // output cannot be restored.
+  fn restore_outputs(
+    &self,
+    config: &Config,
+    entry: &CacheEntry,
+    outputs: &BTreeMap<String, PathBuf>,
+  ) -> RunResult<'static, bool> {
+    for (output, output_path) in outputs {
+      let recorded = entry
+        .outputs
+        .iter()
+        .filter_map(|(file, hash)| {
+          if file == output {
+            Some((output_path.clone(), hash))
+          } else {
+            file
+              .strip_prefix(output.trim_end_matches('/'))
+              .and_then(|rest| rest.strip_prefix('/'))
+              .map(|rest| (output_path.join(rest), hash))
+          }
+        })
+        .collect::<Vec<(PathBuf, &blake3::Hash)>>();
+
+      if recorded.is_empty() {
+        if !filesystem::exists(output_path)? {
+          return Ok(false);
+        }
+        continue;
+      }
+
+      for (path, hash) in recorded {
+        if !self.restore(config, &path, hash)? {
+          return Ok(false);
+        }
+      }
+    }
+
+    Ok(true)
+  }
+
+  fn blob(&self, hash: &blake3::Hash) -> PathBuf {
+    self.path.join(Self::BLOBS).join(hash.to_hex().as_str())
+  }
+
+  // Restore the file at `path` from the blob store if its contents do not
+  // match `hash`, returning false if the blob is unavailable.
+  fn restore(&self, config: &Config, path: &Path, hash: &blake3::Hash) -> RunResult<'static, bool> {
+    if filesystem::is_file(path)? && Self::hash(path)? == *hash {
+      return Ok(true);
+    }
+
+    let blob = self.blob(hash);
+
+    if !filesystem::is_file(&blob)? {
+      return Ok(false);
+    }
+
+    let context = |source| Error::FilesystemIo {
+      path: path.into(),
+      source,
+    };
+
+    if path.symlink_metadata().is_ok() {
+      fs::remove_file(path).map_err(context)?;
+    }
+
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent).map_err(context)?;
+    }
+
+    fs::copy(&blob, path).map_err(context)?;
+
+    if config.verbosity.loquacious() {
+      eprintln!(
+        "{}",
+        config
+          .color
+          .stderr()
+          .banner()
+          .paint(&format!("===> restored cache output `{}`", path.display())),
+      );
     }
 
-    for output in outputs.values() {
-      if !filesystem::exists(output)? {
-        return Ok(CacheStatus::Miss(CacheLock {
-          file,
-          path,
-          recipe: key.recipe.clone(),
-        }));
+    Ok(true)
+  }
+
+  // Copy every file in `outputs` into the blob store, returning a map of
+  // output file paths to content hashes for recording in the cache entry.
+  pub(crate) fn store(
+    &self,
+    outputs: &BTreeMap<String, PathBuf>,
+    working_directory: &Path,
+  ) -> RunResult<'static, BTreeMap<String, blake3::Hash>> {
+    let mut files = BTreeMap::new();
+
+    for (output, path) in outputs {
+      if filesystem::is_file(path)? {
+        files.insert(output.clone(), path.clone());
+      } else {
+        self
+          .walk(
+            output,
+            path,
+            &GlobSet::empty(),
+            false,
+            working_directory,
+            |_relative| true,
+            &mut files,
+          )
+          .map_err(|source| Error::CacheOutputWalk { source })?;
       }
     }
 
-    Ok(CacheStatus::Hit)
+    let blobs = self.path.join(Self::BLOBS);
+
+    if !files.is_empty() {
+      fs::create_dir_all(&blobs).map_err(|source| Error::FilesystemIo {
+        source,
+        path: blobs.clone(),
+      })?;
+    }
+
+    let mut hashes = BTreeMap::new();
+
+    for (output, path) in files {
+      let hash = Self::hash(&path)?;
+
+      let blob = self.blob(&hash);
+
+      if !filesystem::exists(&blob)? {
+        let tmp = blobs.join(format!("{}.tmp", uuid::Uuid::new_v4()));
+
+        fs::copy(&path, &tmp).map_err(|source| Error::FilesystemIo {
+          source,
+          path: path.clone(),
+        })?;
+
+        fs::rename(&tmp, &blob).map_err(|source| Error::FilesystemIo {
+          source,
+          path: blob.clone(),
+        })?;
+      }
+
+      hashes.insert(output, hash);
+    }
+
+    Ok(hashes)
   }
 
-  pub(crate) fn new(search: &Search) -> Self {
+  // Read cache entries, skipping those which cannot be read, for example
+  // because they are locked, or which are empty or cannot be parsed.
+  fn entries(&self) -> RunResult<'static, Vec<(u64, CacheEntry)>> {
+    let mut entries = Vec::new();
+
+    for (path, size) in Self::files(&self.path)? {
+      if path.extension().is_none_or(|extension| extension != "json") {
+        continue;
+      }
+
+      let Ok(json) = fs::read_to_string(&path) else {
+        continue;
+      };
+
+      if let Ok(entry) = serde_json::from_str::<CacheEntry>(&json) {
+        entries.push((size, entry));
+      }
+    }
+
+    Ok(entries)
+  }
+
+  // Map blob names to sizes.
+  fn blobs(&self) -> RunResult<'static, BTreeMap<String, u64>> {
+    Ok(
+      Self::files(&self.path.join(Self::BLOBS))?
+        .into_iter()
+        .filter_map(|(path, size)| Some((path.file_name()?.to_str()?.to_owned(), size)))
+        .collect(),
+    )
+  }
+
+  // List the files in `dir` along with their sizes. A missing directory is
+  // treated as empty.
+  fn files(dir: &Path) -> RunResult<'static, Vec<(PathBuf, u64)>> {
+    let context = |source| Error::FilesystemIo {
+      source,
+      path: dir.into(),
+    };
+
+    let entries = match fs::read_dir(dir) {
+      Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+      result => result.map_err(context)?,
+    };
+
+    let mut files = Vec::new();
+
+    for entry in entries {
+      let entry = entry.map_err(context)?;
+
+      let metadata = entry.metadata().map_err(context)?;
+
+      if metadata.is_file() {
+        files.push((entry.path(), metadata.len()));
+      }
+    }
+
+    files.sort();
+
+    Ok(files)
+  }
+
+  pub(crate) fn list(&self) -> RunResult<'static> {
+    let mut entries = self
+      .entries()?
+      .into_iter()
+      .map(|(_size, entry)| entry)
+      .collect::<Vec<CacheEntry>>();
+
+    entries.sort_by(|a, b| (&a.recipe, &a.arguments).cmp(&(&b.recipe, &b.arguments)));
+
+    let timestamp = |seconds: u64| {
+      i64::try_from(seconds)
+        .ok()
+        .and_then(|seconds| Local.timestamp_opt(seconds, 0).single())
+        .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default()
+    };
+
+    for entry in entries {
+      let mut invocation = entry.recipe.to_string();
+
+      for argument in &entry.arguments {
+        invocation.push(' ');
+        invocation.push_str(argument);
+      }
+
+      println!(
+        "{invocation}: created {}, last hit {}",
+        timestamp(entry.created),
+        entry.last_hit.map_or_else(|| "never".into(), timestamp),
+      );
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn stats(&self) -> RunResult<'static> {
+    let blobs = self.blobs()?;
+
+    let entries = self.entries()?;
+
+    let mut recipes = BTreeMap::<&Modulepath, (usize, u64, BTreeSet<String>)>::new();
+
+    for (size, entry) in &entries {
+      let (count, total, hashes) = recipes.entry(&entry.recipe).or_default();
+      *count += 1;
+      *total += size;
+      hashes.extend(entry.outputs.values().map(|hash| hash.to_hex().to_string()));
+    }
+
+    let size = entries.iter().map(|(size, _entry)| size).sum::<u64>() + blobs.values().sum::<u64>();
+
+    println!("cache directory: {}", self.path.display());
+    println!("entries: {}", entries.len());
+    println!("size: {}", Size(size));
+
+    for (recipe, (count, total, hashes)) in recipes {
+      let outputs = hashes
+        .iter()
+        .filter_map(|hash| blobs.get(hash))
+        .sum::<u64>();
+
+      println!(
+        "  {recipe}: {}, {}",
+        Count::numbered_irregular("entry", "entries", count),
+        Size(total + outputs),
+      );
+    }
+
+    Ok(())
+  }
+
+  // Evict entries which were last used before `older_than`, and then evict
+  // least recently used entries until the size of the cache is no larger than
+  // `max_size`. Blobs which are no longer referenced by an entry are removed,
+  // unless an entry is locked by a running invocation, which may be in the
+  // process of storing outputs.
+  pub(crate) fn prune(
+    &self,
+    config: &Config,
+    max_size: Option<Size>,
+    older_than: Option<Timespan>,
+  ) -> RunResult<'static> {
+    struct Locked {
+      blobs: Vec<String>,
+      file: File,
+      last_used: u64,
+      path: PathBuf,
+      size: u64,
+    }
+
+    let blobs = self.blobs()?;
+
+    let mut busy = false;
+    let mut entries = Vec::new();
+
+    for (path, size) in Self::files(&self.path)? {
+      if path.extension().is_none_or(|extension| extension != "json") {
+        continue;
+      }
+
+      let context = |source| Error::FilesystemIo {
+        source,
+        path: path.clone(),
+      };
+
+      let file = fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(&path)
+        .map_err(context)?;
+
+      match file.try_lock() {
+        Ok(()) => {}
+        Err(TryLockError::WouldBlock) => {
+          busy = true;
+          continue;
+        }
+        Err(TryLockError::Error(err)) => return Err(context(err)),
+      }
+
+      let mut json = String::new();
+
+      (&file).read_to_string(&mut json).map_err(context)?;
+
+      let (last_used, blobs) = if let Ok(entry) = serde_json::from_str::<CacheEntry>(&json) {
+        (
+          entry.last_used(),
+          entry
+            .outputs
+            .values()
+            .map(|hash| hash.to_hex().to_string())
+            .collect(),
+        )
+      } else {
+        let modified = file
+          .metadata()
+          .and_then(|metadata| metadata.modified())
+          .map_err(context)?;
+
+        (
+          modified
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default(),
+          Vec::new(),
+        )
+      };
+
+      entries.push(Locked {
+        blobs,
+        file,
+        last_used,
+        path,
+        size,
+      });
+    }
+
+    entries.sort_by_key(|entry| entry.last_used);
+
+    let mut evict = vec![false; entries.len()];
+
+    if let Some(Timespan(older_than)) = older_than {
+      let cutoff = Self::now().saturating_sub(older_than.as_secs());
+
+      for (evict, entry) in evict.iter_mut().zip(&entries) {
+        *evict = entry.last_used < cutoff;
+      }
+    }
+
+    let mut references = BTreeMap::<&str, usize>::new();
+
+    for (entry, _) in entries.iter().zip(&evict).filter(|(_, evict)| !**evict) {
+      for blob in &entry.blobs {
+        *references.entry(blob).or_default() += 1;
+      }
+    }
+
+    if let Some(Size(max_size)) = max_size {
+      let mut size = entries
+        .iter()
+        .zip(&evict)
+        .filter(|(_, evict)| !**evict)
+        .map(|(entry, _)| entry.size)
+        .sum::<u64>()
+        + references
+          .keys()
+          .filter_map(|blob| blobs.get(*blob))
+          .sum::<u64>();
+
+      for (entry, evict) in entries.iter().zip(evict.iter_mut()) {
+        if size <= max_size {
+          break;
+        }
+
+        if *evict {
+          continue;
+        }
+
+        *evict = true;
+
+        size -= entry.size;
+
+        for blob in &entry.blobs {
+          let count = references.get_mut(blob.as_str()).unwrap();
+          *count -= 1;
+          if *count == 0 {
+            references.remove(blob.as_str());
+            size -= blobs.get(blob).copied().unwrap_or_default();
+          }
+        }
+      }
+    }
+
+    let mut freed = 0;
+    let mut removed_entries = 0;
+    let mut removed_blobs = 0;
+
+    let referenced = references
+      .into_keys()
+      .map(str::to_owned)
+      .collect::<BTreeSet<String>>();
+
+    for (entry, evict) in entries.into_iter().zip(evict) {
+      if !evict {
+        continue;
+      }
+
+      let Locked {
+        file, path, size, ..
+      } = entry;
+
+      drop(file);
+
+      fs::remove_file(&path).map_err(|source| Error::FilesystemIo { source, path })?;
+
+      freed += size;
+      removed_entries += 1;
+    }
+
+    if !busy {
+      for (blob, size) in blobs {
+        if referenced.contains(&blob) {
+          continue;
+        }
+
+        let path = self.path.join(Self::BLOBS).join(blob);
+
+        fs::remove_file(&path).map_err(|source| Error::FilesystemIo { source, path })?;
+
+        freed += size;
+        removed_blobs += 1;
+      }
+    }
+
+    if config.verbosity.loud() {
+      eprintln!(
+        "removed {} and {}, freeing {}",
+        Count::numbered_irregular("cache entry", "cache entries", removed_entries),
+        Count::numbered("blob", removed_blobs),
+        Size(freed),
+      );
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn now() -> u64 {
+    SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map(|duration| duration.as_secs())
+      .unwrap_or_default()
+  }
+
+  pub(crate) fn new(config: &Config, search: &Search, settings: &Settings) -> Self {
+    let root = search.justfile_parent();
+
     Self {
-      path: Self::dir(search),
+      path: Self::dir(config, search, settings),
       initialized: Mutex::new(false),
+      // The root is matched in serialized keys, so it is escaped like a JSON
+      // string, without the quotes.
+      root: root
+        .parent()
+        .is_some()
+        .then(|| serde_json::to_string(root).ok())
+        .flatten()
+        .map(|json| json[1..json.len() - 1].to_owned()),
+      watched: Mutex::new(BTreeSet::new()),
     }
   }
 
+  // Serialize and hash `key`, replacing the directory containing the justfile
+  // in every string with `{{justfile_directory()}}`, so that keys do not
+  // change when the project is moved.
+  pub(crate) fn key(
+    &self,
+    key: &impl Serialize,
+  ) -> RunResult<'static, (blake3::Hash, serde_json::Value)> {
+    let json = self.relativize(
+      &serde_json::to_string(key).map_err(|source| Error::CacheKeySerialize { source })?,
+    );
+
+    let value =
+      serde_json::from_str(&json).map_err(|source| Error::CacheKeySerialize { source })?;
+
+    Ok((blake3::hash(json.as_bytes()), value))
+  }
+
+  // Replace occurrences of the directory containing the justfile in `json`
+  // which are not followed by more of a file name, so that `/foo` is replaced
+  // in `/foo/bar`, but not in `/foobar`.
+  fn relativize(&self, json: &str) -> String {
+    let Some(root) = &self.root else {
+      return json.into();
+    };
+
+    let mut relativized = String::new();
+    let mut end = 0;
+
+    // The root is only replaced if it is a whole path, and not part of a
+    // longer path, like `/bar/foo` or `/foobar` with root `/foo`, so it must
+    // start the string or follow whitespace, a quote, or a separator, and
+    // must not be followed by a character which may continue a file name.
+    for (i, _) in json.match_indices(root.as_str()) {
+      let before = &json[..i];
+
+      let start = before.is_empty()
+        || before.ends_with(|c: char| {
+          c.is_whitespace() || matches!(c, '"' | '\'' | '`' | '(' | ',' | ':' | ';' | '=')
+        })
+        || before.ends_with("\\n")
+        || before.ends_with("\\r")
+        || before.ends_with("\\t");
+
+      let after = json[i + root.len()..]
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphanumeric() || matches!(c, '-' | '.' | '_'));
+
+      if start && !after {
+        relativized.push_str(&json[end..i]);
+        relativized.push_str(Self::ROOT);
+        end = i + root.len();
+      }
+    }
+
+    relativized.push_str(&json[end..]);
+
+    relativized
+  }
+
   fn entry(&self, key: blake3::Hash) -> RunResult<'static, PathBuf> {
     let mut initialized = self.initialized.lock().unwrap();
 
@@ -91,15 +902,84 @@ impl Cache {
     Ok(self.path.join(format!("{key}.json")))
   }
 
+  // Get the path of the file used to hold global lock `name`, creating the
+  // lock directory if it does not exist.
+  pub(crate) fn lock(&self, name: &str) -> RunResult<'static, PathBuf> {
+    let dir = self.path.join(Self::LOCKS);
+
+    fs::create_dir_all(&dir).map_err(|source| Error::FilesystemIo {
+      source,
+      path: dir.clone(),
+    })?;
+
+    Ok(dir.join(format!("{}.lock", blake3::hash(name.as_bytes()))))
+  }
+
   pub(crate) fn inputs(
-    value: Value,
+    &self,
+    inputs: Value,
+    exclude: Value,
+    gitignore: bool,
     working_directory: &Path,
   ) -> RunResult<'static, BTreeMap<String, blake3::Hash>> {
-    let mut inputs = BTreeMap::new();
+    let mut builder = GlobSetBuilder::new();
+
+    for pattern in &exclude {
+      builder.add(
+        Self::glob(pattern).map_err(|source| Error::CacheInputPattern {
+          pattern: pattern.into(),
+          source,
+        })?,
+      );
+    }
+
+    let exclude = builder.build().map_err(|source| Error::CacheInputPattern {
+      pattern: exclude.join(),
+      source,
+    })?;
+
+    let mut files = BTreeMap::new();
+
+    for input in inputs {
+      if input.contains(GLOB_METACHARACTERS) {
+        let (base, pattern) = Self::split_pattern(&input);
+
+        let matcher = Self::glob(pattern)
+          .map_err(|source| Error::CacheInputPattern {
+            pattern: pattern.into(),
+            source,
+          })?
+          .compile_matcher();
+
+        let root = working_directory.join(base);
+
+        self.watch(&root);
+
+        let matched = filesystem::exists(&root)?
+          && self
+            .walk(
+              base,
+              &root,
+              &exclude,
+              gitignore,
+              working_directory,
+              |relative| matcher.is_match(relative),
+              &mut files,
+            )
+            .map_err(|source| Error::CacheInputWalk { source })?
+            > 0;
+
+        if !matched {
+          return Err(Error::CacheInputUnmatched { pattern: input });
+        }
+
+        continue;
+      }
 
-    for input in value {
       let path = working_directory.join(&input);
 
+      self.watch(&path);
+
       let metadata = match fs::metadata(&path) {
         Ok(metadata) => metadata,
         Err(source) if source.kind() == io::ErrorKind::NotFound => {
@@ -109,25 +989,219 @@ impl Cache {
       };
 
       if metadata.is_dir() {
-        return Err(Error::CacheInputDirectory { path });
+        self
+          .walk(
+            &input,
+            &path,
+            &exclude,
+            gitignore,
+            working_directory,
+            |_relative| true,
+            &mut files,
+          )
+          .map_err(|source| Error::CacheInputWalk { source })?;
+      } else {
+        files.insert(input, path);
       }
+    }
 
-      let mut hasher = blake3::Hasher::new();
+    files
+      .into_iter()
+      .map(|(input, path)| Ok((input, Self::hash(&path)?)))
+      .collect()
+  }
 
-      hasher
-        .update_mmap_rayon(&path)
-        .map_err(|source| Error::FilesystemIo {
-          source,
-          path: path.clone(),
-        })?;
+  // Build a glob in which `*` does not match `/`. Used for both cache inputs
+  // and `[stale]` paths, so that they agree on which files match.
+  pub(crate) fn glob(pattern: &str) -> Result<Glob, globset::Error> {
+    GlobBuilder::new(pattern).literal_separator(true).build()
+  }
+
+  // Split `pattern` into the longest leading run of literal path components and
+  // the remaining glob, so that only the literal prefix needs to be walked.
+  pub(crate) fn split_pattern(pattern: &str) -> (&str, &str) {
+    let mut split = None;
+
+    for (i, c) in pattern.char_indices() {
+      if c == '/' {
+        split = Some(i);
+      } else if GLOB_METACHARACTERS.contains(&c) {
+        break;
+      }
+    }
+
+    match split {
+      None => ("", pattern),
+      Some(0) => ("/", &pattern[1..]),
+      Some(i) => (&pattern[..i], &pattern[i + 1..]),
+    }
+  }
+
+  fn hash(path: &Path) -> RunResult<'static, blake3::Hash> {
+    let mut hasher = blake3::Hasher::new();
+
+    hasher
+      .update_mmap_rayon(path)
+      .map_err(|source| Error::FilesystemIo {
+        source,
+        path: path.into(),
+      })?;
+
+    Ok(hasher.finalize())
+  }
+
+  // Walk `root` in sorted order, collecting every file whose path relative to
+  // `root` satisfies `filter`, and returning the number of files collected.
+  // Paths matching `exclude` relative to the working directory, and the cache
+  // directory itself, are skipped.
+  pub(crate) fn walk(
+    &self,
+    base: &str,
+    root: &Path,
+    exclude: &GlobSet,
+    gitignore: bool,
+    working_directory: &Path,
+    filter: impl Fn(&Path) -> bool,
+    files: &mut BTreeMap<String, PathBuf>,
+  ) -> Result<usize, ignore::Error> {
+    let cache = self.path.canonicalize().ok();
+    let exclude = exclude.clone();
+    let working_directory = working_directory.to_owned();
+
+    let walk = WalkBuilder::new(root)
+      .standard_filters(false)
+      .follow_links(true)
+      .git_exclude(gitignore)
+      .git_ignore(gitignore)
+      .parents(gitignore)
+      .require_git(false)
+      .sort_by_file_path(Path::cmp)
+      .filter_entry(move |entry| {
+        let path = entry.path();
+
+        if exclude.is_match(path.strip_prefix(&working_directory).unwrap_or(path)) {
+          return false;
+        }
+
+        !(cache.is_some()
+          && entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_dir())
+          && path.canonicalize().ok() == cache)
+      })
+      .build();
+
+    let mut count = 0;
+
+    for entry in walk {
+      let entry = entry?;
+
+      if !entry
+        .file_type()
+        .is_some_and(|file_type| file_type.is_file())
+      {
+        continue;
+      }
+
+      let relative = entry.path().strip_prefix(root).unwrap();
+
+      if !filter(relative) {
+        continue;
+      }
+
+      let relative = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<String>>()
+        .join("/");
 
-      inputs.insert(input, hasher.finalize());
+      let key = if base.is_empty() {
+        relative
+      } else {
+        format!("{}/{relative}", base.trim_end_matches('/'))
+      };
+
+      files.insert(key, entry.into_path());
+
+      count += 1;
     }
 
-    Ok(inputs)
+    Ok(count)
+  }
+
+  pub(crate) fn dir(config: &Config, search: &Search, settings: &Settings) -> PathBuf {
+    if let Some(path) = &config.cache_directory {
+      config.invocation_directory.join(path).clean()
+    } else if let Some(path) = &settings.cache_directory {
+      search.justfile_parent().join(path).clean()
+    } else {
+      search.justfile_parent().join(DIR)
+    }
+  }
+
+  pub(crate) fn path(&self) -> &Path {
+    &self.path
+  }
+
+  // Record `path`, an input of a cached recipe or a source of a stale recipe,
+  // to be watched by `--watch`.
+  pub(crate) fn watch(&self, path: &Path) {
+    self.watched.lock().unwrap().insert(path.clean());
+  }
+
+  pub(crate) fn watched(&self) -> BTreeSet<PathBuf> {
+    self.watched.lock().unwrap().clone()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[track_caller]
+  fn case(pattern: &str, base: &str, rest: &str) {
+    assert_eq!(Cache::split_pattern(pattern), (base, rest));
   }
 
-  pub(crate) fn dir(search: &Search) -> PathBuf {
-    search.justfile_parent().join(DIR)
+  #[test]
+  fn split_pattern() {
+    case("*.rs", "", "*.rs");
+    case("**/*.rs", "", "**/*.rs");
+    case("src/*.rs", "src", "*.rs");
+    case("src/**/*.rs", "src", "**/*.rs");
+    case("src/foo/b?r/*.rs", "src/foo", "b?r/*.rs");
+    case("/src/*.rs", "/src", "*.rs");
+    case("/*.rs", "/", "*.rs");
+  }
+
+  #[test]
+  fn relativize() {
+    #[track_caller]
+    fn case(string: &str, expected: &str) {
+      let cache = Cache {
+        initialized: Mutex::new(false),
+        path: PathBuf::new(),
+        root: Some("/foo".into()),
+        watched: Mutex::new(BTreeSet::new()),
+      };
+
+      assert_eq!(cache.relativize(string), expected);
+    }
+
+    case("", "");
+    case(r#""/foo""#, r#""{{justfile_directory()}}""#);
+    case("/foo/bar", "{{justfile_directory()}}/bar");
+    case("cd /foo && ls", "cd {{justfile_directory()}} && ls");
+    case(
+      "/foo/a:/foo/b",
+      "{{justfile_directory()}}/a:{{justfile_directory()}}/b",
+    );
+    case("/foobar", "/foobar");
+    case("/foo.txt", "/foo.txt");
+    case("/bar/foo", "/bar/foo");
+    case("/bar/foo/baz", "/bar/foo/baz");
+    case("FOO=/foo", "FOO={{justfile_directory()}}");
+    case(r"\n/foo", r"\n{{justfile_directory()}}");
+    case("/foo/foo", "{{justfile_directory()}}/foo");
   }
 }
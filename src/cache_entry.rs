@@ -2,5 +2,37 @@ use super::*;
 
 #[derive(Deserialize, Serialize)]
 pub(crate) struct CacheEntry {
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub(crate) arguments: Vec<String>,
+  #[serde(default)]
+  pub(crate) created: u64,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) key: Option<serde_json::Value>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) last_hit: Option<u64>,
+  #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+  pub(crate) outputs: BTreeMap<String, blake3::Hash>,
   pub(crate) recipe: Modulepath,
 }
+
+impl CacheEntry {
+  pub(crate) fn last_used(&self) -> u64 {
+    self.last_hit.unwrap_or_default().max(self.created)
+  }
+
+  pub(crate) fn write(&self, file: &mut File, path: &Path) -> RunResult<'static> {
+    let context = |source| Error::FilesystemIo {
+      source,
+      path: path.into(),
+    };
+
+    file.set_len(0).map_err(context)?;
+
+    file.rewind().map_err(context)?;
+
+    serde_json::to_writer(file, self).map_err(|source| Error::CacheEntryWrite {
+      source,
+      path: path.into(),
+    })
+  }
+}
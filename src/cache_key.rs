@@ -3,6 +3,7 @@ use super::*;
 #[derive(Serialize)]
 pub(crate) struct CacheKey<'a> {
   pub(crate) body: &'a [String],
+  pub(crate) dependencies: &'a [Fingerprint],
   pub(crate) environment: &'a Environment,
   pub(crate) executor: &'a Executor<'a>,
   pub(crate) extension: Option<&'a str>,
@@ -10,5 +11,6 @@ pub(crate) struct CacheKey<'a> {
   pub(crate) inputs: Option<BTreeMap<String, blake3::Hash>>,
   pub(crate) positional: Option<&'a [String]>,
   pub(crate) recipe: &'a Modulepath,
+  pub(crate) sigils: Option<&'a [BTreeSet<Sigil>]>,
   pub(crate) working_directory: Option<&'a Path>,
 }
@@ -1,29 +1,34 @@
 use super::*;
 
 pub(crate) struct CacheLock {
+  pub(crate) arguments: Vec<String>,
   pub(crate) file: File,
+  pub(crate) hash: blake3::Hash,
+  pub(crate) key: serde_json::Value,
+  pub(crate) outputs: BTreeMap<String, PathBuf>,
   pub(crate) path: PathBuf,
   pub(crate) recipe: Modulepath,
+  pub(crate) working_directory: PathBuf,
 }
 
 impl CacheLock {
-  pub(crate) fn save(mut self) -> RunResult<'static> {
-    let context = |source| Error::FilesystemIo {
-      source,
-      path: self.path.clone(),
-    };
+  pub(crate) fn save(mut self, cache: &Cache) -> RunResult<'static, CacheRecord> {
+    let outputs = cache.store(&self.outputs, &self.working_directory)?;
 
-    self.file.set_len(0).map_err(context)?;
-
-    self.file.rewind().map_err(context)?;
-
-    let entry = CacheEntry {
+    CacheEntry {
+      arguments: self.arguments,
+      created: Cache::now(),
+      key: Some(self.key),
+      last_hit: None,
+      outputs: outputs.clone(),
       recipe: self.recipe,
-    };
+    }
+    .write(&mut self.file, &self.path)?;
 
-    serde_json::to_writer(&mut self.file, &entry).map_err(|source| Error::CacheEntryWrite {
-      source,
-      path: self.path,
+    Ok(CacheRecord {
+      hit: false,
+      key: self.hash,
+      outputs,
     })
   }
 }
@@ -0,0 +1,9 @@
+use super::*;
+
+// The cache key and output hashes of a cached invocation which either hit the
+// cache or was run and saved.
+pub(crate) struct CacheRecord {
+  pub(crate) hit: bool,
+  pub(crate) key: blake3::Hash,
+  pub(crate) outputs: BTreeMap<String, blake3::Hash>,
+}
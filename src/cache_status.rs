@@ -1,6 +1,6 @@
 use super::*;
 
 pub(crate) enum CacheStatus {
-  Hit,
+  Hit(CacheRecord),
   Miss(CacheLock),
 }
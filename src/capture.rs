@@ -0,0 +1,197 @@
+use super::*;
+
+// The output of a recipe's commands, piped instead of inherited when the
+// output mode is not `interleaved`. In `prefixed` mode, each line is written
+// with a label naming the recipe as soon as it is complete, and in `grouped`
+// mode, output is buffered and written all at once when the recipe finishes.
+// With `--junit`, the end of standard error is also kept for the report, and
+// in `interleaved` mode, standard error alone is piped and written as soon as
+// it is read.
+pub(crate) struct Capture {
+  buffer: Vec<(bool, Vec<u8>)>,
+  labels: [String; 2],
+  lines: [Vec<u8>; 2],
+  mode: OutputMode,
+  tail: Option<Tail>,
+}
+
+impl Capture {
+  pub(crate) fn new(config: &Config, mode: OutputMode, recipe: &Modulepath) -> Option<Self> {
+    if config.verbosity.quiet() || (mode == OutputMode::Interleaved && config.junit.is_none()) {
+      return None;
+    }
+
+    let label = format!("[{recipe}]");
+
+    let color = config.color.label(&label);
+
+    Some(Self {
+      buffer: Vec::new(),
+      labels: [
+        format!("{} ", color.stdout().paint(&label)),
+        format!("{} ", color.stderr().paint(&label)),
+      ],
+      lines: [Vec::new(), Vec::new()],
+      mode,
+      tail: config.junit.is_some().then(Tail::new),
+    })
+  }
+
+  // Write a message from `just` itself, such as an echoed command, to
+  // standard error, in the same way as the output of a recipe's commands.
+  pub(crate) fn echo(&mut self, message: &str) {
+    self.write(true, format!("{message}\n").as_bytes());
+  }
+
+  // Write a marker, such as those which fold the output of recipes in the logs
+  // of CI services, to standard error, without a label, but in the same group
+  // as the output of the recipe's commands.
+  pub(crate) fn mark(&mut self, marker: &str) {
+    let marker = format!("{marker}\n");
+
+    if self.mode == OutputMode::Grouped {
+      self.write(true, marker.as_bytes());
+    } else {
+      self.terminate();
+      Self::stream(true).write_all(marker.as_bytes()).ok();
+    }
+  }
+
+  // Write any remaining output, and return the captured end of standard error.
+  pub(crate) fn finish(mut self) -> Option<Tail> {
+    self.terminate();
+
+    if !self.buffer.is_empty() {
+      // Both streams are locked so that output from other recipes cannot
+      // appear in the middle of the group.
+      let mut stdout = io::stdout().lock();
+      let mut stderr = io::stderr().lock();
+
+      for (is_stderr, bytes) in &self.buffer {
+        if *is_stderr {
+          stdout.flush().ok();
+          stderr.write_all(bytes).ok();
+        } else {
+          stdout.write_all(bytes).ok();
+        }
+      }
+
+      stdout.flush().ok();
+    }
+
+    self.tail
+  }
+
+  fn print(&self, stderr: bool, line: &[u8]) {
+    let mut stream = Self::stream(stderr);
+    stream
+      .write_all(self.labels[usize::from(stderr)].as_bytes())
+      .ok();
+    stream.write_all(line).ok();
+    stream.flush().ok();
+  }
+
+  // Run `command` like `CommandExt::status_guard_deadline`, capturing its
+  // output.
+  pub(crate) fn run(
+    &mut self,
+    mut command: Command,
+    deadline: Option<Instant>,
+  ) -> (io::Result<ExitStatus>, Option<Signal>, bool) {
+    if self.mode != OutputMode::Interleaved {
+      command.stdout(Stdio::piped());
+    }
+
+    command.stderr(Stdio::piped());
+
+    SignalHandler::spawn_with_deadline(command, deadline, |mut child| {
+      let stdout = child.stdout.take();
+      let stderr = child.stderr.take();
+
+      let capture = Mutex::new(&mut *self);
+
+      let copy = &|stderr: bool, mut reader: Box<dyn Read + Send>| -> io::Result<()> {
+        let mut buffer = [0; 4096];
+
+        loop {
+          let n = match reader.read(&mut buffer) {
+            Ok(0) => return Ok(()),
+            Ok(n) => n,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+          };
+
+          let mut capture = capture.lock().unwrap();
+
+          if stderr && let Some(tail) = &mut capture.tail {
+            tail.push(&buffer[..n]);
+          }
+
+          capture.write(stderr, &buffer[..n]);
+        }
+      };
+
+      thread::scope(|scope| {
+        let stdout = stdout.map(|stdout| scope.spawn(move || copy(false, Box::new(stdout))));
+
+        if let Some(stderr) = stderr {
+          copy(true, Box::new(stderr))?;
+        }
+
+        if let Some(stdout) = stdout {
+          stdout.join().unwrap()?;
+        }
+
+        io::Result::Ok(())
+      })?;
+
+      child.wait()
+    })
+  }
+
+  fn stream(stderr: bool) -> Box<dyn Write> {
+    if stderr {
+      Box::new(io::stderr().lock())
+    } else {
+      Box::new(io::stdout().lock())
+    }
+  }
+
+  // Write lines which have not been terminated by a newline.
+  fn terminate(&mut self) {
+    for stderr in [false, true] {
+      let mut line = mem::take(&mut self.lines[usize::from(stderr)]);
+
+      if !line.is_empty() {
+        line.push(b'\n');
+        self.print(stderr, &line);
+      }
+    }
+  }
+
+  fn write(&mut self, stderr: bool, bytes: &[u8]) {
+    match self.mode {
+      OutputMode::Grouped => match self.buffer.last_mut() {
+        Some((last, buffer)) if *last == stderr => buffer.extend_from_slice(bytes),
+        _ => self.buffer.push((stderr, bytes.into())),
+      },
+      OutputMode::Interleaved => {
+        let mut stream = Self::stream(stderr);
+        stream.write_all(bytes).ok();
+        stream.flush().ok();
+      }
+      OutputMode::Prefixed => {
+        let mut line = mem::take(&mut self.lines[usize::from(stderr)]);
+
+        line.extend_from_slice(bytes);
+
+        while let Some(end) = line.iter().position(|&byte| byte == b'\n') {
+          self.print(stderr, &line[..=end]);
+          line.drain(..=end);
+        }
+
+        self.lines[usize::from(stderr)] = line;
+      }
+    }
+  }
+}
@@ -0,0 +1,127 @@
+use {
+  super::*,
+  std::sync::atomic::{self, AtomicU64},
+};
+
+// Format of markers which fold the output of each recipe into a collapsible
+// section in the logs of a CI service, and annotate failed recipes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, ValueEnum)]
+pub(crate) enum CiFormat {
+  #[default]
+  Auto,
+  Github,
+  Gitlab,
+  None,
+}
+
+impl CiFormat {
+  // Resolve `Auto` to the format of the CI service `just` is running under,
+  // if any, using the environment variables which each service sets.
+  pub(crate) fn resolve(self) -> Self {
+    if self != Self::Auto {
+      return self;
+    }
+
+    let set = |key| env::var_os(key).is_some_and(|value| value == "true");
+
+    if set("GITHUB_ACTIONS") {
+      Self::Github
+    } else if set("GITLAB_CI") {
+      Self::Gitlab
+    } else {
+      Self::None
+    }
+  }
+
+  // Get the name of the section containing the output of the recipe named
+  // `name`, and the marker which opens it.
+  pub(crate) fn start(self, name: &str) -> Option<(String, String)> {
+    static SECTIONS: AtomicU64 = AtomicU64::new(0);
+
+    match self {
+      Self::Auto | Self::None => None,
+      Self::Github => Some((name.into(), format!("::group::{name}"))),
+      Self::Gitlab => {
+        // Section names may only contain letters, digits, `_`, `.`, and `-`,
+        // and are numbered so that recipes which run more than once have
+        // distinct sections.
+        let section = format!(
+          "{}_{}",
+          name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '-') {
+              c
+            } else {
+              '_'
+            })
+            .collect::<String>(),
+          SECTIONS.fetch_add(1, atomic::Ordering::Relaxed),
+        );
+
+        let marker = format!(
+          "\x1b[0Ksection_start:{}:{section}[collapsed=true]\r\x1b[0K{name}",
+          Self::timestamp(),
+        );
+
+        Some((section, marker))
+      }
+    }
+  }
+
+  // Get the marker which closes `section`.
+  pub(crate) fn finish(self, section: &str) -> String {
+    match self {
+      Self::Auto | Self::None => String::new(),
+      Self::Github => "::endgroup::".into(),
+      Self::Gitlab => format!(
+        "\x1b[0Ksection_end:{}:{section}\r\x1b[0K",
+        Self::timestamp()
+      ),
+    }
+  }
+
+  // Annotate the failure of a recipe with `message`, at `line` of `path`,
+  // where `line` starts at 1. GitHub expects paths relative to the root of
+  // the repository, so `path` is made relative to the workspace directory.
+  pub(crate) fn error(self, path: &Path, line: usize, title: &str, message: &str) {
+    fn escape(s: &str, property: bool) -> String {
+      let mut escaped = String::new();
+
+      for c in s.chars() {
+        match c {
+          '%' => escaped.push_str("%25"),
+          '\r' => escaped.push_str("%0D"),
+          '\n' => escaped.push_str("%0A"),
+          ':' if property => escaped.push_str("%3A"),
+          ',' if property => escaped.push_str("%2C"),
+          _ => escaped.push(c),
+        }
+      }
+
+      escaped
+    }
+
+    if self == Self::Github {
+      let workspace = env::var_os("GITHUB_WORKSPACE");
+
+      let path = workspace
+        .as_ref()
+        .and_then(|workspace| path.strip_prefix(workspace).ok())
+        .unwrap_or(path);
+
+      eprintln!(
+        "::error file={},line={line},title={}::{}",
+        escape(&path.to_string_lossy(), true),
+        escape(title, true),
+        escape(message, false),
+      );
+    }
+  }
+
+  fn timestamp() -> u64 {
+    SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map(|duration| duration.as_secs())
+      .unwrap_or_default()
+  }
+}
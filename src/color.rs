@@ -97,6 +97,16 @@ impl Color {
     self.indentation
   }
 
+  // Choose a color for `label` which is stable between runs, so that labels
+  // with different text are likely to have different colors.
+  pub(crate) fn label(self, label: &str) -> Self {
+    const COLORS: [nu_ansi_term::Color; 5] = [Blue, Cyan, Green, Purple, Yellow];
+
+    let index = label.bytes().map(usize::from).sum::<usize>() % COLORS.len();
+
+    self.restyle(Style::new().fg(COLORS[index]).bold())
+  }
+
   pub(crate) fn message(self) -> Self {
     self.restyle(Style::new().bold())
   }
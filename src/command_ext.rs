@@ -10,6 +10,11 @@ pub(crate) trait CommandExt {
   fn shell_arg(&mut self, arg: impl AsRef<OsStr>) -> &mut Command;
 
   fn status_guard(self) -> (io::Result<ExitStatus>, Option<Signal>);
+
+  fn status_guard_deadline(
+    self,
+    deadline: Option<Instant>,
+  ) -> (io::Result<ExitStatus>, Option<Signal>, bool);
 }
 
 impl CommandExt for Command {
@@ -103,4 +108,11 @@ impl CommandExt for Command {
   fn status_guard(self) -> (io::Result<ExitStatus>, Option<Signal>) {
     SignalHandler::spawn(self, |mut child| child.wait())
   }
+
+  fn status_guard_deadline(
+    self,
+    deadline: Option<Instant>,
+  ) -> (io::Result<ExitStatus>, Option<Signal>, bool) {
+    SignalHandler::spawn_with_deadline(self, deadline, |mut child| child.wait())
+  }
 }
@@ -49,7 +49,7 @@ impl Display for CompileError<'_> {
       ArgumentCountParse { key, value, source } => {
         write!(f, "invalid `{key}` value `{value}`: {source}")
       }
-      ArgumentCountValue { key, value } => {
+      ArgumentCountValue { key, value } | AttributeKeyValue { key, value } => {
         write!(f, "invalid `{key}` value `{value}`")
       }
       ArgumentPatternRegex { .. } => {
@@ -282,6 +282,7 @@ impl Display for CompileError<'_> {
         "{item_kind} `{item_name}` has invalid attribute `{}`",
         attribute.name(),
       ),
+      InvalidDuration { message } => write!(f, "{message}"),
       InvalidEscapeSequence { character } => write!(
         f,
         "`\\{}` is not a valid escape sequence",
@@ -301,16 +302,31 @@ impl Display for CompileError<'_> {
       InvalidIndentation { message } => {
         write!(f, "{message}")
       }
+      InvalidJobLimit { group, limit } => write!(
+        f,
+        "`job-limits` setting has invalid limit `{limit}` for group `{group}`, expected a positive \
+         integer"
+      ),
       InvalidMinimumVersion { source, version } => {
         write!(
           f,
           "`minimum-version` setting has invalid version `{version}`: {source}"
         )
       }
+      InvalidOutputMode { mode } => write!(
+        f,
+        "`output` setting has invalid mode `{mode}`, expected `grouped`, `interleaved`, or \
+         `prefixed`"
+      ),
       InvalidSignal { signal } => write!(
         f,
         "invalid signal `{signal}`: expected `SIGHUP`, `SIGINT`, or `SIGQUIT`"
       ),
+      JobLimitsNotMap { value } => write!(
+        f,
+        "`job-limits` setting must be a map but evaluated to {}",
+        value.color_display(Color::never()),
+      ),
       ListFeature(feature) => write!(f, "{feature}"),
       MappedDependencyMultipleStarredArguments => {
         write!(
@@ -391,6 +407,10 @@ impl Display for CompileError<'_> {
           )
         }
       }
+      RetryResumeScriptRecipe { recipe } => write!(
+        f,
+        "script recipe `{recipe}` cannot resume retries from the failing line"
+      ),
       ScriptAndShellAttribute { recipe } => write!(
         f,
         "recipe `{recipe}` has both `[script]` and `[shell]` attributes"
@@ -459,6 +479,9 @@ impl Display for CompileError<'_> {
       AttributeKeyMissingValue { key } => {
         write!(f, "attribute key `{key}` requires value")
       }
+      AttributeKeyRequired { attribute, key } => {
+        write!(f, "attribute `{attribute}` requires key `{key}`")
+      }
       UnknownAttributeKey { attribute, key } => {
         write!(f, "unknown key `{key}` for `{attribute}` attribute")
       }
@@ -466,6 +489,7 @@ impl Display for CompileError<'_> {
       UnknownDependency { recipe, unknown } => {
         write!(f, "recipe `{recipe}` has unknown dependency `{unknown}`")
       }
+      UnknownLint { lint } => write!(f, "unknown lint `{lint}`"),
       UnknownSetting { setting } => write!(f, "unknown setting `{setting}`"),
       UnknownStartOfToken { start } => {
         write!(f, "unknown start of token '{start}'")?;
@@ -36,9 +36,17 @@ pub(crate) enum CompileErrorKind<'src> {
   AttributeKeyMissingValue {
     key: Name<'src>,
   },
+  AttributeKeyRequired {
+    attribute: Name<'src>,
+    key: &'src str,
+  },
   AttributeKeyTakesNoValue {
     key: Name<'src>,
   },
+  AttributeKeyValue {
+    key: Name<'src>,
+    value: String,
+  },
   AttributePositionalFollowsKeyword,
   BacktickShebang,
   CircularRecipeDependency {
@@ -147,16 +155,26 @@ pub(crate) enum CompileErrorKind<'src> {
     item_name: &'src str,
     attribute: Box<Attribute<'src>>,
   },
+  InvalidDuration {
+    message: String,
+  },
   InvalidEscapeSequence {
     character: char,
   },
   InvalidIndentation {
     message: &'static str,
   },
+  InvalidJobLimit {
+    group: String,
+    limit: String,
+  },
   InvalidMinimumVersion {
     source: &'static str,
     version: String,
   },
+  InvalidOutputMode {
+    mode: String,
+  },
   InvalidShellRecipeAttribute {
     attribute: Box<Attribute<'src>>,
     recipe: &'src str,
@@ -164,6 +182,9 @@ pub(crate) enum CompileErrorKind<'src> {
   InvalidSignal {
     signal: String,
   },
+  JobLimitsNotMap {
+    value: Value,
+  },
   ListFeature(ListFeature),
   MappedDependencyMultipleStarredArguments,
   MappedDependencyWithoutListsSetting,
@@ -205,6 +226,9 @@ pub(crate) enum CompileErrorKind<'src> {
   RequiredParameterFollowsDefaultParameter {
     parameter: &'src str,
   },
+  RetryResumeScriptRecipe {
+    recipe: &'src str,
+  },
   ScriptAndShellAttribute {
     recipe: &'src str,
   },
@@ -269,6 +293,9 @@ pub(crate) enum CompileErrorKind<'src> {
     recipe: &'src str,
     unknown: Namepath<'src>,
   },
+  UnknownLint {
+    lint: String,
+  },
   UnknownSetting {
     setting: &'src str,
   },
@@ -4,24 +4,33 @@ use super::*;
 pub(crate) struct Config {
   pub(crate) alias_style: AliasStyle,
   pub(crate) allow_missing: bool,
+  pub(crate) cache_directory: Option<PathBuf>,
   pub(crate) ceiling: Option<PathBuf>,
   pub(crate) check: bool,
+  pub(crate) ci_format: CiFormat,
   pub(crate) color: Color,
   pub(crate) command_color: Option<nu_ansi_term::Color>,
   pub(crate) complete_aliases: bool,
   pub(crate) cygpath: PathBuf,
   pub(crate) default_list: bool,
+  pub(crate) deny_warnings: bool,
   pub(crate) dotenv_command: Vec<String>,
   pub(crate) dotenv_filename: Vec<String>,
   pub(crate) dotenv_path: Vec<String>,
   pub(crate) dry_run: bool,
+  pub(crate) events: Option<EventsFormat>,
+  pub(crate) events_file: Option<PathBuf>,
   pub(crate) explain: bool,
+  pub(crate) explain_cache: bool,
   pub(crate) groups: Vec<String>,
   pub(crate) highlight: bool,
   pub(crate) indentation: Option<Indentation>,
   pub(crate) invocation_directory: PathBuf,
   pub(crate) jobs: Option<NonZeroU64>,
+  pub(crate) junit: Option<PathBuf>,
   pub(crate) justfile_names: Option<Vec<String>>,
+  pub(crate) keep_going: bool,
+  pub(crate) lint: bool,
   pub(crate) list_heading: String,
   pub(crate) list_prefix: String,
   pub(crate) list_submodules: bool,
@@ -30,7 +39,9 @@ pub(crate) struct Config {
   pub(crate) no_cache: bool,
   pub(crate) no_dependencies: bool,
   pub(crate) one: bool,
+  pub(crate) output: Option<OutputMode>,
   pub(crate) overrides: BTreeMap<(Modulepath, String), String>,
+  pub(crate) parallel: bool,
   pub(crate) search_config: SearchConfig,
   pub(crate) shell: Option<String>,
   pub(crate) shell_args: Option<Vec<String>>,
@@ -38,11 +49,17 @@ pub(crate) struct Config {
   pub(crate) subcommand: Subcommand,
   pub(crate) tempdir: Option<PathBuf>,
   pub(crate) time: bool,
+  pub(crate) timeout: Option<Duration>,
   pub(crate) timestamp: bool,
   pub(crate) timestamp_format: String,
+  pub(crate) trace: Option<PathBuf>,
   pub(crate) unsorted: bool,
   pub(crate) unstable: bool,
   pub(crate) verbosity: Verbosity,
+  pub(crate) watch: bool,
+  pub(crate) watch_debounce: Duration,
+  pub(crate) watch_interval: Duration,
+  pub(crate) watch_paths: Vec<PathBuf>,
   pub(crate) yes: bool,
 }
 
@@ -51,24 +68,33 @@ impl Config {
     Ok(Self {
       alias_style: AliasStyle::Right,
       allow_missing: false,
+      cache_directory: None,
       ceiling: None,
       check: false,
+      ci_format: CiFormat::Auto,
       color: Color::default(),
       command_color: None,
       complete_aliases: false,
       cygpath: Arguments::DEFAULT_CYGPATH.into(),
       default_list: false,
+      deny_warnings: false,
       dotenv_command: Vec::new(),
       dotenv_filename: Vec::new(),
       dotenv_path: Vec::new(),
       dry_run: false,
+      events: None,
+      events_file: None,
       explain: false,
+      explain_cache: false,
       groups: Vec::new(),
       highlight: true,
       indentation: None,
       invocation_directory: env::current_dir().context(config_error::CurrentDir)?,
       jobs: None,
+      junit: None,
       justfile_names: None,
+      keep_going: false,
+      lint: false,
       list_heading: Arguments::DEFAULT_LIST_HEADING.into(),
       list_prefix: Arguments::DEFAULT_LIST_PREFIX.into(),
       list_submodules: false,
@@ -77,7 +103,9 @@ impl Config {
       no_cache: false,
       no_dependencies: false,
       one: false,
+      output: None,
       overrides: BTreeMap::new(),
+      parallel: false,
       search_config: SearchConfig::FromInvocationDirectory,
       shell: None,
       shell_args: None,
@@ -87,11 +115,23 @@ impl Config {
       },
       tempdir: None,
       time: false,
+      timeout: None,
       timestamp: false,
       timestamp_format: Arguments::DEFAULT_TIMESTAMP_FORMAT.into(),
+      trace: None,
       unsorted: false,
       unstable: false,
       verbosity: Verbosity::Taciturn,
+      watch: false,
+      watch_debounce: Arguments::DEFAULT_WATCH_DEBOUNCE
+        .parse::<Timespan>()
+        .unwrap()
+        .0,
+      watch_interval: Arguments::DEFAULT_WATCH_INTERVAL
+        .parse::<Timespan>()
+        .unwrap()
+        .0,
+      watch_paths: Vec::new(),
       yes: false,
     })
   }
@@ -159,7 +199,16 @@ impl Config {
   }
 
   fn subcommand(arguments: &Arguments, positional: &Positional) -> ConfigResult<Subcommand> {
-    if arguments.subcommand.changelog {
+    if arguments.subcommand.cache_list {
+      Ok(Subcommand::CacheList)
+    } else if arguments.subcommand.cache_prune {
+      Ok(Subcommand::CachePrune {
+        max_size: arguments.max_size,
+        older_than: arguments.older_than,
+      })
+    } else if arguments.subcommand.cache_stats {
+      Ok(Subcommand::CacheStats)
+    } else if arguments.subcommand.changelog {
       Ok(Subcommand::Changelog)
     } else if arguments.subcommand.choose {
       Ok(Subcommand::Choose {
@@ -216,6 +265,8 @@ impl Config {
       Ok(Subcommand::List {
         path: Self::parse_modulepath(&positional.arguments)?,
       })
+    } else if arguments.subcommand.lsp {
+      Ok(Subcommand::Lsp)
     } else if arguments.subcommand.man {
       Ok(Subcommand::Man)
     } else if let Some(request) = arguments.subcommand.request.as_deref() {
@@ -318,24 +369,33 @@ impl Config {
     Ok(Self {
       alias_style: arguments.alias_style,
       allow_missing: arguments.allow_missing,
+      cache_directory: arguments.cache_directory,
       ceiling: arguments.ceiling,
       check: arguments.check,
+      ci_format: arguments.ci_format,
       color,
       command_color: arguments.command_color.map(CommandColor::into),
       complete_aliases: arguments.complete_aliases,
       cygpath: arguments.cygpath,
       default_list: arguments.default_list,
+      deny_warnings: arguments.deny_warnings,
       dotenv_command: arguments.dotenv_command,
       dotenv_filename: arguments.dotenv_filename,
       dotenv_path: arguments.dotenv_path,
       dry_run: arguments.dry_run,
+      events: arguments.events,
+      events_file: arguments.events_file,
       explain: arguments.explain,
+      explain_cache: arguments.explain_cache,
       groups: arguments.group,
       highlight: !arguments.no_highlight,
       indentation: arguments.indentation,
       invocation_directory,
       jobs: arguments.jobs,
+      junit: arguments.junit,
       justfile_names: arguments.justfile_names,
+      keep_going: arguments.keep_going,
+      lint: arguments.lint,
       list_heading: arguments.list_heading,
       list_prefix: arguments.list_prefix,
       list_submodules: arguments.list_submodules,
@@ -344,7 +404,9 @@ impl Config {
       no_cache: arguments.no_cache,
       no_dependencies: arguments.no_deps,
       one: arguments.one,
+      output: arguments.output,
       overrides,
+      parallel: arguments.parallel,
       search_config,
       shell: arguments.shell,
       shell_args: if arguments.clear_shell_args {
@@ -358,8 +420,10 @@ impl Config {
       subcommand,
       tempdir: arguments.tempdir,
       time: arguments.time,
+      timeout: arguments.timeout.map(|timeout| timeout.0),
       timestamp: arguments.timestamp,
       timestamp_format: arguments.timestamp_format,
+      trace: arguments.trace,
       unsorted: arguments.unsorted,
       unstable,
       verbosity: if arguments.quiet {
@@ -367,10 +431,19 @@ impl Config {
       } else {
         Verbosity::from_flag_occurrences(arguments.verbose)
       },
+      watch: arguments.watch,
+      watch_debounce: arguments.watch_debounce.0,
+      watch_interval: arguments.watch_interval.0,
+      watch_paths: arguments.watch_path,
       yes: arguments.yes,
     })
   }
 
+  /// Whether warnings about the justfile should be reported
+  pub(crate) fn lints(&self) -> bool {
+    self.lint || self.deny_warnings
+  }
+
   pub(crate) fn require_unstable(
     &self,
     justfile: &Justfile,
@@ -409,20 +482,37 @@ mod tests {
     {
       name: $name:ident,
       args: [$($arg:expr),*],
+      $(cache_directory: $cache_directory:expr,)?
+      $(ci_format: $ci_format:expr,)?
       $(color: $color:expr,)?
+      $(deny_warnings: $deny_warnings:expr,)?
       $(dry_run: $dry_run:expr,)?
       $(dump_format: $dump_format:expr,)?
+      $(events: $events:expr,)?
+      $(events_file: $events_file:expr,)?
+      $(explain_cache: $explain_cache:expr,)?
       $(highlight: $highlight:expr,)?
+      $(junit: $junit:expr,)?
+      $(keep_going: $keep_going:expr,)?
+      $(lint: $lint:expr,)?
       $(no_cache: $no_cache:expr,)?
       $(no_dependencies: $no_dependencies:expr,)?
+      $(output: $output:expr,)?
       $(overrides: $overrides:expr,)?
+      $(parallel: $parallel:expr,)?
       $(search_config: $search_config:expr,)?
       $(shell: $shell:expr,)?
       $(shell_args: $shell_args:expr,)?
       $(subcommand: $subcommand:expr,)?
+      $(timeout: $timeout:expr,)?
+      $(trace: $trace:expr,)?
       $(unsorted: $unsorted:expr,)?
       $(unstable: $unstable:expr,)?
       $(verbosity: $verbosity:expr,)?
+      $(watch: $watch:expr,)?
+      $(watch_debounce: $watch_debounce:expr,)?
+      $(watch_interval: $watch_interval:expr,)?
+      $(watch_paths: $watch_paths:expr,)?
     } => {
       #[test]
       fn $name() {
@@ -432,20 +522,37 @@ mod tests {
         ];
 
         let want = Config {
+          $(cache_directory: $cache_directory,)?
+          $(ci_format: $ci_format,)?
           $(color: $color,)?
+          $(deny_warnings: $deny_warnings,)?
           $(dry_run: $dry_run,)?
           $(dump_format: $dump_format,)?
+          $(events: $events,)?
+          $(events_file: $events_file,)?
+          $(explain_cache: $explain_cache,)?
           $(highlight: $highlight,)?
+          $(junit: $junit,)?
+          $(keep_going: $keep_going,)?
+          $(lint: $lint,)?
           $(no_cache: $no_cache,)?
           $(no_dependencies: $no_dependencies,)?
+          $(output: $output,)?
           $(overrides: $overrides,)?
+          $(parallel: $parallel,)?
           $(search_config: $search_config,)?
           $(shell: $shell,)?
           $(shell_args: $shell_args,)?
           $(subcommand: $subcommand,)?
+          $(timeout: $timeout,)?
+          $(trace: $trace,)?
           $(unsorted: $unsorted,)?
           $(unstable: $unstable,)?
           $(verbosity: $verbosity,)?
+          $(watch: $watch,)?
+          $(watch_debounce: $watch_debounce,)?
+          $(watch_interval: $watch_interval,)?
+          $(watch_paths: $watch_paths,)?
           ..testing::config(&[])
         };
 
@@ -632,6 +739,171 @@ mod tests {
     highlight: false,
   }
 
+  test! {
+    name: parallel,
+    args: ["--parallel"],
+    parallel: true,
+  }
+
+  test! {
+    name: keep_going,
+    args: ["--keep-going"],
+    keep_going: true,
+  }
+
+  test! {
+    name: timeout,
+    args: ["--timeout", "1m30s"],
+    timeout: Some(Duration::from_secs(90)),
+  }
+
+  error! {
+    name: timeout_invalid,
+    args: ["--timeout", "90"],
+  }
+
+  test! {
+    name: junit,
+    args: ["--junit", "report.xml"],
+    junit: Some("report.xml".into()),
+  }
+
+  test! {
+    name: ci_format_github,
+    args: ["--ci-format", "github"],
+    ci_format: CiFormat::Github,
+  }
+
+  test! {
+    name: ci_format_auto,
+    args: ["--ci-format", "auto"],
+    ci_format: CiFormat::Auto,
+  }
+
+  test! {
+    name: ci_format_none,
+    args: ["--ci-format", "none"],
+    ci_format: CiFormat::None,
+  }
+
+  error! {
+    name: ci_format_invalid,
+    args: ["--ci-format", "foo"],
+  }
+
+  test! {
+    name: deny_warnings_default,
+    args: [],
+    deny_warnings: false,
+  }
+
+  test! {
+    name: deny_warnings,
+    args: ["--deny-warnings"],
+    deny_warnings: true,
+  }
+
+  test! {
+    name: lint_default,
+    args: [],
+    lint: false,
+  }
+
+  test! {
+    name: lint,
+    args: ["--lint"],
+    lint: true,
+  }
+
+  test! {
+    name: output_grouped,
+    args: ["--output", "grouped"],
+    output: Some(OutputMode::Grouped),
+  }
+
+  test! {
+    name: output_prefixed,
+    args: ["--output", "prefixed"],
+    output: Some(OutputMode::Prefixed),
+  }
+
+  error! {
+    name: output_invalid,
+    args: ["--output", "foo"],
+  }
+
+  test! {
+    name: trace,
+    args: ["--trace", "trace.json"],
+    trace: Some("trace.json".into()),
+  }
+
+  test! {
+    name: cache_directory,
+    args: ["--cache-dir", "foo"],
+    cache_directory: Some("foo".into()),
+  }
+
+  test! {
+    name: events,
+    args: ["--events", "json"],
+    events: Some(EventsFormat::Json),
+  }
+
+  error! {
+    name: events_invalid,
+    args: ["--events", "xml"],
+  }
+
+  test! {
+    name: events_file,
+    args: ["--events-file", "events.json"],
+    events_file: Some("events.json".into()),
+  }
+
+  test! {
+    name: explain_cache,
+    args: ["--explain-cache"],
+    explain_cache: true,
+  }
+
+  test! {
+    name: watch,
+    args: ["--watch"],
+    watch: true,
+  }
+
+  test! {
+    name: watch_debounce,
+    args: ["--watch", "--watch-debounce", "1s"],
+    watch: true,
+    watch_debounce: Duration::from_secs(1),
+  }
+
+  test! {
+    name: watch_interval,
+    args: ["--watch", "--watch-interval", "1s"],
+    watch: true,
+    watch_interval: Duration::from_secs(1),
+  }
+
+  test! {
+    name: watch_paths,
+    args: ["--watch", "--watch-path", "foo", "--watch-path", "bar"],
+    watch: true,
+    watch_paths: vec![PathBuf::from("foo"), PathBuf::from("bar")],
+  }
+
+  error! {
+    name: watch_path_requires_watch,
+    args: ["--watch-path", "foo"],
+  }
+
+  error! {
+    name: watch_conflicts_with_subcommand,
+    args: ["--watch", "--list"],
+  }
+
   test! {
     name: no_cache,
     args: ["--no-cache"],
@@ -865,6 +1137,48 @@ mod tests {
     args: ["--list", "--choose"],
   }
 
+  error! {
+    name: subcommand_conflict_lsp,
+    args: ["--list", "--lsp"],
+  }
+
+  test! {
+    name: subcommand_cache_list,
+    args: ["--cache-list"],
+    subcommand: Subcommand::CacheList,
+  }
+
+  test! {
+    name: subcommand_cache_prune,
+    args: ["--cache-prune"],
+    subcommand: Subcommand::CachePrune { max_size: None, older_than: None },
+  }
+
+  test! {
+    name: subcommand_cache_prune_options,
+    args: ["--cache-prune", "--max-size", "2G", "--older-than", "7d"],
+    subcommand: Subcommand::CachePrune {
+      max_size: Some(Size(2 << 30)),
+      older_than: Some(Timespan(Duration::from_secs(7 * 24 * 60 * 60))),
+    },
+  }
+
+  error! {
+    name: subcommand_cache_prune_invalid_size,
+    args: ["--cache-prune", "--max-size", "2X"],
+  }
+
+  error! {
+    name: max_size_requires_cache_prune,
+    args: ["--max-size", "2G"],
+  }
+
+  test! {
+    name: subcommand_cache_stats,
+    args: ["--cache-stats"],
+    subcommand: Subcommand::CacheStats,
+  }
+
   test! {
     name: subcommand_completions,
     args: ["--completions", "bash"],
@@ -948,6 +1262,12 @@ mod tests {
     subcommand: Subcommand::List { path: Modulepath::try_from(["bar"].as_slice()).unwrap() },
   }
 
+  test! {
+    name: subcommand_lsp,
+    args: ["--lsp"],
+    subcommand: Subcommand::Lsp,
+  }
+
   test! {
     name: subcommand_show_long,
     args: ["--show", "build"],
@@ -1294,6 +1614,16 @@ mod tests {
     },
   }
 
+  error! {
+    name: lsp_arguments,
+    args: ["--lsp", "bar"],
+    error: ConfigError::SubcommandArguments { subcommand, arguments },
+    check: {
+      assert_eq!(subcommand, "LSP");
+      assert_eq!(arguments, &["bar"]);
+    },
+  }
+
   error! {
     name: summary_arguments,
     args: ["--summary", "bar"],
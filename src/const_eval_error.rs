@@ -10,6 +10,10 @@ pub(crate) enum ConstEvalError<'src> {
   EmptyInterpreter {
     setting: Name<'src>,
   },
+  IndexNonMap {
+    token: Token<'src>,
+    value: Value,
+  },
   ListInStringContext {
     context: StringContext<'src>,
     value: Value,
@@ -20,6 +24,18 @@ pub(crate) enum ConstEvalError<'src> {
     rhs: Value,
     token: Token<'src>,
   },
+  MapDuplicateKey {
+    key: String,
+    token: Token<'src>,
+  },
+  MapMissingKey {
+    key: String,
+    token: Token<'src>,
+  },
+  MapOperand {
+    token: Token<'src>,
+    value: Value,
+  },
   RegexCompile {
     source: regex::Error,
     token: Token<'src>,
@@ -33,7 +49,12 @@ impl<'src> ConstEvalError<'src> {
       Self::Const(const_error) => const_error.context(),
       Self::EmptyInterpreter { setting } => setting.token,
       Self::ListInStringContext { context, .. } => context.token(),
-      Self::ListOperation { token, .. } | Self::RegexCompile { token, .. } => *token,
+      Self::IndexNonMap { token, .. }
+      | Self::ListOperation { token, .. }
+      | Self::MapDuplicateKey { token, .. }
+      | Self::MapMissingKey { token, .. }
+      | Self::MapOperand { token, .. }
+      | Self::RegexCompile { token, .. } => *token,
     }
   }
 
@@ -51,10 +72,16 @@ impl Display for ConstEvalError<'_> {
         f,
         "`{setting}` setting requires at least one element but evaluated to empty list"
       ),
+      Self::IndexNonMap { value, .. } => write!(
+        f,
+        "list value {} cannot be indexed",
+        value.color_display(Color::never())
+      ),
       Self::ListInStringContext { context, value } => {
         write!(
           f,
-          "list value {} {context}",
+          "{} value {} {context}",
+          if value.is_map() { "map" } else { "list" },
           value.color_display(Color::never())
         )?;
 
@@ -82,6 +109,22 @@ impl Display for ConstEvalError<'_> {
           )
         }
       }
+      Self::MapDuplicateKey { key, .. } => write!(
+        f,
+        "map has duplicate key {}",
+        Element(key).color_display(Color::never())
+      ),
+      Self::MapMissingKey { key, .. } => write!(
+        f,
+        "map has no key {}",
+        Element(key).color_display(Color::never())
+      ),
+      Self::MapOperand { token, value } => write!(
+        f,
+        "operator `{}` cannot be applied to map value {}",
+        token.lexeme(),
+        value.color_display(Color::never())
+      ),
       Self::RegexCompile { source, .. } => write!(f, "{source}"),
     }
   }
@@ -44,12 +44,19 @@ pub(crate) enum Error<'src> {
     path: PathBuf,
     source: serde_json::Error,
   },
-  CacheInputDirectory {
-    path: PathBuf,
-  },
   CacheInputMissing {
     path: PathBuf,
   },
+  CacheInputPattern {
+    pattern: String,
+    source: globset::Error,
+  },
+  CacheInputUnmatched {
+    pattern: String,
+  },
+  CacheInputWalk {
+    source: ignore::Error,
+  },
   CacheKeySerialize {
     source: serde_json::Error,
   },
@@ -57,6 +64,9 @@ pub(crate) enum Error<'src> {
     recipe: &'src str,
     output: String,
   },
+  CacheOutputWalk {
+    source: ignore::Error,
+  },
   ChooserInvoke {
     shell_binary: String,
     shell_arguments: String,
@@ -116,6 +126,9 @@ pub(crate) enum Error<'src> {
     recipe: &'src str,
     min_arguments: usize,
   },
+  DeniedWarnings {
+    count: usize,
+  },
   Dotenv {
     dotenv_error: dotenvy::Error,
     path: PathBuf,
@@ -181,6 +194,10 @@ pub(crate) enum Error<'src> {
     code: i32,
   },
   Homedir,
+  IndexNonMap {
+    token: Box<Token<'src>>,
+    value: Value,
+  },
   InitExists {
     justfile: PathBuf,
   },
@@ -211,6 +228,21 @@ pub(crate) enum Error<'src> {
     path: PathBuf,
     io_error: io::Error,
   },
+  LspIo {
+    io_error: io::Error,
+  },
+  MapDuplicateKey {
+    key: String,
+    token: Box<Token<'src>>,
+  },
+  MapMissingKey {
+    key: String,
+    token: Box<Token<'src>>,
+  },
+  MapOperand {
+    token: Box<Token<'src>>,
+    value: Value,
+  },
   MissingImportFile {
     path: Token<'src>,
   },
@@ -224,6 +256,9 @@ pub(crate) enum Error<'src> {
   ModuleAbsent {
     module: Modulepath,
   },
+  Multiple {
+    failures: Vec<(Modulepath, Self)>,
+  },
   NoChoosableRecipes,
   NoDefaultRecipe,
   NoRecipes,
@@ -308,6 +343,22 @@ pub(crate) enum Error<'src> {
   SignalHandlerSpawnThread {
     io_error: io::Error,
   },
+  Skipped {
+    recipe: &'src str,
+  },
+  StalePattern {
+    pattern: String,
+    source: globset::Error,
+  },
+  StaleSourceMissing {
+    path: PathBuf,
+  },
+  StaleSourceUnmatched {
+    pattern: String,
+  },
+  StaleWalk {
+    source: ignore::Error,
+  },
   StdoutIo {
     io_error: io::Error,
   },
@@ -315,6 +366,10 @@ pub(crate) enum Error<'src> {
     recipe: &'src str,
     io_error: io::Error,
   },
+  Timeout {
+    recipe: &'src str,
+    timeout: Duration,
+  },
   Unknown {
     line_number: Option<usize>,
     print_message: bool,
@@ -341,6 +396,7 @@ pub(crate) enum Error<'src> {
   UnstableFeature {
     unstable_feature: UnstableFeature,
   },
+  WatchNoPaths,
   WriteJustfile {
     justfile: PathBuf,
     io_error: io::Error,
@@ -383,7 +439,7 @@ impl<'src> Error<'src> {
     }
   }
 
-  fn context(&self) -> Option<Token<'src>> {
+  pub(crate) fn context(&self) -> Option<Token<'src>> {
     match self {
       Self::AmbiguousModuleFile { module, .. } | Self::MissingModuleFile { module, .. } => {
         Some(module.token)
@@ -393,8 +449,12 @@ impl<'src> Error<'src> {
       Self::Compile { compile_error } => Some(compile_error.context()),
       Self::Const { const_error } => Some(const_error.context()),
       Self::FunctionCall { function, .. } => Some(function.token),
+      Self::IndexNonMap { token, .. }
+      | Self::ListOperation { token, .. }
+      | Self::MapDuplicateKey { token, .. }
+      | Self::MapMissingKey { token, .. }
+      | Self::MapOperand { token, .. } => Some(**token),
       Self::ListInStringContext { context, .. } => Some(context.token()),
-      Self::ListOperation { token, .. } => Some(**token),
       Self::MissingImportFile { path } => Some(*path),
       _ => None,
     }
@@ -434,6 +494,7 @@ impl<'src> Error<'src> {
       Self::Code { print_message, .. }
       | Self::Signal { print_message, .. }
       | Self::Unknown { print_message, .. } => *print_message,
+      Self::Skipped { .. } => false,
       _ => true,
     }
   }
@@ -459,6 +520,10 @@ impl<'src> Error<'src> {
     match self {
       Self::Assert { message, name } => ConstEvalError::Assert { message, name },
       Self::Const { const_error } => ConstEvalError::Const(const_error),
+      Self::IndexNonMap { token, value } => ConstEvalError::IndexNonMap {
+        token: *token,
+        value,
+      },
       Self::ListInStringContext { context, value } => {
         ConstEvalError::ListInStringContext { context, value }
       }
@@ -473,6 +538,14 @@ impl<'src> Error<'src> {
         rhs,
         token: *token,
       },
+      Self::MapDuplicateKey { key, token } => {
+        ConstEvalError::MapDuplicateKey { key, token: *token }
+      }
+      Self::MapMissingKey { key, token } => ConstEvalError::MapMissingKey { key, token: *token },
+      Self::MapOperand { token, value } => ConstEvalError::MapOperand {
+        token: *token,
+        value,
+      },
       Self::RegexCompile { source, token } => ConstEvalError::RegexCompile { source, token },
       error => unreachable!(
         "non-const error in const evaluation: {}",
@@ -510,6 +583,14 @@ impl ColorDisplay for Error<'_> {
   fn fmt(&self, f: &mut Formatter, color: Color) -> fmt::Result {
     use Error::*;
 
+    if let Multiple { failures } = self {
+      for (_recipe, error) in failures {
+        if error.print_message() {
+          writeln!(f, "{}", error.color_display(color))?;
+        }
+      }
+    }
+
     let error = color.error().paint("error");
     let message = color.message().prefix();
     write!(f, "{error}: {message}")?;
@@ -604,12 +685,16 @@ impl ColorDisplay for Error<'_> {
         "failed to write cache entry at `{}`: {source}",
         path.display(),
       )?,
-      CacheInputDirectory { path } => {
-        write!(f, "cache input is directory: `{}`", path.display())?;
-      }
       CacheInputMissing { path } => {
         write!(f, "cache input does not exist: `{}`", path.display())?;
       }
+      CacheInputPattern { pattern, source } => {
+        write!(f, "invalid cache input pattern `{pattern}`: {source}")?;
+      }
+      CacheInputUnmatched { pattern } => {
+        write!(f, "cache input pattern `{pattern}` matches no files")?;
+      }
+      CacheInputWalk { source } => write!(f, "failed to walk cache input: {source}")?,
       CacheKeySerialize { source } => write!(f, "failed to serialize cache key: {source}")?,
       CacheOutputMissing { recipe, output } => {
         write!(
@@ -617,6 +702,7 @@ impl ColorDisplay for Error<'_> {
           "recipe `{recipe}` failed to create cache output `{output}`",
         )?;
       }
+      CacheOutputWalk { source } => write!(f, "failed to walk cache output: {source}")?,
       ChooserInvoke {
         shell_binary,
         shell_arguments,
@@ -733,6 +819,13 @@ impl ColorDisplay for Error<'_> {
           Count::numbered("argument", min_arguments),
         )?;
       }
+      DeniedWarnings { count } => {
+        write!(
+          f,
+          "{} denied by `--deny-warnings`",
+          Count::numbered("warning", count),
+        )?;
+      }
       Dotenv { dotenv_error, path } => {
         write!(
           f,
@@ -825,6 +918,13 @@ impl ColorDisplay for Error<'_> {
       Homedir => {
         write!(f, "failed to get homedir")?;
       }
+      IndexNonMap { value, .. } => {
+        write!(
+          f,
+          "list value {} cannot be indexed",
+          value.color_display(color)
+        )?;
+      }
       InitExists { justfile } => {
         write!(f, "justfile `{}` already exists", justfile.display())?;
       }
@@ -845,7 +945,12 @@ impl ColorDisplay for Error<'_> {
         write!(f, "recipe `{recipe}` has invalid shebang `{shebang}`")?;
       }
       ListInStringContext { context, value, .. } => {
-        write!(f, "list value {} {context}", value.color_display(color))?;
+        write!(
+          f,
+          "{} value {} {context}",
+          if value.is_map() { "map" } else { "list" },
+          value.color_display(color),
+        )?;
 
         if matches!(context, StringContext::Function { .. }) {
           write!(
@@ -899,6 +1004,25 @@ impl ColorDisplay for Error<'_> {
           path.display()
         )?;
       }
+      LspIo { io_error } => write!(f, "I/O error in language server: {io_error}")?,
+      MapDuplicateKey { key, .. } => {
+        write!(
+          f,
+          "map has duplicate key {}",
+          Element(key).color_display(color)
+        )?;
+      }
+      MapMissingKey { key, .. } => {
+        write!(f, "map has no key {}", Element(key).color_display(color))?;
+      }
+      MapOperand { token, value } => {
+        write!(
+          f,
+          "operator `{}` cannot be applied to map value {}",
+          token.lexeme(),
+          value.color_display(color),
+        )?;
+      }
       NonFinalOptionWithValue { recipe, switch } => {
         write!(
           f,
@@ -915,6 +1039,15 @@ impl ColorDisplay for Error<'_> {
       ModuleAbsent { module } => {
         write!(f, "optional module `{module}` is absent")?;
       }
+      Multiple { failures } => {
+        write!(f, "{} failed:", Count::numbered("recipe", failures.len()))?;
+        for (recipe, error) in failures {
+          write!(f, "\n  `{recipe}`")?;
+          if let Some(code) = error.code() {
+            write!(f, " with exit code {code}")?;
+          }
+        }
+      }
       NoChoosableRecipes => write!(f, "justfile contains no choosable recipes")?,
       NoDefaultRecipe => write!(f, "justfile contains no default recipe")?,
       NoRecipes => write!(f, "justfile contains no recipes")?,
@@ -1047,6 +1180,22 @@ impl ColorDisplay for Error<'_> {
           "I/O error spawning thread for signal handler: {io_error}",
         )?;
       }
+      Skipped { recipe } => {
+        write!(
+          f,
+          "recipe `{recipe}` was skipped because a `--parallel` invocation failed",
+        )?;
+      }
+      StalePattern { pattern, source } => {
+        write!(f, "invalid stale pattern `{pattern}`: {source}")?;
+      }
+      StaleSourceMissing { path } => {
+        write!(f, "stale source does not exist: `{}`", path.display())?;
+      }
+      StaleSourceUnmatched { pattern } => {
+        write!(f, "`[stale]` source pattern `{pattern}` matches no files")?;
+      }
+      StaleWalk { source } => write!(f, "failed to walk stale path: {source}")?,
       StdoutIo { io_error } => {
         write!(f, "I/O error writing to stdout: {io_error}")?;
       }
@@ -1057,6 +1206,13 @@ impl ColorDisplay for Error<'_> {
           directory or write a file to that directory: {io_error}",
         )?;
       }
+      Timeout { recipe, timeout } => {
+        write!(
+          f,
+          "recipe `{recipe}` timed out after {}",
+          Timespan(*timeout)
+        )?;
+      }
       Unknown {
         recipe,
         line_number,
@@ -1097,6 +1253,11 @@ impl ColorDisplay for Error<'_> {
           "{unstable_feature}, invoke `just` with `--unstable`, set the `JUST_UNSTABLE` environment variable, or add `set unstable` to your `justfile` to enable unstable features",
         )?;
       }
+      WatchNoPaths => write!(
+        f,
+        "no paths to watch, pass `--watch-path` or invoke recipes with \
+         `[cache(inputs = ...)]` or `[stale(sources = ...)]`"
+      )?,
       WriteJustfile { justfile, io_error } => {
         let justfile = justfile.display();
         write!(f, "failed to write justfile to `{justfile}`: {io_error}")?;
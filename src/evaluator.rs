@@ -75,6 +75,10 @@ impl<'src, 'run> Evaluator<'src, 'run> {
         Setting::AllowDuplicateVariables(value) => {
           settings.allow_duplicate_variables = value;
         }
+        Setting::CacheDirectory(value) => {
+          settings.cache_directory =
+            Some(self.evaluate_string_const(&value, StringContext::Setting(set.name))?);
+        }
         Setting::DefaultList(value) => {
           settings.default_list = value;
         }
@@ -114,6 +118,9 @@ impl<'src, 'run> Evaluator<'src, 'run> {
         Setting::Indentation(_, indentation) => {
           settings.indentation = Some(indentation);
         }
+        Setting::JobLimits(value) => {
+          settings.job_limits = self.evaluate_job_limits(&value, set.name)?;
+        }
         Setting::Lazy(value) => {
           settings.lazy = value;
         }
@@ -127,6 +134,9 @@ impl<'src, 'run> Evaluator<'src, 'run> {
         Setting::NoExitMessage(value) => {
           settings.no_exit_message = value;
         }
+        Setting::Output(_, mode) => {
+          settings.output = Some(mode);
+        }
         Setting::PositionalArguments(value) => {
           settings.positional_arguments = value;
         }
@@ -165,6 +175,38 @@ impl<'src, 'run> Evaluator<'src, 'run> {
     Ok(settings)
   }
 
+  fn evaluate_job_limits(
+    &self,
+    expression: &Expression<'src>,
+    setting: Name<'src>,
+  ) -> CompileResult<'src, BTreeMap<String, NonZeroU64>> {
+    let value = self.evaluate_value_const(expression)?;
+
+    let Some(limits) = value.values() else {
+      return Err(setting.error(CompileErrorKind::JobLimitsNotMap { value }));
+    };
+
+    value
+      .elements()
+      .iter()
+      .zip(limits)
+      .map(|(group, limit)| {
+        limit
+          .bytes()
+          .all(|byte| byte.is_ascii_digit())
+          .then(|| limit.parse::<NonZeroU64>().ok())
+          .flatten()
+          .map(|limit| (group.clone(), limit))
+          .ok_or_else(|| {
+            setting.error(CompileErrorKind::InvalidJobLimit {
+              group: group.clone(),
+              limit: limit.clone(),
+            })
+          })
+      })
+      .collect()
+  }
+
   pub(crate) fn evaluate_interpreter(
     &self,
     interpreter: &Interpreter<Expression<'src>>,
@@ -452,7 +494,7 @@ impl<'src, 'run> Evaluator<'src, 'run> {
   ) -> RunResult<'src, String> {
     let value = self.evaluate_value(expression)?;
 
-    if value.elements().len() != 1 {
+    if value.is_map() || value.elements().len() != 1 {
       return Err(Error::ListInStringContext { context, value });
     }
 
@@ -539,14 +581,15 @@ impl<'src, 'run> Evaluator<'src, 'run> {
         let rhs = self.evaluate_value(rhs)?;
         lhs.apply(&rhs, ListOperator::Concatenate, *operator)
       }
-      Expression::ListConcatenation { lhs, rhs, .. } => {
+      Expression::ListConcatenation { lhs, operator, rhs } => {
         let lhs = self.evaluate_value(lhs)?;
         let rhs = self.evaluate_value(rhs)?;
         Ok(
           lhs
-            .into_elements()
-            .into_iter()
-            .chain(rhs.into_elements())
+            .list(*operator)?
+            .iter()
+            .chain(rhs.list(*operator)?)
+            .cloned()
             .collect(),
         )
       }
@@ -578,6 +621,26 @@ impl<'src, 'run> Evaluator<'src, 'run> {
         }
       }
       Expression::Group { contents } => self.evaluate_value(contents),
+      Expression::Index { index, open, value } => {
+        let value = self.evaluate_value(value)?;
+
+        if !value.is_map() {
+          return Err(Error::IndexNonMap {
+            token: Box::new(*open),
+            value,
+          });
+        }
+
+        let key = self.evaluate_string(index, StringContext::Index(*open))?;
+
+        match value.get(&key) {
+          Some(value) => Ok(value.into()),
+          None => Err(Error::MapMissingKey {
+            key,
+            token: Box::new(*open),
+          }),
+        }
+      }
       Expression::Join {
         lhs: None,
         operator,
@@ -602,6 +665,21 @@ impl<'src, 'run> Evaluator<'src, 'run> {
         }
         Ok(values.into())
       }
+      Expression::Map { entries, open } => {
+        let mut map = BTreeMap::new();
+        for (key, value) in entries {
+          let key = self.evaluate_string(key, StringContext::MapKey(*open))?;
+          let value = self.evaluate_string(value, StringContext::MapValue(*open))?;
+          if map.contains_key(&key) {
+            return Err(Error::MapDuplicateKey {
+              key,
+              token: Box::new(*open),
+            });
+          }
+          map.insert(key, value);
+        }
+        Ok(map.into())
+      }
       Expression::Not { operand } => Ok((!self.evaluate_value(operand)?.is_truthy()).into()),
       Expression::Or { lhs, rhs } => {
         let lhs = self.evaluate_value(lhs)?;
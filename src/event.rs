@@ -0,0 +1,46 @@
+use super::*;
+
+// An event written by `--events` and `--events-file`. Changes to event names
+// or fields which are not backwards compatible must increment
+// `Events::VERSION`.
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case", tag = "event")]
+pub(crate) enum Event<'a> {
+  Cache {
+    hit: bool,
+    key: String,
+    recipe: &'a Modulepath,
+  },
+  CommandStart {
+    command: &'a str,
+    line: usize,
+    recipe: &'a Modulepath,
+  },
+  RecipeFinish {
+    arguments: &'a [String],
+    cache: Option<&'static str>,
+    code: i32,
+    dependency: bool,
+    duration: f64,
+    recipe: &'a Modulepath,
+    success: bool,
+  },
+  RecipeStart {
+    arguments: &'a [String],
+    dependency: bool,
+    recipe: &'a Modulepath,
+  },
+  RunFinish {
+    code: i32,
+    duration: f64,
+    success: bool,
+  },
+  RunStart {
+    just_version: &'static str,
+    recipes: Vec<&'a Modulepath>,
+    version: u32,
+  },
+  Signal {
+    signal: Signal,
+  },
+}
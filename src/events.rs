@@ -0,0 +1,63 @@
+use super::*;
+
+static WRITER: Mutex<Option<Box<dyn Write + Send>>> = Mutex::new(None);
+
+// Writes events as newline-delimited JSON, to standard error with `--events`,
+// or to a file with `--events-file`. Like the signal handler, the writer is
+// global, so that events can be emitted from the signal handler thread.
+pub(crate) struct Events;
+
+#[derive(Serialize)]
+struct Record<'a> {
+  #[serde(flatten)]
+  event: Event<'a>,
+  time: f64,
+}
+
+impl Events {
+  pub(crate) const VERSION: u32 = 1;
+
+  pub(crate) fn install(config: &Config) -> RunResult<'static> {
+    let writer: Option<Box<dyn Write + Send>> = if let Some(path) = &config.events_file {
+      Some(Box::new(File::create(path).map_err(|source| {
+        Error::FilesystemIo {
+          path: path.clone(),
+          source,
+        }
+      })?))
+    } else if config.events.is_some() {
+      Some(Box::new(io::stderr()))
+    } else {
+      None
+    };
+
+    *WRITER.lock().unwrap() = writer;
+
+    Ok(())
+  }
+
+  // Events are written and flushed immediately, so that they are not lost if
+  // `just` exits due to a signal. Events are informational, so errors writing
+  // them are ignored rather than failing the run.
+  pub(crate) fn emit(event: Event) {
+    let mut writer = WRITER.lock().unwrap();
+
+    let Some(writer) = writer.as_mut() else {
+      return;
+    };
+
+    let record = Record {
+      event,
+      time: SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64(),
+    };
+
+    let mut line = serde_json::to_string(&record).unwrap();
+    line.push('\n');
+
+    writer.write_all(line.as_bytes()).ok();
+    writer.flush().ok();
+  }
+}
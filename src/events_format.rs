@@ -0,0 +1,6 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub(crate) enum EventsFormat {
+  Json,
+}
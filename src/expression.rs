@@ -52,6 +52,12 @@ pub(crate) enum Expression<'src> {
   },
   /// `(contents)`
   Group { contents: Box<Self> },
+  /// `value[index]`
+  Index {
+    index: Box<Self>,
+    open: Token<'src>,
+    value: Box<Self>,
+  },
   /// `lhs / rhs`
   Join {
     lhs: Option<Box<Self>>,
@@ -69,6 +75,11 @@ pub(crate) enum Expression<'src> {
     operator: Token<'src>,
     rhs: Box<Self>,
   },
+  /// `{a: b, c: d}`
+  Map {
+    entries: Vec<(Expression<'src>, Expression<'src>)>,
+    open: Token<'src>,
+  },
   /// `!operand`
   Not { operand: Box<Self> },
   /// `lhs || rhs`
@@ -132,6 +143,10 @@ impl<'src> Expression<'src> {
         }
       }
       Self::Group { contents } => contents.resolve_variables(context, bindings),
+      Self::Index { index, value, .. } => {
+        value.resolve_variables(context, bindings);
+        index.resolve_variables(context, bindings);
+      }
       Self::Join { lhs, rhs, .. } => {
         if let Some(lhs) = lhs {
           lhs.resolve_variables(context, bindings);
@@ -143,6 +158,12 @@ impl<'src> Expression<'src> {
           element.resolve_variables(context, bindings);
         }
       }
+      Self::Map { entries, .. } => {
+        for (key, value) in entries {
+          key.resolve_variables(context, bindings);
+          value.resolve_variables(context, bindings);
+        }
+      }
       Self::Not { operand } => operand.resolve_variables(context, bindings),
       Self::Variable { name, number } => {
         let name = name.lexeme();
@@ -208,6 +229,7 @@ impl Display for Expression<'_> {
         Ok(())
       }
       Self::Group { contents } => write!(f, "({contents})"),
+      Self::Index { index, value, .. } => write!(f, "{value}[{index}]"),
       Self::Join { lhs: None, rhs, .. } => write!(f, "/ {rhs}"),
       Self::Join {
         lhs: Some(lhs),
@@ -224,6 +246,16 @@ impl Display for Expression<'_> {
         }
         write!(f, "]")
       }
+      Self::Map { entries, .. } => {
+        write!(f, "{{")?;
+        for (i, (key, value)) in entries.iter().enumerate() {
+          if i > 0 {
+            write!(f, ", ")?;
+          }
+          write!(f, "{key}: {value}")?;
+        }
+        write!(f, "}}")
+      }
       Self::Not { operand } => write!(f, "!{operand}"),
       Self::Or { lhs, rhs } => write!(f, "{lhs} || {rhs}"),
       Self::StringLiteral { string_literal } => write!(f, "{string_literal}"),
@@ -315,6 +347,13 @@ impl Serialize for Expression<'_> {
         seq.end()
       }
       Self::Group { contents } => contents.serialize(serializer),
+      Self::Index { index, value, .. } => {
+        let mut seq = serializer.serialize_seq(None)?;
+        seq.serialize_element("index")?;
+        seq.serialize_element(value)?;
+        seq.serialize_element(index)?;
+        seq.end()
+      }
       Self::Join { lhs, rhs, .. } => {
         let mut seq = serializer.serialize_seq(None)?;
         seq.serialize_element("join")?;
@@ -330,6 +369,14 @@ impl Serialize for Expression<'_> {
         }
         seq.end()
       }
+      Self::Map { entries, .. } => {
+        let mut seq = serializer.serialize_seq(None)?;
+        seq.serialize_element("map")?;
+        for entry in entries {
+          seq.serialize_element(entry)?;
+        }
+        seq.end()
+      }
       Self::Not { operand } => {
         let mut seq = serializer.serialize_seq(None)?;
         seq.serialize_element("not")?;
@@ -0,0 +1,56 @@
+use super::*;
+
+// A record of a completed invocation, included in the cache keys of cached
+// dependents so that changes to their dependencies invalidate them.
+//
+// Cached invocations are identified by their cache key and output hashes,
+// which already cover their own dependencies. Uncached invocations are
+// identified by their arguments and a hash of the fingerprints of their
+// dependencies, so that changes to cached recipes propagate through them.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct Fingerprint {
+  pub(crate) arguments: Vec<Value>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) dependencies: Option<blake3::Hash>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) key: Option<blake3::Hash>,
+  #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+  pub(crate) outputs: BTreeMap<String, blake3::Hash>,
+  pub(crate) recipe: Modulepath,
+}
+
+impl Fingerprint {
+  pub(crate) fn new(
+    recipe: &Recipe,
+    arguments: &[Value],
+    cache: &Cache,
+    dependencies: &[Fingerprint],
+    record: Option<CacheRecord>,
+  ) -> RunResult<'static, Self> {
+    if let Some(CacheRecord { key, outputs, .. }) = record {
+      return Ok(Self {
+        arguments: arguments.into(),
+        dependencies: None,
+        key: Some(key),
+        outputs,
+        recipe: recipe.recipe_path().clone(),
+      });
+    }
+
+    // Dependencies are hashed, rather than nested, so that fingerprints stay
+    // small in dependency graphs with many shared dependencies.
+    let dependencies = if dependencies.is_empty() {
+      None
+    } else {
+      Some(cache.key(&dependencies)?.0)
+    };
+
+    Ok(Self {
+      arguments: arguments.into(),
+      dependencies,
+      key: None,
+      outputs: BTreeMap::new(),
+      recipe: recipe.recipe_path().clone(),
+    })
+  }
+}
@@ -8,7 +8,117 @@ use {
   semver::{Version, VersionReq},
 };
 
+pub(crate) const FUNCTIONS: &[(&str, Function)] = &[
+  ("absolute_path", UnaryMap(absolute_path)),
+  ("append", BinaryStrValue(append)),
+  ("arch", Nullary(arch)),
+  ("blake3", Unary(blake3)),
+  ("blake3_file", Unary(blake3_file)),
+  ("bool", ValueUnary(bool)),
+  (
+    "cache_directory",
+    Nullary(|_| dir("cache", dirs::cache_dir)),
+  ),
+  ("canonicalize", Unary(canonicalize)),
+  ("capitalize", Unary(capitalize)),
+  ("choose", Binary(choose)),
+  ("clean", Unary(clean)),
+  (
+    "config_directory",
+    Nullary(|_| dir("config", dirs::config_dir)),
+  ),
+  (
+    "config_local_directory",
+    Nullary(|_| dir("local config", dirs::config_local_dir)),
+  ),
+  ("data_directory", Nullary(|_| dir("data", dirs::data_dir))),
+  (
+    "data_local_directory",
+    Nullary(|_| dir("local data", dirs::data_local_dir)),
+  ),
+  ("datetime", Unary(datetime)),
+  ("datetime_utc", Unary(datetime_utc)),
+  ("encode_uri_component", Unary(encode_uri_component)),
+  ("env", ValueBinaryOpt(env)),
+  ("env_var", ValueUnary(env_var)),
+  ("env_var_or_default", ValueBinary(env_var_or_default)),
+  ("error", Unary(error)),
+  (
+    "executable_directory",
+    Nullary(|_| dir("executable", dirs::executable_dir)),
+  ),
+  ("extension", Unary(extension)),
+  ("file_name", Unary(file_name)),
+  ("file_stem", Unary(file_stem)),
+  ("home_directory", Nullary(|_| dir("home", dirs::home_dir))),
+  ("invocation_directory", Nullary(invocation_directory)),
+  (
+    "invocation_directory_native",
+    Nullary(invocation_directory_native),
+  ),
+  ("is_dependency", ValueNullary(is_dependency)),
+  ("join", BinaryPlus(join)),
+  ("join_list", BinaryOptValueStrToValue(join_list)),
+  ("just_executable", Nullary(just_executable)),
+  ("just_pid", Nullary(just_pid)),
+  ("just_version", Nullary(just_version)),
+  ("justfile", Nullary(justfile)),
+  ("justfile_directory", Nullary(justfile_directory)),
+  ("kebabcase", Unary(kebabcase)),
+  ("keys", ValueUnary(keys)),
+  ("len", ValueUnary(len)),
+  ("lowercamelcase", Unary(lowercamelcase)),
+  ("lowercase", Unary(lowercase)),
+  ("module_directory", Nullary(module_directory)),
+  ("module_file", Nullary(module_file)),
+  ("module_path", Nullary(module_path)),
+  ("num_cpus", Nullary(num_cpus)),
+  ("num_jobs", ValueNullary(num_jobs)),
+  ("os", Nullary(os)),
+  ("os_family", Nullary(os_family)),
+  ("parent_directory", Unary(parent_directory)),
+  ("path_exists", UnaryToValue(path_exists)),
+  ("prepend", BinaryStrValue(prepend)),
+  ("quote", UnaryMap(quote)),
+  ("read", Unary(read)),
+  ("recipe_name", Nullary(recipe_name)),
+  ("replace", Ternary(replace)),
+  ("replace_regex", Ternary(replace_regex)),
+  ("require", Unary(require)),
+  (
+    "runtime_directory",
+    Nullary(|_| dir("runtime", dirs::runtime_dir)),
+  ),
+  ("semver_matches", BinaryToValue(semver_matches)),
+  ("sha256", Unary(sha256)),
+  ("sha256_file", Unary(sha256_file)),
+  ("shell", UnaryPlus(shell)),
+  ("shoutykebabcase", Unary(shoutykebabcase)),
+  ("shoutysnakecase", Unary(shoutysnakecase)),
+  ("show", ValueUnary(show)),
+  ("snakecase", Unary(snakecase)),
+  ("source_directory", Nullary(source_directory)),
+  ("source_file", Nullary(source_file)),
+  ("split", BinaryOptToValue(split)),
+  ("style", BinaryOptValueStr(style)),
+  ("titlecase", Unary(titlecase)),
+  ("trim", Unary(trim)),
+  ("trim_end", Unary(trim_end)),
+  ("trim_end_match", Binary(trim_end_match)),
+  ("trim_end_matches", Binary(trim_end_matches)),
+  ("trim_start", Unary(trim_start)),
+  ("trim_start_match", Binary(trim_start_match)),
+  ("trim_start_matches", Binary(trim_start_matches)),
+  ("uppercamelcase", Unary(uppercamelcase)),
+  ("uppercase", Unary(uppercase)),
+  ("uuid", Nullary(uuid)),
+  ("values", ValueUnary(values)),
+  ("which", UnaryToValue(which)),
+  ("without_extension", Unary(without_extension)),
+];
+
 #[allow(clippy::arbitrary_source_item_ordering)]
+#[derive(Clone, Copy)]
 pub(crate) enum Function {
   Nullary(fn(Context) -> StringResult),
   Unary(fn(Context, &str) -> StringResult),
@@ -65,93 +175,10 @@ pub(crate) fn get(name: &str) -> Option<Function> {
     name.into()
   };
 
-  let function = match name.as_str() {
-    "absolute_path" => UnaryMap(absolute_path),
-    "append" => BinaryStrValue(append),
-    "arch" => Nullary(arch),
-    "blake3" => Unary(blake3),
-    "blake3_file" => Unary(blake3_file),
-    "bool" => ValueUnary(bool),
-    "cache_directory" => Nullary(|_| dir("cache", dirs::cache_dir)),
-    "canonicalize" => Unary(canonicalize),
-    "capitalize" => Unary(capitalize),
-    "choose" => Binary(choose),
-    "clean" => Unary(clean),
-    "config_directory" => Nullary(|_| dir("config", dirs::config_dir)),
-    "config_local_directory" => Nullary(|_| dir("local config", dirs::config_local_dir)),
-    "data_directory" => Nullary(|_| dir("data", dirs::data_dir)),
-    "data_local_directory" => Nullary(|_| dir("local data", dirs::data_local_dir)),
-    "datetime" => Unary(datetime),
-    "datetime_utc" => Unary(datetime_utc),
-    "encode_uri_component" => Unary(encode_uri_component),
-    "env" => ValueBinaryOpt(env),
-    "env_var" => ValueUnary(env_var),
-    "env_var_or_default" => ValueBinary(env_var_or_default),
-    "error" => Unary(error),
-    "executable_directory" => Nullary(|_| dir("executable", dirs::executable_dir)),
-    "extension" => Unary(extension),
-    "file_name" => Unary(file_name),
-    "file_stem" => Unary(file_stem),
-    "home_directory" => Nullary(|_| dir("home", dirs::home_dir)),
-    "invocation_directory" => Nullary(invocation_directory),
-    "invocation_directory_native" => Nullary(invocation_directory_native),
-    "is_dependency" => ValueNullary(is_dependency),
-    "join" => BinaryPlus(join),
-    "join_list" => BinaryOptValueStrToValue(join_list),
-    "just_executable" => Nullary(just_executable),
-    "just_pid" => Nullary(just_pid),
-    "just_version" => Nullary(just_version),
-    "justfile" => Nullary(justfile),
-    "justfile_directory" => Nullary(justfile_directory),
-    "kebabcase" => Unary(kebabcase),
-    "len" => ValueUnary(len),
-    "lowercamelcase" => Unary(lowercamelcase),
-    "lowercase" => Unary(lowercase),
-    "module_directory" => Nullary(module_directory),
-    "module_file" => Nullary(module_file),
-    "module_path" => Nullary(module_path),
-    "num_cpus" => Nullary(num_cpus),
-    "num_jobs" => ValueNullary(num_jobs),
-    "os" => Nullary(os),
-    "os_family" => Nullary(os_family),
-    "parent_directory" => Unary(parent_directory),
-    "path_exists" => UnaryToValue(path_exists),
-    "prepend" => BinaryStrValue(prepend),
-    "quote" => UnaryMap(quote),
-    "read" => Unary(read),
-    "recipe_name" => Nullary(recipe_name),
-    "replace" => Ternary(replace),
-    "replace_regex" => Ternary(replace_regex),
-    "require" => Unary(require),
-    "runtime_directory" => Nullary(|_| dir("runtime", dirs::runtime_dir)),
-    "semver_matches" => BinaryToValue(semver_matches),
-    "sha256" => Unary(sha256),
-    "sha256_file" => Unary(sha256_file),
-    "shell" => UnaryPlus(shell),
-    "shoutykebabcase" => Unary(shoutykebabcase),
-    "shoutysnakecase" => Unary(shoutysnakecase),
-    "show" => ValueUnary(show),
-    "snakecase" => Unary(snakecase),
-    "source_directory" => Nullary(source_directory),
-    "source_file" => Nullary(source_file),
-    "split" => BinaryOptToValue(split),
-    "style" => BinaryOptValueStr(style),
-    "titlecase" => Unary(titlecase),
-    "trim" => Unary(trim),
-    "trim_end" => Unary(trim_end),
-    "trim_end_match" => Binary(trim_end_match),
-    "trim_end_matches" => Binary(trim_end_matches),
-    "trim_start" => Unary(trim_start),
-    "trim_start_match" => Binary(trim_start_match),
-    "trim_start_matches" => Binary(trim_start_matches),
-    "uppercamelcase" => Unary(uppercamelcase),
-    "uppercase" => Unary(uppercase),
-    "uuid" => Nullary(uuid),
-    "which" => UnaryToValue(which),
-    "without_extension" => Unary(without_extension),
-    _ => return None,
-  };
-  Some(function)
+  FUNCTIONS
+    .iter()
+    .find(|(function, _)| *function == name)
+    .map(|(_, function)| *function)
 }
 
 fn boolean(context: &Context, condition: bool) -> Value {
@@ -504,6 +531,17 @@ fn kebabcase(_context: Context, s: &str) -> StringResult {
   Ok(s.to_kebab_case())
 }
 
+fn keys(_context: Context, value: &Value) -> ValueResult {
+  if value.is_map() {
+    Ok(value.elements().iter().cloned().collect())
+  } else {
+    Err(format!(
+      "value {} is not a map",
+      value.color_display(Color::never())
+    ))
+  }
+}
+
 fn len(_context: Context, value: &Value) -> ValueResult {
   Ok(Value::from(value.len().to_string()))
 }
@@ -878,6 +916,16 @@ fn uuid(_context: Context) -> StringResult {
   Ok(uuid::Uuid::new_v4().to_string())
 }
 
+fn values(_context: Context, value: &Value) -> ValueResult {
+  match value.values() {
+    Some(values) => Ok(values.iter().cloned().collect()),
+    None => Err(format!(
+      "value {} is not a map",
+      value.color_display(Color::never())
+    )),
+  }
+}
+
 fn which(context: Context, name: &str) -> ValueResult {
   Ok(match crate::which(&context, name)? {
     Some(path) => Value::from(path),
@@ -13,3 +13,16 @@ impl<'src> Keyed<'src> for FunctionDefinition<'src> {
     self.name.lexeme()
   }
 }
+
+impl Display for FunctionDefinition<'_> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}(", self.name)?;
+    for (i, (parameter, _number)) in self.parameters.iter().enumerate() {
+      if i > 0 {
+        write!(f, ", ")?;
+      }
+      write!(f, "{parameter}")?;
+    }
+    write!(f, ") := {}", self.body)
+  }
+}
@@ -73,7 +73,7 @@ impl<'src: 'run, 'run> InvocationParser<'src, 'run> {
       recipe
     };
 
-    let mut arguments = vec![Value::new(); recipe.parameters.len()];
+    let mut arguments = vec![Vec::new(); recipe.parameters.len()];
 
     let long = recipe
       .parameters
@@ -152,8 +152,7 @@ impl<'src: 'run, 'run> InvocationParser<'src, 'run> {
         let Some(&index) = positional.get(positional_index) else {
           break;
         };
-        let group = &mut arguments[index];
-        group.push(argument);
+        arguments[index].push((*argument).to_owned());
         if !recipe.parameters[index].kind.is_variadic() {
           positional_index += 1;
         }
@@ -211,6 +210,11 @@ impl<'src: 'run, 'run> InvocationParser<'src, 'run> {
       });
     }
 
+    let arguments = arguments
+      .into_iter()
+      .map(Value::from)
+      .collect::<Vec<Value>>();
+
     for (group, parameter) in arguments.iter().zip(&recipe.parameters) {
       if parameter.value.is_some() {
         continue;
@@ -95,16 +95,7 @@ impl ColorDisplay for Item<'_> {
       Self::Alias(alias) => write!(f, "{alias}"),
       Self::Assignment(assignment) => write!(f, "{assignment}"),
       Self::Comment(comment) => write!(f, "{comment}"),
-      Self::Function(function) => {
-        write!(f, "{}(", function.name)?;
-        for (i, (parameter, _number)) in function.parameters.iter().enumerate() {
-          if i > 0 {
-            write!(f, ", ")?;
-          }
-          write!(f, "{parameter}")?;
-        }
-        write!(f, ") := {}", function.body)
-      }
+      Self::Function(function) => write!(f, "{function}"),
       Self::Import {
         relative, optional, ..
       } => {
@@ -0,0 +1,83 @@
+use super::*;
+
+// Limits the number of simultaneously running recipes, both in total, with
+// `--jobs`, and in each group with a limit in the `job-limits` setting of the
+// group's module.
+pub(crate) struct Jobs {
+  finally: bool,
+  groups: Arc<BTreeMap<(Modulepath, String), Semaphore>>,
+  total: Arc<Semaphore>,
+}
+
+impl Jobs {
+  pub(crate) fn new(config: &Config, justfile: &Justfile) -> Self {
+    fn collect(justfile: &Justfile, groups: &mut BTreeMap<(Modulepath, String), Semaphore>) {
+      for (group, limit) in &justfile.settings.job_limits {
+        groups.insert(
+          (justfile.module_path.clone(), group.clone()),
+          Semaphore::new(*limit),
+        );
+      }
+
+      for module in justfile.modules.values() {
+        collect(module, groups);
+      }
+    }
+
+    let mut groups = BTreeMap::new();
+
+    collect(justfile, &mut groups);
+
+    Self {
+      finally: false,
+      groups: Arc::new(groups),
+      total: Arc::new(Semaphore::new(config.jobs.unwrap_or(NonZeroU64::MAX))),
+    }
+  }
+
+  // Get jobs for running finally dependencies, which share limits with `self`,
+  // but are not stopped when `self` is closed.
+  pub(crate) fn cleanup(&self) -> Self {
+    Self {
+      finally: true,
+      groups: self.groups.clone(),
+      total: self.total.clone(),
+    }
+  }
+
+  // Acquire a job for `recipe`, and for each of its groups which has a limit.
+  // Group jobs are acquired in order, and the total job last, so that recipes
+  // waiting for jobs cannot deadlock, and recipes waiting for a group do not
+  // hold total jobs which recipes in other groups could use.
+  pub(crate) fn acquire(
+    &self,
+    recipe: &Recipe,
+  ) -> Option<(semaphore::Guard, Vec<semaphore::Guard>)> {
+    let groups = recipe
+      .attributes
+      .groups()
+      .into_iter()
+      .map(|group| (recipe.module_path().clone(), group.cooked))
+      .collect::<BTreeSet<(Modulepath, String)>>()
+      .iter()
+      .filter_map(|key| self.groups.get(key))
+      .map(|semaphore| semaphore.acquire(self.finally))
+      .collect::<Option<Vec<semaphore::Guard>>>()?;
+
+    let total = self.total.acquire(self.finally)?;
+
+    Some((total, groups))
+  }
+
+  pub(crate) fn close(&self) {
+    self.total.close();
+
+    for semaphore in self.groups.values() {
+      semaphore.close();
+    }
+  }
+
+  pub(crate) fn is_closed(&self) -> bool {
+    !self.finally && self.total.is_closed()
+  }
+}
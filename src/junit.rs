@@ -0,0 +1,161 @@
+use super::*;
+
+static JUNIT: Mutex<Option<Junit>> = Mutex::new(None);
+
+// Records recipe runs with `--junit`, and writes them as a JUnit XML report,
+// with one test case per recipe run. Like traces, the report is global, so
+// that test cases can be recorded from the threads on which recipes run.
+pub(crate) struct Junit {
+  path: PathBuf,
+  testcases: Vec<Testcase>,
+  timestamp: DateTime<Local>,
+}
+
+impl Junit {
+  pub(crate) fn install(config: &Config) {
+    *JUNIT.lock().unwrap() = config.junit.as_ref().map(|path| Self {
+      path: path.clone(),
+      testcases: Vec::new(),
+      timestamp: Local::now(),
+    });
+  }
+
+  pub(crate) fn testcase(testcase: Testcase) {
+    if let Some(junit) = JUNIT.lock().unwrap().as_mut() {
+      junit.testcases.push(testcase);
+    }
+  }
+
+  // Escape `text` for inclusion in XML. Characters which may not appear in
+  // XML documents, for example the escape characters of ANSI color codes, are
+  // removed.
+  fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+      match c {
+        '&' => escaped.push_str("&amp;"),
+        '<' => escaped.push_str("&lt;"),
+        '>' => escaped.push_str("&gt;"),
+        '"' => escaped.push_str("&quot;"),
+        '\'' => escaped.push_str("&apos;"),
+        '\t' | '\n' | '\r' => escaped.push(c),
+        c if c < ' ' || c == '\u{FFFE}' || c == '\u{FFFF}' => {}
+        c => escaped.push(c),
+      }
+    }
+
+    escaped
+  }
+
+  fn seconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64())
+  }
+
+  // Write the report recorded so far. With `--watch`, this is called after
+  // each run, so the report covers every run.
+  pub(crate) fn save() -> RunResult<'static> {
+    use std::fmt::Write;
+
+    let junit = JUNIT.lock().unwrap();
+
+    let Some(junit) = junit.as_ref() else {
+      return Ok(());
+    };
+
+    let tests = junit.testcases.len();
+
+    let failures = junit
+      .testcases
+      .iter()
+      .filter(|testcase| testcase.failure.is_some())
+      .count();
+
+    let skipped = junit
+      .testcases
+      .iter()
+      .filter(|testcase| testcase.skipped.is_some())
+      .count();
+
+    let time = Self::seconds(
+      junit
+        .testcases
+        .iter()
+        .map(|testcase| testcase.duration)
+        .sum(),
+    );
+
+    let mut xml = String::new();
+
+    writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+
+    writeln!(
+      xml,
+      r#"<testsuites name="just" tests="{tests}" failures="{failures}" errors="0" skipped="{skipped}" time="{time}">"#,
+    )
+    .unwrap();
+
+    writeln!(
+      xml,
+      r#"  <testsuite name="just" tests="{tests}" failures="{failures}" errors="0" skipped="{skipped}" time="{time}" timestamp="{}">"#,
+      junit.timestamp.format("%Y-%m-%dT%H:%M:%S"),
+    )
+    .unwrap();
+
+    for testcase in &junit.testcases {
+      write!(
+        xml,
+        r#"    <testcase name="{}" classname="just" time="{}""#,
+        Self::escape(&testcase.name),
+        Self::seconds(testcase.duration),
+      )
+      .unwrap();
+
+      if testcase.failure.is_none() && testcase.skipped.is_none() && testcase.stderr.is_empty() {
+        writeln!(xml, "/>").unwrap();
+        continue;
+      }
+
+      writeln!(xml, ">").unwrap();
+
+      if let Some(failure) = &testcase.failure {
+        let message = failure.lines().next().unwrap_or_default();
+        writeln!(
+          xml,
+          r#"      <failure message="{}">{}</failure>"#,
+          Self::escape(message),
+          Self::escape(failure),
+        )
+        .unwrap();
+      }
+
+      if let Some(skipped) = testcase.skipped {
+        writeln!(
+          xml,
+          r#"      <skipped message="{}"/>"#,
+          Self::escape(skipped)
+        )
+        .unwrap();
+      }
+
+      if !testcase.stderr.is_empty() {
+        writeln!(
+          xml,
+          "      <system-err>{}</system-err>",
+          Self::escape(&testcase.stderr),
+        )
+        .unwrap();
+      }
+
+      writeln!(xml, "    </testcase>").unwrap();
+    }
+
+    writeln!(xml, "  </testsuite>").unwrap();
+    writeln!(xml, "</testsuites>").unwrap();
+
+    fs::write(&junit.path, xml).map_err(|source| Error::FilesystemIo {
+      path: junit.path.clone(),
+      source,
+    })
+  }
+}
@@ -37,6 +37,8 @@ pub(crate) struct Justfile<'src> {
   #[serde(skip)]
   pub(crate) name: Option<Name<'src>>,
   #[serde(skip)]
+  pub(crate) platform_disabled_recipes: BTreeSet<&'src str>,
+  #[serde(skip)]
   pub(crate) private: bool,
   #[serde(rename = "aliases")]
   pub(crate) recipe_aliases: Table<'src, RecipeAlias<'src>>,
@@ -46,7 +48,7 @@ pub(crate) struct Justfile<'src> {
   pub(crate) unexports: BTreeSet<String>,
   #[serde(skip)]
   pub(crate) unstable_features: BTreeSet<UnstableFeature>,
-  pub(crate) warnings: Vec<Warning>,
+  pub(crate) warnings: Vec<Warning<'src>>,
   #[serde(skip)]
   pub(crate) working_directory: PathBuf,
 }
@@ -195,6 +197,7 @@ impl<'src> Justfile<'src> {
     &self,
     config: &Config,
     search: &Search,
+    cache: &Cache,
     arguments: &[String],
     overrides: &HashMap<Number, String>,
   ) -> RunResult<'src> {
@@ -249,24 +252,116 @@ impl<'src> Justfile<'src> {
         )?;
 
         let ran = Ran::new();
-        let cache = Cache::new(search);
-        let jobs = Semaphore::new(config.jobs.unwrap_or(NonZeroU64::MAX));
-        for invocation in invocations {
-          Self::run_recipe(
-            &invocation.arguments,
-            config,
-            false,
-            overrides,
-            &ran,
-            invocation.recipe,
-            &scopes,
-            search,
-            &cache,
-            &jobs,
-          )?;
+        let jobs = Jobs::new(config, self);
+        let locks = Locks::new();
+
+        Events::emit(Event::RunStart {
+          just_version: env!("CARGO_PKG_VERSION"),
+          recipes: invocations
+            .iter()
+            .map(|invocation| invocation.recipe.recipe_path())
+            .collect(),
+          version: Events::VERSION,
+        });
+
+        // Recipes disabled on this platform by OS attributes never run, but are
+        // reported as skipped, along with the recipes of the same module.
+        for module in invocations
+          .iter()
+          .map(|invocation| invocation.recipe.module_path())
+          .collect::<BTreeSet<&Modulepath>>()
+        {
+          for name in &self.submodule(module).unwrap().platform_disabled_recipes {
+            Junit::testcase(Testcase {
+              duration: Duration::ZERO,
+              failure: None,
+              name: module.join(name).to_string(),
+              skipped: Some("disabled on this platform"),
+              stderr: String::new(),
+            });
+          }
         }
 
-        Ok(())
+        let start = Instant::now();
+
+        let result = if config.parallel {
+          thread::scope(|thread_scope| {
+            let mut handles = Vec::new();
+            for invocation in &invocations {
+              let (ran, scopes, jobs, locks) = (&ran, &scopes, &jobs, &locks);
+              handles.push(thread_scope.spawn(move || {
+                let result = Self::run_recipe(
+                  &invocation.arguments,
+                  config,
+                  false,
+                  overrides,
+                  ran,
+                  invocation.recipe,
+                  scopes,
+                  search,
+                  cache,
+                  jobs,
+                  locks,
+                );
+                if result.is_err() && !config.keep_going {
+                  jobs.close();
+                }
+                result
+              }));
+            }
+
+            let mut results = Vec::new();
+            for handle in handles {
+              results.push(
+                handle
+                  .join()
+                  .map_err(|_| Error::internal("parallel invocation thread panicked"))?,
+              );
+            }
+
+            Self::collect(config, Self::skipped_last(results)).map(drop)
+          })
+        } else {
+          Self::collect(
+            config,
+            invocations.iter().map(|invocation| {
+              Self::run_recipe(
+                &invocation.arguments,
+                config,
+                false,
+                overrides,
+                &ran,
+                invocation.recipe,
+                &scopes,
+                search,
+                cache,
+                &jobs,
+                &locks,
+              )
+            }),
+          )
+          .map(drop)
+        };
+
+        // A single failure is reported as if `--keep-going` was not passed.
+        let result = result.map_err(|error| match error {
+          Error::Multiple { mut failures } if failures.len() == 1 => failures.pop().unwrap().1,
+          error => error,
+        });
+
+        Events::emit(Event::RunFinish {
+          code: match &result {
+            Ok(()) => 0,
+            Err(error) => error.code().unwrap_or(EXIT_FAILURE),
+          },
+          duration: start.elapsed().as_secs_f64(),
+          success: result.is_ok(),
+        });
+
+        let junit = Junit::save();
+        let trace = Trace::save();
+
+        result.and(junit).and(trace)
       }
       Subcommand::Command {
         binary, arguments, ..
@@ -344,7 +439,12 @@ impl<'src> Justfile<'src> {
         let scope = scopes.get(&module.module_path).unwrap().1;
 
         if let Some(assignment) = variable {
-          print!("{}", scope.value(assignment.number).unwrap().join());
+          let value = scope.value(assignment.number).unwrap();
+          if value.is_map() {
+            print!("{}", value.color_display(config.color.stdout()));
+          } else {
+            print!("{}", value.join());
+          }
         } else {
           let mut bindings = scope
             .bindings()
@@ -367,6 +467,9 @@ impl<'src> Justfile<'src> {
                   binding.value.color_display(config.color.stdout()),
                 );
               }
+              // Shell variables cannot hold maps, so map bindings are
+              // skipped.
+              EvaluateFormat::Shell if binding.value.is_map() => {}
               EvaluateFormat::Shell => {
                 if binding.export || module.settings.export {
                   print!("export ");
@@ -508,16 +611,70 @@ impl<'src> Justfile<'src> {
     scopes: &Scopes<'src, '_>,
     search: &Search,
     cache: &Cache,
-    jobs: &Semaphore,
-  ) -> RunResult<'src> {
+    jobs: &Jobs,
+    locks: &Locks,
+  ) -> RunResult<'src, Fingerprint> {
     let mutex = ran.mutex(recipe, arguments);
 
     let mut guard = mutex.lock().unwrap();
 
-    if *guard {
-      return Ok(());
+    match &*guard {
+      Some(Ok(fingerprint)) => return Ok(fingerprint.clone()),
+      // The recipe failed with `--keep-going`, and its error is reported by
+      // the invocation which ran it.
+      Some(Err(())) => {
+        return Err(Error::Multiple {
+          failures: Vec::new(),
+        });
+      }
+      None => {}
+    }
+
+    let result = Self::run_recipe_unchecked(
+      arguments,
+      config,
+      is_dependency,
+      overrides,
+      ran,
+      recipe,
+      scopes,
+      search,
+      cache,
+      jobs,
+      locks,
+    );
+
+    match result {
+      Ok(fingerprint) => {
+        *guard = Some(Ok(fingerprint.clone()));
+        Ok(fingerprint)
+      }
+      Err(error) if config.keep_going => {
+        *guard = Some(Err(()));
+        Err(match error {
+          Error::Multiple { .. } => error,
+          error => Error::Multiple {
+            failures: vec![(recipe.recipe_path().clone(), error)],
+          },
+        })
+      }
+      Err(error) => Err(error),
     }
+  }
 
+  fn run_recipe_unchecked(
+    arguments: &[Value],
+    config: &Config,
+    is_dependency: bool,
+    overrides: &HashMap<Number, String>,
+    ran: &Ran,
+    recipe: &Recipe<'src>,
+    scopes: &Scopes<'src, '_>,
+    search: &Search,
+    cache: &Cache,
+    jobs: &Jobs,
+    locks: &Locks,
+  ) -> RunResult<'src, Fingerprint> {
     let (module, scope, dotenv) = scopes
       .get(recipe.module_path())
       .expect("failed to retrieve scope for module");
@@ -556,34 +713,70 @@ impl<'src> Justfile<'src> {
       });
     }
 
-    Self::run_dependencies(
-      config,
-      &context,
-      recipe.priors(),
-      recipe,
-      &mut evaluator,
-      overrides,
-      ran,
-      scopes,
-      search,
-      cache,
-      jobs,
-    )?;
+    let result = (|| {
+      let dependencies = Self::run_dependencies(
+        config,
+        &context,
+        recipe.priors(),
+        recipe,
+        &mut evaluator,
+        overrides,
+        ran,
+        scopes,
+        search,
+        cache,
+        jobs,
+        locks,
+      )?;
 
-    recipe.run(
-      &context,
-      &env,
-      is_dependency,
-      &positional,
-      &scope,
-      cache,
-      jobs,
-    )?;
+      Trace::priors(recipe, arguments, &dependencies);
+
+      let record = recipe.run(
+        &context,
+        arguments,
+        &dependencies,
+        &env,
+        is_dependency,
+        &positional,
+        &scope,
+        cache,
+        jobs,
+        locks,
+      )?;
+
+      let subsequents = Self::run_dependencies(
+        config,
+        &context,
+        recipe.subsequents(),
+        recipe,
+        &mut evaluator,
+        overrides,
+        &Ran::new(),
+        scopes,
+        search,
+        cache,
+        jobs,
+        locks,
+      )?;
+
+      Trace::subsequents(recipe, arguments, &subsequents);
+
+      Fingerprint::new(recipe, arguments, cache, &dependencies, record)
+    })();
+
+    if recipe.finally().is_empty() {
+      return result;
+    }
 
-    Self::run_dependencies(
+    // Finally dependencies run even if the recipe failed or was interrupted,
+    // or `--parallel` invocations have stopped, so the signal which
+    // interrupted the recipe is cleared while they run, and restored after.
+    let caught = SignalHandler::take();
+
+    let cleanup = Self::run_dependencies(
       config,
       &context,
-      recipe.subsequents(),
+      recipe.finally(),
       recipe,
       &mut evaluator,
       overrides,
@@ -591,12 +784,38 @@ impl<'src> Justfile<'src> {
       scopes,
       search,
       cache,
-      jobs,
-    )?;
+      &jobs.cleanup(),
+      locks,
+    );
 
-    *guard = true;
+    SignalHandler::restore(caught);
 
-    Ok(())
+    if let Ok(finally) = &cleanup {
+      Trace::subsequents(recipe, arguments, finally);
+    }
+
+    match (result, cleanup) {
+      (result, Ok(_)) => result,
+      (Ok(_), Err(error)) => Err(error),
+      // With `--keep-going`, failed finally dependencies are reported along
+      // with the recipe's failure.
+      (Err(error), Err(Error::Multiple { failures: cleanup })) if config.keep_going => {
+        let mut failures = match error {
+          Error::Multiple { failures } => failures,
+          error => vec![(recipe.recipe_path().clone(), error)],
+        };
+        failures.extend(cleanup);
+        Err(Error::Multiple { failures })
+      }
+      // Otherwise the original error is returned, and the finally
+      // dependency's error is printed, so that it is not masked.
+      (Err(error), Err(cleanup)) => {
+        if !config.verbosity.quiet() && cleanup.print_message() {
+          eprintln!("{}", cleanup.color_display(config.color.stderr()));
+        }
+        Err(error)
+      }
+    }
   }
 
   fn run_dependencies<'run>(
@@ -610,10 +829,11 @@ impl<'src> Justfile<'src> {
     scopes: &Scopes<'src, 'run>,
     search: &Search,
     cache: &Cache,
-    jobs: &Semaphore,
-  ) -> RunResult<'src> {
+    jobs: &Jobs,
+    locks: &Locks,
+  ) -> RunResult<'src, Vec<Fingerprint>> {
     if context.config.no_dependencies {
-      return Ok(());
+      return Ok(Vec::new());
     }
 
     let mut evaluated = Vec::new();
@@ -652,31 +872,78 @@ impl<'src> Justfile<'src> {
     }
 
     if dependent.is_parallel() {
-      thread::scope::<_, RunResult>(|thread_scope| {
+      thread::scope(|thread_scope| {
         let mut handles = Vec::new();
         for (recipe, arguments) in evaluated {
           handles.push(thread_scope.spawn(move || {
             Self::run_recipe(
-              &arguments, config, true, overrides, ran, recipe, scopes, search, cache, jobs,
+              &arguments, config, true, overrides, ran, recipe, scopes, search, cache, jobs, locks,
             )
           }));
         }
+        let mut results = Vec::new();
         for handle in handles {
-          handle
-            .join()
-            .map_err(|_| Error::internal("parallel dependency thread panicked"))??;
+          results.push(
+            handle
+              .join()
+              .map_err(|_| Error::internal("parallel dependency thread panicked"))?,
+          );
         }
-        Ok(())
-      })?;
+        Self::collect(config, Self::skipped_last(results))
+      })
     } else {
-      for (recipe, arguments) in evaluated {
-        Self::run_recipe(
-          &arguments, config, true, overrides, ran, recipe, scopes, search, cache, jobs,
-        )?;
+      Self::collect(
+        config,
+        evaluated.into_iter().map(|(recipe, arguments)| {
+          Self::run_recipe(
+            &arguments, config, true, overrides, ran, recipe, scopes, search, cache, jobs, locks,
+          )
+        }),
+      )
+    }
+  }
+
+  // Collect the results of running recipes. Without `--keep-going`, the
+  // first error is returned, and since `results` is consumed lazily, no
+  // further recipes are run. With `--keep-going`, every recipe is run, and the
+  // failures of all recipes which failed are returned together.
+  fn collect<T>(
+    config: &Config,
+    results: impl IntoIterator<Item = RunResult<'src, T>>,
+  ) -> RunResult<'src, Vec<T>> {
+    if !config.keep_going {
+      return results.into_iter().collect();
+    }
+
+    let mut values = Vec::new();
+    let mut failed = false;
+    let mut failures = Vec::new();
+
+    for result in results {
+      match result {
+        Ok(value) => values.push(value),
+        Err(Error::Multiple { failures: more }) => {
+          failed = true;
+          failures.extend(more);
+        }
+        Err(error) => return Err(error),
       }
     }
 
-    Ok(())
+    if failed {
+      Err(Error::Multiple { failures })
+    } else {
+      Ok(values)
+    }
+  }
+
+  // Move the results of recipes which were skipped because a `--parallel`
+  // invocation failed after all other results, so that the failure which
+  // caused them to be skipped is reported instead. The sort is stable, so the
+  // order of other results is unchanged.
+  fn skipped_last<T>(mut results: Vec<RunResult<'src, T>>) -> Vec<RunResult<'src, T>> {
+    results.sort_by_key(|result| matches!(result, Err(Error::Skipped { .. })));
+    results
   }
 
   pub(crate) fn public_modules(&self, config: &Config) -> Vec<&Justfile> {
@@ -7,6 +7,7 @@ pub(crate) enum Keyword {
   AllowDuplicateRecipes,
   AllowDuplicateVariables,
   Assert,
+  CacheDirectory,
   DefaultList,
   DefaultScript,
   DotenvCommand,
@@ -26,12 +27,14 @@ pub(crate) enum Keyword {
   IgnoreComments,
   Import,
   Indentation,
+  JobLimits,
   Lazy,
   Lists,
   MinimumVersion,
   Mod,
   NoCd,
   NoExitMessage,
+  Output,
   PositionalArguments,
   Quiet,
   ScriptInterpreter,
@@ -22,7 +22,10 @@ pub(crate) use {
     cache_entry::CacheEntry,
     cache_key::CacheKey,
     cache_lock::CacheLock,
+    cache_record::CacheRecord,
     cache_status::CacheStatus,
+    capture::Capture,
+    ci_format::CiFormat,
     clean::Clean,
     color::Color,
     color_display::ColorDisplay,
@@ -53,10 +56,14 @@ pub(crate) use {
     error::Error,
     evaluate_format::EvaluateFormat,
     evaluator::Evaluator,
+    event::Event,
+    events::Events,
+    events_format::EventsFormat,
     execution_context::ExecutionContext,
     executor::Executor,
     expression::Expression,
     expression_context::ExpressionContext,
+    fingerprint::Fingerprint,
     format_string_part::FormatStringPart,
     fragment::Fragment,
     function::Function,
@@ -66,18 +73,24 @@ pub(crate) use {
     invocation::Invocation,
     invocation_parser::InvocationParser,
     item::{Item, ItemKind},
+    jobs::Jobs,
+    junit::Junit,
     justfile::Justfile,
     keyed::Keyed,
     keyword::Keyword,
     layer::Layer,
     lexer::Lexer,
     line::Line,
+    lint::Lint,
+    linter::Linter,
     list::List,
     list_entry::ListEntry,
     list_feature::ListFeature,
     list_operator::ListOperator,
     load_dotenv::load_dotenv,
     loader::Loader,
+    locks::Locks,
+    lsp::Lsp,
     modulepath::Modulepath,
     name::Name,
     namepath::Namepath,
@@ -85,6 +98,7 @@ pub(crate) use {
     numerator::Numerator,
     ordinal::Ordinal,
     output_error::OutputError,
+    output_mode::OutputMode,
     parameter::Parameter,
     parameter_kind::ParameterKind,
     parser::Parser,
@@ -108,7 +122,7 @@ pub(crate) use {
     search_error::SearchError,
     semaphore::Semaphore,
     set::Set,
-    setting::Setting,
+    setting::{Setting, SettingKind},
     settings::Settings,
     shebang::Shebang,
     shell::Shell,
@@ -117,7 +131,10 @@ pub(crate) use {
     sigil::Sigil,
     signal::Signal,
     signal_handler::SignalHandler,
+    size::Size,
     source::Source,
+    span::Span,
+    stale::Stale,
     string_context::StringContext,
     string_delimiter::StringDelimiter,
     string_kind::StringKind,
@@ -127,10 +144,16 @@ pub(crate) use {
     subcommand::Subcommand,
     suggestion::Suggestion,
     switch::Switch,
+    symbol::Symbol,
+    symbol_index::SymbolIndex,
     table::Table,
+    tail::Tail,
     tangle::tangle,
+    testcase::Testcase,
+    timespan::Timespan,
     token::Token,
     token_kind::TokenKind,
+    trace::Trace,
     unresolved_dependency::UnresolvedDependency,
     unresolved_recipe::UnresolvedRecipe,
     unstable_feature::UnstableFeature,
@@ -141,6 +164,7 @@ pub(crate) use {
     verbosity::Verbosity,
     version::Version,
     warning::Warning,
+    watch::Watch,
     which::which,
   },
   camino::Utf8Path,
@@ -148,6 +172,8 @@ pub(crate) use {
   clap::{CommandFactory, FromArgMatches, Parser as _, ValueEnum},
   clap_complete::{ArgValueCompleter, CompletionCandidate, PathCompleter, engine::ValueCompleter},
   digest_io::HashWriter,
+  globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder},
+  ignore::WalkBuilder,
   libc::EXIT_FAILURE,
   rand::seq::IndexedRandom,
   regex::Regex,
@@ -164,8 +190,8 @@ pub(crate) use {
     env::{self, VarError},
     ffi::{OsStr, OsString},
     fmt::{self, Debug, Display, Formatter},
-    fs::{self, File},
-    io::{self, Seek, Sink, Write},
+    fs::{self, File, TryLockError},
+    io::{self, BufRead, Read, Seek, Sink, Write},
     iter::{self, FromIterator},
     mem,
     num::{NonZeroU64, ParseIntError},
@@ -175,12 +201,15 @@ pub(crate) use {
     process::{self, Command, ExitStatus, Stdio},
     slice,
     str::{self, Chars, FromStr},
-    sync::{Arc, Condvar, LazyLock, Mutex, MutexGuard},
+    sync::{
+      Arc, Condvar, LazyLock, Mutex, MutexGuard,
+      mpsc::{self, RecvTimeoutError},
+    },
     thread,
-    time::Instant,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
     vec,
   },
-  strum::{Display, EnumDiscriminants, EnumIter, EnumString, IntoStaticStr},
+  strum::{Display, EnumDiscriminants, EnumIter, EnumString, IntoEnumIterator, IntoStaticStr},
   tempfile::TempDir,
   typed_arena::Arena,
   unicode_width::{UnicodeWidthChar, UnicodeWidthStr},
@@ -245,7 +274,10 @@ mod cache;
 mod cache_entry;
 mod cache_key;
 mod cache_lock;
+mod cache_record;
 mod cache_status;
+mod capture;
+mod ci_format;
 mod clean;
 mod color;
 mod color_display;
@@ -276,11 +308,15 @@ mod environment;
 mod error;
 mod evaluate_format;
 mod evaluator;
+mod event;
+mod events;
+mod events_format;
 mod execution_context;
 mod executor;
 mod expression;
 mod expression_context;
 mod filesystem;
+mod fingerprint;
 mod format_string_part;
 mod fragment;
 mod function;
@@ -290,18 +326,24 @@ mod interpreter;
 mod invocation;
 mod invocation_parser;
 mod item;
+mod jobs;
+mod junit;
 mod justfile;
 mod keyed;
 mod keyword;
 mod layer;
 mod lexer;
 mod line;
+mod lint;
+mod linter;
 mod list;
 mod list_entry;
 mod list_feature;
 mod list_operator;
 mod load_dotenv;
 mod loader;
+mod locks;
+mod lsp;
 mod modulepath;
 mod name;
 mod namepath;
@@ -309,6 +351,7 @@ mod number;
 mod numerator;
 mod ordinal;
 mod output_error;
+mod output_mode;
 mod parameter;
 mod parameter_kind;
 mod parser;
@@ -343,7 +386,10 @@ mod signal;
 mod signal_handler;
 #[cfg(unix)]
 mod signals;
+mod size;
 mod source;
+mod span;
+mod stale;
 mod string_context;
 mod string_delimiter;
 mod string_kind;
@@ -353,10 +399,16 @@ mod style;
 mod subcommand;
 mod suggestion;
 mod switch;
+mod symbol;
+mod symbol_index;
 mod table;
+mod tail;
 mod tangle;
+mod testcase;
+mod timespan;
 mod token;
 mod token_kind;
+mod trace;
 mod unindent;
 mod unresolved_dependency;
 mod unresolved_recipe;
@@ -368,4 +420,5 @@ mod variable_resolver;
 mod verbosity;
 mod version;
 mod warning;
+mod watch;
 mod which;
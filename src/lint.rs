@@ -0,0 +1,15 @@
+use super::*;
+
+#[derive(
+  Clone, Copy, Debug, Display, EnumString, Eq, IntoStaticStr, Ord, PartialEq, PartialOrd, Serialize,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub(crate) enum Lint {
+  DuplicateDependency,
+  ParameterShadowsVariable,
+  RedundantSetting,
+  UnusedParameter,
+  UnusedPrivateRecipe,
+  UnusedVariable,
+}
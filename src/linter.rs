@@ -0,0 +1,358 @@
+use super::*;
+
+pub(crate) struct Linter<'src: 'run, 'run> {
+  assignments: &'run Table<'src, Assignment<'src>>,
+  default: Option<&'run Arc<Recipe<'src>>>,
+  disabled: &'run [&'run Item<'src>],
+  functions: &'run Table<'src, FunctionDefinition<'src>>,
+  modulepath: &'run Modulepath,
+  recipe_aliases: &'run Table<'src, RecipeAlias<'src>>,
+  recipes: &'run Table<'src, Arc<Recipe<'src>>>,
+  settings: &'run Settings,
+  variable_references: &'run HashSet<Number>,
+  variable_resolver: &'run VariableResolver<'src, 'run>,
+  warnings: Vec<Warning<'src>>,
+}
+
+impl<'src: 'run, 'run> Linter<'src, 'run> {
+  pub(crate) fn lint(
+    assignments: &'run Table<'src, Assignment<'src>>,
+    default: Option<&'run Arc<Recipe<'src>>>,
+    disabled: &'run [&'run Item<'src>],
+    functions: &'run Table<'src, FunctionDefinition<'src>>,
+    modulepath: &'run Modulepath,
+    modules: &mut Table<'src, Justfile<'src>>,
+    recipe_aliases: &'run Table<'src, RecipeAlias<'src>>,
+    recipes: &'run Table<'src, Arc<Recipe<'src>>>,
+    settings: &'run Settings,
+    variable_references: &'run HashSet<Number>,
+    variable_resolver: &'run VariableResolver<'src, 'run>,
+  ) -> Vec<Warning<'src>> {
+    let mut linter = Self {
+      assignments,
+      default,
+      disabled,
+      functions,
+      modulepath,
+      recipe_aliases,
+      recipes,
+      settings,
+      variable_references,
+      variable_resolver,
+      warnings: Vec::new(),
+    };
+
+    linter.lint_variables();
+
+    for recipe in recipes.values() {
+      linter.lint_recipe(recipe);
+    }
+
+    linter.lint_private_recipes(modules);
+
+    linter.warnings
+  }
+
+  fn lint_private_recipes(&mut self, modules: &mut Table<'src, Justfile<'src>>) {
+    let mut referenced = HashSet::new();
+
+    if let Some(default) = self.default {
+      referenced.insert(default.recipe_path().clone());
+    }
+
+    for alias in self.recipe_aliases.values() {
+      referenced.insert(alias.target.recipe_path().clone());
+    }
+
+    for recipe in self.recipes.values() {
+      for dependency in &recipe.dependencies {
+        referenced.insert(dependency.recipe.recipe_path().clone());
+      }
+    }
+
+    // Dependencies of recipes disabled on this platform are not resolved, so
+    // their paths are constructed from their names.
+    for item in self.disabled {
+      if let Item::Recipe(recipe) = item {
+        for dependency in &recipe.dependencies {
+          let mut path = self.modulepath.clone();
+          path
+            .components
+            .extend(dependency.recipe.iter().map(|name| name.lexeme().into()));
+          referenced.insert(path);
+        }
+      }
+    }
+
+    // Private recipes may also be run by recipe bodies, for example with
+    // `just _helper`, so those mentioned in the text of a body are used.
+    let text = self
+      .recipes
+      .values()
+      .map(|recipe| &recipe.body)
+      .chain(self.disabled.iter().filter_map(|item| match item {
+        Item::Recipe(recipe) => Some(&recipe.body),
+        _ => None,
+      }))
+      .flatten()
+      .flat_map(|line| &line.fragments)
+      .filter_map(|fragment| match fragment {
+        Fragment::Text { token } => Some(token.lexeme()),
+        Fragment::Interpolation { .. } => None,
+      })
+      .collect::<Vec<&str>>();
+
+    let mentioned = |name: &str| text.iter().any(|text| Self::mentions(text, name));
+
+    for recipe in self.recipes.values() {
+      if recipe.private
+        && !referenced.contains(recipe.recipe_path())
+        && !mentioned(recipe.name.lexeme())
+        && !recipe.attributes.allows(Lint::UnusedPrivateRecipe)
+      {
+        self.warnings.push(Warning::UnusedPrivateRecipe {
+          recipe: recipe.name,
+        });
+      }
+    }
+
+    // Recipes in submodules may be dependencies of recipes in this module, so
+    // retract warnings about those which are.
+    let mut stack = modules.values_mut().collect::<Vec<&mut Justfile>>();
+
+    while let Some(module) = stack.pop() {
+      let recipes = &module.recipes;
+
+      module.warnings.retain(|warning| match warning {
+        Warning::UnusedPrivateRecipe { recipe } => {
+          !mentioned(recipe.lexeme())
+            && recipes
+              .get(recipe.lexeme())
+              .is_none_or(|recipe| !referenced.contains(recipe.recipe_path()))
+        }
+        _ => true,
+      });
+
+      stack.extend(module.modules.values_mut());
+    }
+  }
+
+  fn lint_recipe(&mut self, recipe: &Recipe<'src>) {
+    // Each group of dependencies runs with its own record of which recipes
+    // have run, so dependencies are only duplicates within a group.
+    for group in [recipe.priors(), recipe.subsequents(), recipe.finally()] {
+      let mut dependencies = HashSet::new();
+
+      for dependency in group {
+        let arguments = dependency
+          .arguments
+          .iter()
+          .flatten()
+          .map(ToString::to_string)
+          .collect::<Vec<String>>();
+
+        if !dependencies.insert((dependency.recipe.recipe_path(), arguments))
+          && !recipe.attributes.allows(Lint::DuplicateDependency)
+        {
+          self.warnings.push(Warning::DuplicateDependency {
+            dependency: dependency.path.clone(),
+            recipe: recipe.name.lexeme(),
+          });
+        }
+      }
+    }
+
+    for parameter in &recipe.parameters {
+      if self.assignments.contains_key(parameter.name.lexeme())
+        && !recipe.attributes.allows(Lint::ParameterShadowsVariable)
+      {
+        self.warnings.push(Warning::ParameterShadowsVariable {
+          parameter: parameter.name,
+          recipe: recipe.name.lexeme(),
+        });
+      }
+    }
+
+    // Parameters are also used if they are exported to, or passed as
+    // positional arguments to, the recipe's commands, and the parameters of
+    // recipes without a body only document their interface.
+    if self.settings.export
+      || recipe.body.is_empty()
+      || recipe.takes_positional_arguments(self.settings)
+      || recipe.attributes.allows(Lint::UnusedParameter)
+    {
+      return;
+    }
+
+    let references = Self::parameter_references(recipe);
+
+    for parameter in &recipe.parameters {
+      if !parameter.export && !references.contains(parameter.name.lexeme()) {
+        self.warnings.push(Warning::UnusedParameter {
+          parameter: parameter.name,
+          recipe: recipe.name.lexeme(),
+        });
+      }
+    }
+  }
+
+  fn lint_variables(&mut self) {
+    let mut used = self.variable_references.clone();
+
+    for recipe in self.recipes.values() {
+      used.extend(&recipe.variable_references);
+    }
+
+    for assignment in self.assignments.values() {
+      self.variable_resolver.collect_references(
+        &assignment.value,
+        &ExpressionContext::new(),
+        &mut used,
+        &mut HashSet::new(),
+      );
+    }
+
+    for function in self.functions.values() {
+      self.variable_resolver.collect_references(
+        &function.body,
+        &function.parameters.as_slice().into(),
+        &mut used,
+        &mut HashSet::new(),
+      );
+    }
+
+    let disabled = Self::disabled_references(self.disabled);
+
+    // Public variables may be evaluated with `--evaluate` or overridden on the
+    // command line, so only private variables are linted.
+    for assignment in self.assignments.values() {
+      if assignment.private
+        && !used.contains(&assignment.number)
+        && !disabled.contains(assignment.name.lexeme())
+        && !assignment.export
+        && !self.settings.export
+        && !assignment.attributes.allows(Lint::UnusedVariable)
+      {
+        self.warnings.push(Warning::UnusedVariable {
+          variable: assignment.name,
+        });
+      }
+    }
+  }
+
+  // Names of variables referenced by items which are disabled on this
+  // platform, which are not resolved.
+  fn disabled_references(disabled: &[&Item<'src>]) -> HashSet<&'src str> {
+    let mut expressions = Vec::new();
+
+    for item in disabled {
+      match item {
+        Item::Assignment(assignment) => expressions.push(&assignment.value),
+        Item::Function(function) => expressions.push(&function.body),
+        Item::Recipe(recipe) => {
+          for parameter in &recipe.parameters {
+            expressions.extend(&parameter.default);
+            expressions.extend(&parameter.value);
+          }
+
+          for dependency in &recipe.dependencies {
+            expressions.extend(
+              dependency
+                .arguments
+                .iter()
+                .map(|argument| &argument.expression),
+            );
+          }
+
+          expressions.extend(Self::interpolations(&recipe.body));
+        }
+        _ => {}
+      }
+    }
+
+    Self::variables(expressions)
+  }
+
+  // Whether `text` contains `name` as a whole word, that is, not as part of a
+  // longer identifier.
+  fn mentions(text: &str, name: &str) -> bool {
+    let identifier = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+
+    text.match_indices(name).any(|(i, _)| {
+      !text[..i].ends_with(identifier) && !text[i + name.len()..].starts_with(identifier)
+    })
+  }
+
+  fn interpolations<'a>(body: &'a [Line<'src>]) -> impl Iterator<Item = &'a Expression<'src>> {
+    body
+      .iter()
+      .flat_map(|line| &line.fragments)
+      .filter_map(|fragment| match fragment {
+        Fragment::Interpolation { expression } => Some(expression),
+        Fragment::Text { .. } => None,
+      })
+  }
+
+  // Names of variables referenced by expressions in which a recipe's
+  // parameters are in scope.
+  fn parameter_references(recipe: &Recipe<'src>) -> HashSet<&'src str> {
+    let mut expressions = Vec::new();
+
+    for parameter in &recipe.parameters {
+      expressions.extend(&parameter.default);
+      expressions.extend(&parameter.value);
+    }
+
+    for dependency in &recipe.dependencies {
+      expressions.extend(
+        dependency
+          .arguments
+          .iter()
+          .flatten()
+          .map(|argument| &argument.expression),
+      );
+    }
+
+    for attribute in &recipe.attributes {
+      match attribute {
+        Attribute::Cache {
+          env,
+          exclude,
+          extra,
+          inputs,
+          outputs,
+          ..
+        } => {
+          expressions.extend(
+            [env, exclude, extra, inputs, outputs]
+              .into_iter()
+              .filter_map(Option::as_ref),
+          );
+        }
+        Attribute::Confirm(Some(expression))
+        | Attribute::Timestamp(Some(expression))
+        | Attribute::WorkingDirectory(expression) => expressions.push(expression),
+        Attribute::Stale { sources, targets } => {
+          expressions.extend(sources);
+          expressions.push(targets);
+        }
+        _ => {}
+      }
+    }
+
+    expressions.extend(Self::interpolations(&recipe.body));
+
+    Self::variables(expressions)
+  }
+
+  fn variables(expressions: Vec<&Expression<'src>>) -> HashSet<&'src str> {
+    expressions
+      .into_iter()
+      .flat_map(Expression::references)
+      .filter_map(|reference| match reference {
+        Reference::Call { .. } => None,
+        Reference::Variable(name) => Some(name.lexeme()),
+      })
+      .collect()
+  }
+}
@@ -8,16 +8,20 @@ pub(crate) enum ListFeature {
   ComparisonOperator,
   Flag,
   IfWithoutElse,
+  IndexOperator,
   JoinListFunction,
+  KeysFunction,
   ListConcatenationOperator,
   ListLiteral,
   LogicalOperator,
+  MapLiteral,
   Multiple,
   NegationOperator,
   NonComparisonCondition,
   NumJobsFunction,
   ShowFunction,
   SplitFunction,
+  ValuesFunction,
   WhichFunction,
 }
 
@@ -26,18 +30,22 @@ impl ListFeature {
     match self {
       Self::BoolFunction
       | Self::JoinListFunction
+      | Self::KeysFunction
       | Self::NumJobsFunction
       | Self::ShowFunction
       | Self::SplitFunction
+      | Self::ValuesFunction
       | Self::WhichFunction => true,
       Self::ArgMax
       | Self::ArgMin
       | Self::ComparisonOperator
       | Self::Flag
       | Self::IfWithoutElse
+      | Self::IndexOperator
       | Self::ListConcatenationOperator
       | Self::ListLiteral
       | Self::LogicalOperator
+      | Self::MapLiteral
       | Self::Multiple
       | Self::NegationOperator
       | Self::NonComparisonCondition => false,
@@ -54,12 +62,15 @@ impl Display for ListFeature {
       Self::ComparisonOperator => write!(f, "comparison operators require `set lists`"),
       Self::Flag => write!(f, "`flag` arguments require `set lists`"),
       Self::IfWithoutElse => write!(f, "`if` without `else` requires `set lists`"),
+      Self::IndexOperator => write!(f, "index operator requires `set lists`"),
       Self::JoinListFunction => write!(f, "the `join_list()` function requires `set lists`"),
+      Self::KeysFunction => write!(f, "the `keys()` function requires `set lists`"),
       Self::ListConcatenationOperator => {
         write!(f, "list concatenation operator `++` requires `set lists`")
       }
       Self::ListLiteral => write!(f, "list literals require `set lists`"),
       Self::LogicalOperator => write!(f, "logical operators require `set lists`"),
+      Self::MapLiteral => write!(f, "map literals require `set lists`"),
       Self::Multiple => write!(f, "`[arg(multiple)]` requires `set lists`"),
       Self::NegationOperator => write!(f, "negation operator requires `set lists`"),
       Self::NonComparisonCondition => write!(
@@ -69,6 +80,7 @@ impl Display for ListFeature {
       Self::NumJobsFunction => write!(f, "the `num_jobs()` function requires `set lists`"),
       Self::ShowFunction => write!(f, "the `show()` function requires `set lists`"),
       Self::SplitFunction => write!(f, "the `split()` function requires `set lists`"),
+      Self::ValuesFunction => write!(f, "the `values()` function requires `set lists`"),
       Self::WhichFunction => write!(f, "the `which()` function requires `set lists`"),
     }
   }
@@ -1,15 +1,23 @@
 use super::*;
 
 pub(crate) struct Loader {
+  documents: HashMap<PathBuf, String>,
   paths: Arena<PathBuf>,
   srcs: Arena<String>,
 }
 
 impl Loader {
   pub(crate) fn new() -> Self {
+    Self::with_documents(HashMap::new())
+  }
+
+  /// Create a loader which reads `documents`, such as unsaved editor buffers,
+  /// in place of the files at their paths
+  pub(crate) fn with_documents(documents: HashMap<PathBuf, String>) -> Self {
     Self {
-      srcs: Arena::new(),
+      documents,
       paths: Arena::new(),
+      srcs: Arena::new(),
     }
   }
 
@@ -21,10 +29,14 @@ impl Loader {
   ) -> RunResult<'src, (&'src Path, &'src str)> {
     Config::warn_non_unicode_path(config.color, "justfile", path);
 
-    let src = fs::read_to_string(path).map_err(|io_error| Error::Load {
-      path: path.into(),
-      io_error,
-    })?;
+    let src = if let Some(document) = self.documents.get(path) {
+      document.clone()
+    } else {
+      fs::read_to_string(path).map_err(|io_error| Error::Load {
+        path: path.into(),
+        io_error,
+      })?
+    };
 
     let relative = path.strip_prefix(root.parent().unwrap()).unwrap_or(path);
 
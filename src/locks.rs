@@ -0,0 +1,143 @@
+use super::*;
+
+// Named locks held by running recipes. A lock may be held exclusively by a
+// single recipe, or shared by any number of recipes, so recipes with the same
+// exclusive lock never run at the same time. Locks are held in this map as
+// `None` when exclusive, and as the number of holders when shared.
+pub(crate) struct Locks(Condvar, Mutex<BTreeMap<String, Option<u64>>>);
+
+pub(crate) struct LocksGuard<'a> {
+  files: Vec<File>,
+  locks: &'a Locks,
+  names: Vec<String>,
+}
+
+impl Drop for LocksGuard<'_> {
+  fn drop(&mut self) {
+    // Global lock files are unlocked when closed.
+    self.files.clear();
+
+    let mut held = self.locks.mutex().lock().unwrap();
+
+    for name in &self.names {
+      match held.get_mut(name) {
+        Some(Some(count)) if *count > 1 => *count -= 1,
+        _ => {
+          held.remove(name);
+        }
+      }
+    }
+
+    self.locks.condvar().notify_all();
+  }
+}
+
+impl Locks {
+  pub(crate) fn new() -> Self {
+    Self(Condvar::new(), Mutex::new(BTreeMap::new()))
+  }
+
+  fn condvar(&self) -> &Condvar {
+    &self.0
+  }
+
+  fn mutex(&self) -> &Mutex<BTreeMap<String, Option<u64>>> {
+    &self.1
+  }
+
+  // Acquire the locks of `recipe`, waiting until all of them are available.
+  // Locks are acquired together, and global lock files are locked in order of
+  // name, so that recipes with overlapping locks cannot deadlock.
+  pub(crate) fn acquire(
+    &self,
+    cache: &Cache,
+    recipe: &Recipe,
+  ) -> RunResult<'static, Option<LocksGuard>> {
+    // Map each lock name to whether it is shared and whether it is global. A
+    // lock which is given more than once is only shared if every attribute
+    // which names it is shared.
+    let mut requested = BTreeMap::<String, (bool, bool)>::new();
+
+    for attribute in &recipe.attributes {
+      if let Attribute::Lock {
+        global,
+        name,
+        shared,
+      } = attribute
+      {
+        let entry = requested
+          .entry(name.cooked.clone())
+          .or_insert((true, false));
+        entry.0 &= *shared;
+        entry.1 |= *global;
+      }
+    }
+
+    if requested.is_empty() {
+      return Ok(None);
+    }
+
+    let mut held = self
+      .condvar()
+      .wait_while(self.mutex().lock().unwrap(), |held| {
+        requested
+          .iter()
+          .any(|(name, (shared, _global))| match held.get(name) {
+            None => false,
+            Some(Some(_count)) => !shared,
+            Some(None) => true,
+          })
+      })
+      .unwrap();
+
+    for (name, (shared, _global)) in &requested {
+      if *shared {
+        *held
+          .entry(name.clone())
+          .or_insert(Some(0))
+          .as_mut()
+          .unwrap() += 1;
+      } else {
+        held.insert(name.clone(), None);
+      }
+    }
+
+    drop(held);
+
+    let mut guard = LocksGuard {
+      files: Vec::new(),
+      locks: self,
+      names: requested.keys().cloned().collect(),
+    };
+
+    for (name, (shared, global)) in requested {
+      if !global {
+        continue;
+      }
+
+      let path = cache.lock(&name)?;
+
+      let context = |source| Error::FilesystemIo {
+        path: path.clone(),
+        source,
+      };
+
+      let file = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&path)
+        .map_err(context)?;
+
+      if shared {
+        file.lock_shared().map_err(context)?;
+      } else {
+        file.lock().map_err(context)?;
+      }
+
+      guard.files.push(file);
+    }
+
+    Ok(Some(guard))
+  }
+}
@@ -0,0 +1,575 @@
+use {
+  super::*,
+  percent_encoding::{AsciiSet, CONTROLS, percent_decode_str, utf8_percent_encode},
+  serde_json::json,
+};
+
+/// A language server which speaks the Language Server Protocol over standard
+/// input and output
+pub(crate) struct Lsp<'run> {
+  config: &'run Config,
+  documents: HashMap<PathBuf, String>,
+  published: BTreeMap<PathBuf, BTreeSet<PathBuf>>,
+}
+
+impl<'run> Lsp<'run> {
+  const COMPLETION_FUNCTION: u64 = 3;
+  const COMPLETION_KEYWORD: u64 = 14;
+  const COMPLETION_PROPERTY: u64 = 10;
+  const ERROR: u64 = 1;
+  const METHOD_NOT_FOUND: i64 = -32601;
+  const PARSE_ERROR: i64 = -32700;
+  const SYMBOL_FUNCTION: u64 = 12;
+  const SYMBOL_MODULE: u64 = 2;
+  const SYMBOL_VARIABLE: u64 = 13;
+  const URI_ENCODE: AsciiSet = CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'[')
+    .add(b'\\')
+    .add(b']')
+    .add(b'^')
+    .add(b'`')
+    .add(b'{')
+    .add(b'|')
+    .add(b'}');
+  const WARNING: u64 = 2;
+
+  pub(crate) fn run(config: &'run Config) -> RunResult<'static> {
+    let mut lsp = Self {
+      config,
+      documents: HashMap::new(),
+      published: BTreeMap::new(),
+    };
+
+    let mut stdin = io::stdin().lock();
+    let mut stdout = io::stdout().lock();
+
+    while let Some(content) =
+      Self::receive(&mut stdin).map_err(|io_error| Error::LspIo { io_error })?
+    {
+      let messages = match serde_json::from_slice::<serde_json::Value>(&content) {
+        Ok(message) => {
+          if message["method"] == "exit" {
+            break;
+          }
+          lsp.handle(&message)
+        }
+        Err(err) => vec![Self::response(
+          &serde_json::Value::Null,
+          Err((Self::PARSE_ERROR, err.to_string())),
+        )],
+      };
+
+      for message in messages {
+        Self::send(&mut stdout, &message).map_err(|io_error| Error::LspIo { io_error })?;
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Compile the justfile which `path` belongs to, returning the path of the
+  /// root justfile and the result of compiling it. Files which are not part
+  /// of the nearest justfile, for example because they are opened on their
+  /// own, are compiled as the root justfile.
+  fn compile<'src>(
+    &self,
+    loader: &'src Loader,
+    path: &Path,
+  ) -> (PathBuf, RunResult<'src, Compilation<'src>>) {
+    if let Some(directory) = path.parent()
+      && let Ok(root) = Search::justfile(self.config, directory)
+      && root != path
+    {
+      let result = Compiler::compile(self.config, loader, &root);
+
+      let contains = match &result {
+        Ok(compilation) => compilation
+          .asts
+          .keys()
+          .any(|(_modulepath, ast_path)| ast_path == path),
+        Err(error) => error
+          .context()
+          .is_some_and(|token| root.parent().unwrap().join(token.path) == path),
+      };
+
+      if contains {
+        return (root, result);
+      }
+    }
+
+    (path.into(), Compiler::compile(self.config, loader, path))
+  }
+
+  fn completion(&self, params: &serde_json::Value) -> Option<serde_json::Value> {
+    let path = Self::path(params)?;
+    let src = self.src(&path)?;
+    let offset = Self::offset(&src, &params["position"])?;
+    let line = src[src[..offset].rfind('\n').map_or(0, |i| i + 1)..offset].trim_start();
+
+    let (names, kind, detail): (Vec<&str>, u64, &str) =
+      if line.starts_with('[') && !line.contains([']', '(']) {
+        (
+          AttributeKind::iter().map(Into::into).collect(),
+          Self::COMPLETION_KEYWORD,
+          "attribute",
+        )
+      } else if let Some(setting) = line.strip_prefix("set ")
+        && setting
+          .trim_start()
+          .chars()
+          .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+      {
+        (
+          SettingKind::iter().map(Into::into).collect(),
+          Self::COMPLETION_PROPERTY,
+          "setting",
+        )
+      } else {
+        (
+          function::FUNCTIONS.iter().map(|(name, _)| *name).collect(),
+          Self::COMPLETION_FUNCTION,
+          "built-in function",
+        )
+      };
+
+    Some(
+      names
+        .into_iter()
+        .map(|name| json!({"detail": detail, "kind": kind, "label": name}))
+        .collect(),
+    )
+  }
+
+  fn definition(&self, params: &serde_json::Value) -> Option<serde_json::Value> {
+    let path = Self::path(params)?;
+    let src = self.src(&path)?;
+    let offset = Self::offset(&src, &params["position"])?;
+
+    let loader = self.loader();
+    let (root, result) = self.compile(&loader, &path);
+    let compilation = result.ok()?;
+    let index = SymbolIndex::new(&compilation, &path)?;
+    let (_token, symbol) = index.get(offset)?;
+
+    if let Some(token) = symbol.definition() {
+      Some(json!({
+        "range": Self::range(token),
+        "uri": Self::uri(&root.parent().unwrap().join(token.path)),
+      }))
+    } else {
+      let start = json!({"character": 0, "line": 0});
+      Some(json!({
+        "range": {"end": start, "start": start},
+        "uri": Self::uri(symbol.file()?),
+      }))
+    }
+  }
+
+  fn diagnostic(
+    token: Token,
+    severity: u64,
+    message: String,
+    code: Option<Lint>,
+  ) -> serde_json::Value {
+    let mut diagnostic = json!({
+      "message": message,
+      "range": Self::range(token),
+      "severity": severity,
+      "source": "just",
+    });
+
+    if let Some(code) = code {
+      diagnostic["code"] = json!(code);
+    }
+
+    diagnostic
+  }
+
+  /// Compile the justfile which the document in `params` belongs to and
+  /// publish its errors and warnings, clearing diagnostics published for the
+  /// document previously which no longer apply
+  fn diagnostics(&mut self, params: &serde_json::Value) -> Vec<serde_json::Value> {
+    let Some(path) = Self::path(params) else {
+      return Vec::new();
+    };
+
+    let loader = self.loader();
+    let (root, result) = self.compile(&loader, &path);
+    let directory = root.parent().unwrap();
+
+    let mut diagnostics = BTreeMap::<PathBuf, Vec<serde_json::Value>>::new();
+
+    diagnostics.insert(path.clone(), Vec::new());
+
+    match result {
+      Ok(compilation) => {
+        let mut stack = vec![&compilation.justfile];
+
+        while let Some(module) = stack.pop() {
+          for warning in &module.warnings {
+            if let Some(token) = warning.context() {
+              diagnostics
+                .entry(directory.join(token.path))
+                .or_default()
+                .push(Self::diagnostic(
+                  *token,
+                  Self::WARNING,
+                  warning.to_string(),
+                  Some(warning.lint()),
+                ));
+            }
+          }
+
+          stack.extend(module.modules.values());
+        }
+      }
+      Err(error) => {
+        let message = if let Error::Compile { compile_error } = &error {
+          compile_error.to_string()
+        } else {
+          // Strip the prefix and source context from the rendered error.
+          let rendered = error.color_display(Color::never()).to_string();
+          rendered
+            .strip_prefix("error: ")
+            .unwrap_or(&rendered)
+            .lines()
+            .take_while(|line| !line.trim_start().starts_with("——▶"))
+            .collect::<Vec<&str>>()
+            .join("\n")
+        };
+
+        if let Some(token) = error.context() {
+          diagnostics
+            .entry(directory.join(token.path))
+            .or_default()
+            .push(Self::diagnostic(token, Self::ERROR, message, None));
+        } else {
+          let start = json!({"character": 0, "line": 0});
+          diagnostics.entry(path.clone()).or_default().push(json!({
+            "message": message,
+            "range": {"end": start, "start": start},
+            "severity": Self::ERROR,
+            "source": "just",
+          }));
+        }
+      }
+    }
+
+    for stale in self.published.remove(&path).unwrap_or_default() {
+      diagnostics.entry(stale).or_default();
+    }
+
+    self.published.insert(
+      path,
+      diagnostics
+        .iter()
+        .filter(|(_path, diagnostics)| !diagnostics.is_empty())
+        .map(|(path, _diagnostics)| path.clone())
+        .collect(),
+    );
+
+    diagnostics
+      .into_iter()
+      .map(|(path, diagnostics)| {
+        json!({
+          "jsonrpc": "2.0",
+          "method": "textDocument/publishDiagnostics",
+          "params": {
+            "diagnostics": diagnostics,
+            "uri": Self::uri(&path),
+          },
+        })
+      })
+      .collect()
+  }
+
+  fn document_symbols(&self, params: &serde_json::Value) -> Option<serde_json::Value> {
+    let path = Self::path(params)?;
+    let src = self.src(&path)?;
+
+    let ast =
+      Parser::parse_source(&mut Numerator::new(), &path, &Source::root(&path), &src).ok()?;
+
+    Some(
+      ast
+        .items
+        .iter()
+        .filter_map(|item| {
+          let (name, kind) = match item {
+            Item::Alias(Alias { name, .. }) | Item::Recipe(Recipe { name, .. }) => {
+              (name, Self::SYMBOL_FUNCTION)
+            }
+            Item::Assignment(assignment) => (&assignment.name, Self::SYMBOL_VARIABLE),
+            Item::Function(function) => (&function.name, Self::SYMBOL_FUNCTION),
+            Item::Module { name, .. } => (name, Self::SYMBOL_MODULE),
+            _ => return None,
+          };
+
+          Some(json!({
+            "detail": <&str>::from(ItemKind::from(item)),
+            "kind": kind,
+            "name": name.lexeme(),
+            "range": Self::range(name.token),
+            "selectionRange": Self::range(name.token),
+          }))
+        })
+        .collect(),
+    )
+  }
+
+  fn handle(&mut self, message: &serde_json::Value) -> Vec<serde_json::Value> {
+    let params = &message["params"];
+
+    // Messages without a method are responses to requests, which this server
+    // does not send.
+    let Some(method) = message["method"].as_str() else {
+      return Vec::new();
+    };
+
+    match method {
+      "textDocument/didChange" => {
+        if let (Some(path), Some(text)) = (
+          Self::path(params),
+          params["contentChanges"]
+            .as_array()
+            .and_then(|changes| changes.last())
+            .and_then(|change| change["text"].as_str()),
+        ) {
+          self.documents.insert(path, text.into());
+        }
+        Vec::new()
+      }
+      "textDocument/didClose" => {
+        if let Some(path) = Self::path(params) {
+          self.documents.remove(&path);
+        }
+        Vec::new()
+      }
+      "textDocument/didOpen" => {
+        if let (Some(path), Some(text)) =
+          (Self::path(params), params["textDocument"]["text"].as_str())
+        {
+          self.documents.insert(path, text.into());
+        }
+        self.diagnostics(params)
+      }
+      "textDocument/didSave" => {
+        if let (Some(path), Some(text)) = (Self::path(params), params["text"].as_str()) {
+          self.documents.insert(path, text.into());
+        }
+        self.diagnostics(params)
+      }
+      _ => {
+        // Notifications have no ID and are not responded to.
+        let Some(id) = message.get("id") else {
+          return Vec::new();
+        };
+
+        let result = match method {
+          "initialize" => Ok(Self::initialize()),
+          "shutdown" => Ok(serde_json::Value::Null),
+          "textDocument/completion" => Ok(self.completion(params).into()),
+          "textDocument/definition" => Ok(self.definition(params).into()),
+          "textDocument/documentSymbol" => Ok(self.document_symbols(params).into()),
+          "textDocument/hover" => Ok(self.hover(params).into()),
+          _ => Err((Self::METHOD_NOT_FOUND, format!("unknown method `{method}`"))),
+        };
+
+        vec![Self::response(id, result)]
+      }
+    }
+  }
+
+  fn hover(&self, params: &serde_json::Value) -> Option<serde_json::Value> {
+    let path = Self::path(params)?;
+    let src = self.src(&path)?;
+    let offset = Self::offset(&src, &params["position"])?;
+
+    let loader = self.loader();
+    let (_root, result) = self.compile(&loader, &path);
+    let compilation = result.ok()?;
+    let index = SymbolIndex::new(&compilation, &path)?;
+    let (token, symbol) = index.get(offset)?;
+
+    Some(json!({
+      "contents": {
+        "kind": "markdown",
+        "value": symbol.hover(),
+      },
+      "range": Self::range(*token),
+    }))
+  }
+
+  fn initialize() -> serde_json::Value {
+    json!({
+      "capabilities": {
+        "completionProvider": {},
+        "definitionProvider": true,
+        "documentSymbolProvider": true,
+        "hoverProvider": true,
+        "textDocumentSync": {
+          "change": 1,
+          "openClose": true,
+          "save": {
+            "includeText": true,
+          },
+        },
+      },
+      "serverInfo": {
+        "name": "just",
+        "version": VERSION,
+      },
+    })
+  }
+
+  fn loader(&self) -> Loader {
+    Loader::with_documents(self.documents.clone())
+  }
+
+  /// Convert an LSP position, in UTF-16 code units, to a byte offset
+  fn offset(src: &str, position: &serde_json::Value) -> Option<usize> {
+    let line = usize::try_from(position["line"].as_u64()?).ok()?;
+    let character = usize::try_from(position["character"].as_u64()?).ok()?;
+
+    let start = if line == 0 {
+      0
+    } else {
+      src.match_indices('\n').nth(line - 1)?.0 + 1
+    };
+
+    let mut units = 0;
+
+    for (i, c) in src[start..].char_indices() {
+      if units >= character || c == '\n' {
+        return Some(start + i);
+      }
+      units += c.len_utf16();
+    }
+
+    Some(src.len())
+  }
+
+  fn path(params: &serde_json::Value) -> Option<PathBuf> {
+    let uri = params["textDocument"]["uri"]
+      .as_str()?
+      .strip_prefix("file://")?;
+
+    let path = percent_decode_str(uri).decode_utf8().ok()?;
+
+    // Windows paths are written `file:///C:/path`
+    let path = if cfg!(windows) {
+      path.strip_prefix('/').unwrap_or(&path)
+    } else {
+      &path
+    };
+
+    Some(path.into())
+  }
+
+  fn position(src: &str, offset: usize) -> serde_json::Value {
+    let start = src[..offset].rfind('\n').map_or(0, |i| i + 1);
+
+    json!({
+      "character": src[start..offset].encode_utf16().count(),
+      "line": src[..offset].matches('\n').count(),
+    })
+  }
+
+  fn range(token: Token) -> serde_json::Value {
+    json!({
+      "end": Self::position(token.src, token.offset + token.length),
+      "start": Self::position(token.src, token.offset),
+    })
+  }
+
+  fn receive(reader: &mut impl BufRead) -> io::Result<Option<Vec<u8>>> {
+    let mut length = None;
+
+    loop {
+      let mut line = String::new();
+
+      if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+      }
+
+      let line = line.trim_end();
+
+      if line.is_empty() {
+        break;
+      }
+
+      if let Some((name, value)) = line.split_once(':')
+        && name.eq_ignore_ascii_case("content-length")
+      {
+        length = value.trim().parse::<usize>().ok();
+      }
+    }
+
+    let length = length.ok_or_else(|| {
+      io::Error::new(
+        io::ErrorKind::InvalidData,
+        "message missing `Content-Length` header",
+      )
+    })?;
+
+    let mut content = vec![0; length];
+
+    reader.read_exact(&mut content)?;
+
+    Ok(Some(content))
+  }
+
+  fn response(
+    id: &serde_json::Value,
+    result: Result<serde_json::Value, (i64, String)>,
+  ) -> serde_json::Value {
+    match result {
+      Ok(result) => json!({
+        "id": id,
+        "jsonrpc": "2.0",
+        "result": result,
+      }),
+      Err((code, message)) => json!({
+        "error": {
+          "code": code,
+          "message": message,
+        },
+        "id": id,
+        "jsonrpc": "2.0",
+      }),
+    }
+  }
+
+  fn send(writer: &mut impl Write, message: &serde_json::Value) -> io::Result<()> {
+    let content = message.to_string();
+    write!(writer, "Content-Length: {}\r\n\r\n{content}", content.len())?;
+    writer.flush()
+  }
+
+  fn src(&self, path: &Path) -> Option<String> {
+    if let Some(document) = self.documents.get(path) {
+      Some(document.clone())
+    } else {
+      fs::read_to_string(path).ok()
+    }
+  }
+
+  fn uri(path: &Path) -> String {
+    let path = path.to_string_lossy();
+
+    let path = if cfg!(windows) {
+      format!("/{}", path.replace('\\', "/"))
+    } else {
+      path.into_owned()
+    };
+
+    format!("file://{}", utf8_percent_encode(&path, &Self::URI_ENCODE))
+  }
+}
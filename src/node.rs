@@ -170,6 +170,7 @@ impl<'src> Node<'src> for Expression<'src> {
         tree
       }
       Self::Group { contents } => Tree::List(vec![contents.tree()]),
+      Self::Index { index, value, .. } => Tree::atom("index").push(value.tree()).push(index.tree()),
       Self::Join { lhs: None, rhs, .. } => Tree::atom("/").push(rhs.tree()),
       Self::Join {
         lhs: Some(lhs),
@@ -183,6 +184,13 @@ impl<'src> Node<'src> for Expression<'src> {
         }
         tree
       }
+      Self::Map { entries, .. } => {
+        let mut tree = Tree::atom("map");
+        for (key, value) in entries {
+          tree.push_mut(Tree::list([key.tree(), value.tree()]));
+        }
+        tree
+      }
       Self::Not { operand } => Tree::atom("!").push(operand.tree()),
       Self::Or { lhs, rhs } => Tree::atom("||").push(lhs.tree()).push(rhs.tree()),
       Self::StringLiteral {
@@ -225,6 +233,7 @@ impl<'src> Node<'src> for UnresolvedRecipe<'src> {
     if !self.dependencies.is_empty() {
       let mut dependencies = Tree::atom("deps");
       let mut subsequents = Tree::atom("sups");
+      let mut finally = Tree::atom("fins");
 
       for (i, dependency) in self.dependencies.iter().enumerate() {
         let mut d = dependency.recipe.tree();
@@ -239,8 +248,10 @@ impl<'src> Node<'src> for UnresolvedRecipe<'src> {
 
         if i < self.priors {
           dependencies.push_mut(d);
-        } else {
+        } else if i < self.dependencies.len() - self.finally {
           subsequents.push_mut(d);
+        } else {
+          finally.push_mut(d);
         }
       }
 
@@ -251,6 +262,10 @@ impl<'src> Node<'src> for UnresolvedRecipe<'src> {
       if let Tree::List(_) = subsequents {
         t.push_mut(subsequents);
       }
+
+      if let Tree::List(_) = finally {
+        t.push_mut(finally);
+      }
     }
 
     if !self.body.is_empty() {
@@ -315,14 +330,18 @@ impl<'src> Node<'src> for Set<'src> {
       | Setting::WindowsPowerShell(value) => {
         set.push_mut(value.to_string());
       }
-      Setting::DotenvCommand(value)
+      Setting::CacheDirectory(value)
+      | Setting::DotenvCommand(value)
       | Setting::DotenvFilename(value)
       | Setting::DotenvPath(value)
+      | Setting::JobLimits(value)
       | Setting::Tempdir(value)
       | Setting::WorkingDirectory(value) => {
         set.push_mut(value.tree());
       }
-      Setting::Indentation(value, _) | Setting::MinimumVersion(value) => {
+      Setting::Indentation(value, _)
+      | Setting::MinimumVersion(value)
+      | Setting::Output(value, _) => {
         set.push_mut(Tree::string(&value.cooked));
       }
       Setting::ScriptInterpreter(Interpreter { command, arguments })
@@ -339,7 +358,7 @@ impl<'src> Node<'src> for Set<'src> {
   }
 }
 
-impl<'src> Node<'src> for Warning {
+impl<'src> Node<'src> for Warning<'src> {
   fn tree(&self) -> Tree<'src> {
     unreachable!()
   }
@@ -0,0 +1,10 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum OutputMode {
+  Grouped,
+  #[default]
+  Interleaved,
+  Prefixed,
+}
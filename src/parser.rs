@@ -417,6 +417,18 @@ impl<'run, 'src> Parser<'run, 'src> {
     Ok(self.accept(kind)?.is_some())
   }
 
+  /// Accept `&&!`, which precedes finally dependencies
+  fn accepted_finally(&mut self) -> CompileResult<'src, bool> {
+    if !self.next_are(&[AmpersandAmpersand, Bang]) {
+      return Ok(false);
+    }
+
+    self.presume(AmpersandAmpersand)?;
+    self.presume(Bang)?;
+
+    Ok(true)
+  }
+
   fn take_doc_comment(&mut self, attributes: &AttributeSet<'src>) -> Option<String> {
     if attributes.contains(AttributeKind::Doc) {
       return None;
@@ -915,8 +927,34 @@ impl<'run, 'src> Parser<'run, 'src> {
         .is_some_and(|token| matches!(token.kind, StringToken | FormatStringStart))
   }
 
-  /// Parse a value, e.g. `(bar)`
+  /// Parse a value, e.g. `(bar)`, followed by any number of indices, e.g.
+  /// `(bar)[baz]`
   fn parse_value(&mut self) -> CompileResult<'src, Expression<'src>> {
+    let mut value = self.parse_operand()?;
+
+    // Indices must immediately follow the indexed value, so that `foo [bar]`
+    // in a dependency argument list is two arguments and not an index.
+    while self
+      .tokens
+      .get(self.next_token)
+      .is_some_and(|token| token.kind == BracketL)
+    {
+      let open = self.presume(BracketL)?;
+      self.list_feature(ListFeature::IndexOperator, open);
+      let index = self.parse_expression()?;
+      self.expect(BracketR)?;
+      value = Expression::Index {
+        index: index.into(),
+        open,
+        value: value.into(),
+      };
+    }
+
+    Ok(value)
+  }
+
+  /// Parse a value without indices, e.g. `(bar)`
+  fn parse_operand(&mut self) -> CompileResult<'src, Expression<'src>> {
     if let Some(token) = self.accept(Bang)? {
       self.list_feature(ListFeature::NegationOperator, token);
       Ok(Expression::Not {
@@ -971,6 +1009,9 @@ impl<'run, 'src> Parser<'run, 'src> {
             "join_list" => {
               self.list_feature(ListFeature::JoinListFunction, *name);
             }
+            "keys" => {
+              self.list_feature(ListFeature::KeysFunction, *name);
+            }
             "num_jobs" => {
               self.list_feature(ListFeature::NumJobsFunction, *name);
             }
@@ -980,6 +1021,9 @@ impl<'run, 'src> Parser<'run, 'src> {
             "split" => {
               self.list_feature(ListFeature::SplitFunction, *name);
             }
+            "values" => {
+              self.list_feature(ListFeature::ValuesFunction, *name);
+            }
             "which" => {
               self.list_feature(ListFeature::WhichFunction, *name);
             }
@@ -997,6 +1041,8 @@ impl<'run, 'src> Parser<'run, 'src> {
       Ok(Expression::Group { contents })
     } else if self.next_is(BracketL) {
       self.parse_list()
+    } else if self.next_is(BraceL) {
+      self.parse_map()
     } else {
       Err(self.unexpected_token()?)
     }
@@ -1026,6 +1072,34 @@ impl<'run, 'src> Parser<'run, 'src> {
     })
   }
 
+  /// Parse a map literal, e.g. `{a: b, c: d}`
+  fn parse_map(&mut self) -> CompileResult<'src, Expression<'src>> {
+    let brace = self.presume(BraceL)?;
+
+    self.list_feature(ListFeature::MapLiteral, brace);
+
+    let mut entries = Vec::new();
+
+    while !self.next_is(BraceR) {
+      let key = self.parse_expression()?;
+      self.expect(Colon)?;
+      let value = self.parse_expression()?;
+
+      entries.push((key, value));
+
+      if !self.accepted(Comma)? {
+        break;
+      }
+    }
+
+    self.expect(BraceR)?;
+
+    Ok(Expression::Map {
+      entries,
+      open: brace,
+    })
+  }
+
   /// Parse a string literal, e.g. `"FOO"`
   fn parse_string_literal(&mut self) -> CompileResult<'src, StringLiteral<'src>> {
     self.parse_string_literal_in_state(StringState::Normal)
@@ -1384,7 +1458,9 @@ impl<'run, 'src> Parser<'run, 'src> {
 
     let priors = dependencies.len();
 
-    if self.accepted(AmpersandAmpersand)? {
+    let mut finally = self.accepted_finally()?;
+
+    if !finally && self.accepted(AmpersandAmpersand)? {
       let mut subsequents = Vec::new();
 
       while let Some(subsequent) = self.accept_dependency()? {
@@ -1396,8 +1472,30 @@ impl<'run, 'src> Parser<'run, 'src> {
       }
 
       dependencies.append(&mut subsequents);
+
+      finally = self.accepted_finally()?;
     }
 
+    let finally = if finally {
+      let mut cleanups = Vec::new();
+
+      while let Some(cleanup) = self.accept_dependency()? {
+        cleanups.push(cleanup);
+      }
+
+      if cleanups.is_empty() {
+        return Err(self.unexpected_token()?);
+      }
+
+      let count = cleanups.len();
+
+      dependencies.append(&mut cleanups);
+
+      count
+    } else {
+      0
+    };
+
     if self.next_are(&[Comment, Eol, Indent]) || self.next_are(&[Eol, Indent]) {
       self.expect_eol()?;
     }
@@ -1444,6 +1542,7 @@ impl<'run, 'src> Parser<'run, 'src> {
       dependencies,
       doc,
       file_depth: self.file_depth,
+      finally,
       import_offsets: self.import_offsets.clone(),
       module_path: None,
       name,
@@ -1640,6 +1739,7 @@ impl<'run, 'src> Parser<'run, 'src> {
     self.expect(ColonEquals)?;
 
     let set_value = match keyword {
+      Keyword::CacheDirectory => Some(Setting::CacheDirectory(self.parse_expression()?)),
       Keyword::DotenvCommand => Some(Setting::DotenvCommand(self.parse_expression()?)),
       Keyword::DotenvFilename => Some(Setting::DotenvFilename(self.parse_expression()?)),
       Keyword::DotenvPath => Some(Setting::DotenvPath(self.parse_expression()?)),
@@ -1665,6 +1765,19 @@ impl<'run, 'src> Parser<'run, 'src> {
 
         Some(Setting::Indentation(string_literal, indentation))
       }
+      Keyword::JobLimits => {
+        let expression = self.parse_expression()?;
+
+        // A map literal is the natural way to write job limits, so it does not
+        // require `set lists`, although nested list features still do.
+        if let Expression::Map { open, .. } = &expression {
+          self
+            .list_features
+            .retain(|(feature, token)| !(*feature == ListFeature::MapLiteral && token == open));
+        }
+
+        Some(Setting::JobLimits(expression))
+      }
       Keyword::MinimumVersion => {
         let expression = self.parse_expression()?;
 
@@ -1696,6 +1809,27 @@ impl<'run, 'src> Parser<'run, 'src> {
 
         Some(Setting::MinimumVersion(string_literal))
       }
+      Keyword::Output => {
+        let expression = self.parse_expression()?;
+
+        let Expression::StringLiteral { string_literal } = expression else {
+          return Err(name.error(CompileErrorKind::SettingExpression { setting: keyword }));
+        };
+
+        if string_literal.expand || string_literal.kind.indented || string_literal.part.is_some() {
+          return Err(name.error(CompileErrorKind::SettingExpression { setting: keyword }));
+        }
+
+        let mode = OutputMode::from_str(&string_literal.cooked, false).map_err(|_| {
+          string_literal
+            .token
+            .error(CompileErrorKind::InvalidOutputMode {
+              mode: string_literal.cooked.clone(),
+            })
+        })?;
+
+        Some(Setting::Output(string_literal, mode))
+      }
       Keyword::ScriptInterpreter => Some(Setting::ScriptInterpreter(self.parse_interpreter()?)),
       Keyword::Shell => Some(Setting::Shell(self.parse_interpreter()?)),
       Keyword::Tempdir => Some(Setting::Tempdir(self.parse_expression()?)),
@@ -2164,6 +2298,30 @@ mod tests {
     tree: (justfile (assignment x (++ a b))),
   }
 
+  test! {
+    name: map_literal,
+    text: "x := {'a': b, c: 'd',}",
+    tree: (justfile (assignment x (map ("a" b) (c "d")))),
+  }
+
+  test! {
+    name: map_literal_empty,
+    text: "x := {}",
+    tree: (justfile (assignment x map)),
+  }
+
+  test! {
+    name: index,
+    text: "x := a[b]",
+    tree: (justfile (assignment x (index a b))),
+  }
+
+  test! {
+    name: index_chained,
+    text: "x := {'a': b}['a'][c]",
+    tree: (justfile (assignment x (index (index (map ("a" b)) "a") c))),
+  }
+
   test! {
     name: addition_chained,
     text: "x := a + b + c",
@@ -2306,6 +2464,18 @@ mod tests {
     tree: (justfile (recipe foo (sups bar))),
   }
 
+  test! {
+    name: recipe_finally,
+    text: "foo: &&! bar",
+    tree: (justfile (recipe foo (fins bar))),
+  }
+
+  test! {
+    name: recipe_finally_after_subsequent,
+    text: "foo: bar && baz &&! bob",
+    tree: (justfile (recipe foo (deps bar) (sups baz) (fins bob))),
+  }
+
   test! {
     name: recipe_line_single,
     text: "foo:\n bar",
@@ -2982,6 +3152,18 @@ mod tests {
     tree: (justfile (set quiet false)),
   }
 
+  test! {
+    name: set_job_limits,
+    text: "set job-limits := { 'integration': '2', 'unit': '4' }",
+    tree: (justfile (set job_limits (map ("integration" "2") ("unit" "4")))),
+  }
+
+  test! {
+    name: set_job_limits_empty,
+    text: "set job-limits := {}",
+    tree: (justfile (set job_limits map)),
+  }
+
   test! {
     name: set_no_cd,
     text: "set no-cd := true",
@@ -3265,6 +3447,7 @@ mod tests {
       expected: vec![
         Backtick,
         Bang,
+        BraceL,
         BracketL,
         Identifier,
         ParenL,
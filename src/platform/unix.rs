@@ -17,6 +17,41 @@ impl PlatformInterface for Platform {
     Ok(command)
   }
 
+  fn restore_foreground_process_group(pid: i32) {
+    let stdin = io::stdin();
+
+    if nix::unistd::tcgetpgrp(&stdin) == Ok(nix::unistd::Pid::from_raw(pid)) {
+      Self::set_foreground(nix::unistd::getpgrp());
+    }
+  }
+
+  fn set_foreground_process_group(pid: i32) -> bool {
+    use std::io::IsTerminal;
+
+    let stdin = io::stdin();
+
+    if !stdin.is_terminal() || nix::unistd::tcgetpgrp(&stdin) != Ok(nix::unistd::getpgrp()) {
+      return false;
+    }
+
+    let pid = nix::unistd::Pid::from_raw(pid);
+
+    if !Self::set_foreground(pid) {
+      return false;
+    }
+
+    // the child may have been stopped by reading from the terminal before it
+    // was moved to the foreground
+    nix::sys::signal::killpg(pid, nix::sys::signal::Signal::SIGCONT).ok();
+
+    true
+  }
+
+  fn set_process_group(command: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    command.process_group(0);
+  }
+
   fn set_execute_permission(path: &Path) -> io::Result<()> {
     use std::os::unix::fs::PermissionsExt;
 
@@ -36,6 +71,20 @@ impl PlatformInterface for Platform {
     exit_status.signal()
   }
 
+  fn terminate_process_group(pid: i32, kill: bool) {
+    use nix::sys::signal::Signal;
+
+    nix::sys::signal::killpg(
+      nix::unistd::Pid::from_raw(pid),
+      if kill {
+        Signal::SIGKILL
+      } else {
+        Signal::SIGTERM
+      },
+    )
+    .ok();
+  }
+
   fn convert_native_path(_config: &Config, _working_directory: &Path, path: &Path) -> StringResult {
     path
       .to_str()
@@ -61,3 +110,25 @@ impl PlatformInterface for Platform {
     Ok(())
   }
 }
+
+impl Platform {
+  // Make `pgrp` the foreground process group of the terminal on standard
+  // input. `SIGTTOU` is blocked, since it is otherwise sent to `just` when it
+  // is not in the foreground process group, which would stop it.
+  fn set_foreground(pgrp: nix::unistd::Pid) -> bool {
+    use nix::sys::signal::{SigSet, SigmaskHow, Signal};
+
+    let mut ttou = SigSet::empty();
+    ttou.add(Signal::SIGTTOU);
+
+    let Ok(mask) = ttou.thread_swap_mask(SigmaskHow::SIG_BLOCK) else {
+      return false;
+    };
+
+    let result = nix::unistd::tcsetpgrp(io::stdin(), pgrp).is_ok();
+
+    mask.thread_set_mask().ok();
+
+    result
+  }
+}
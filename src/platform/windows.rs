@@ -51,12 +51,40 @@ impl PlatformInterface for Platform {
     Ok(())
   }
 
+  fn restore_foreground_process_group(_pid: i32) {}
+
+  fn set_foreground_process_group(_pid: i32) -> bool {
+    // windows has no terminal process groups
+    false
+  }
+
+  fn set_process_group(_command: &mut Command) {
+    // `taskkill /T` terminates the process tree, so no process group is needed
+  }
+
   fn signal_from_exit_status(_exit_status: process::ExitStatus) -> Option<i32> {
     // The rust standard library does not expose a way to extract a signal from a
     // windows process exit status, so just return None
     None
   }
 
+  fn terminate_process_group(pid: i32, kill: bool) {
+    let mut command = Command::new("taskkill");
+
+    command
+      .arg("/T")
+      .arg("/PID")
+      .arg(pid.to_string())
+      .stdout(Stdio::null())
+      .stderr(Stdio::null());
+
+    if kill {
+      command.arg("/F");
+    }
+
+    command.status().ok();
+  }
+
   fn convert_native_path(config: &Config, working_directory: &Path, path: &Path) -> StringResult {
     // Translate path from windows style to unix style
     let mut cygpath = Command::resolve(&config.cygpath);
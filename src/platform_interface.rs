@@ -16,9 +16,25 @@ pub(crate) trait PlatformInterface {
     working_directory: Option<&Path>,
   ) -> Result<Command, OutputError>;
 
+  /// return the terminal on standard input to the process group of `just`, if
+  /// the process group led by `pid` is its foreground process group
+  fn restore_foreground_process_group(pid: i32);
+
   /// set the execute permission on file pointed to by `path`
   fn set_execute_permission(path: &Path) -> io::Result<()>;
 
+  /// make the process group led by `pid` the foreground process group of the
+  /// terminal on standard input, if `just` is in the foreground, returning
+  /// whether it was
+  fn set_foreground_process_group(pid: i32) -> bool;
+
+  /// run `command` in a new process group, so that it and its descendants may
+  /// be signaled together
+  fn set_process_group(command: &mut Command);
+
   /// extract signal from process exit status
   fn signal_from_exit_status(exit_status: ExitStatus) -> Option<i32>;
+
+  /// terminate process group led by `pid`, forcefully if `kill` is true
+  fn terminate_process_group(pid: i32, kill: bool);
 }
@@ -1,10 +1,16 @@
 use super::*;
 
 #[derive(Default)]
-pub(crate) struct Ran(Mutex<BTreeMap<Modulepath, BTreeMap<Vec<Value>, Arc<Mutex<bool>>>>>);
+pub(crate) struct Ran(
+  Mutex<BTreeMap<Modulepath, BTreeMap<Vec<Value>, Arc<Mutex<Option<Result<Fingerprint, ()>>>>>>>,
+);
 
 impl Ran {
-  pub(crate) fn mutex(&self, recipe: &Recipe, arguments: &[Value]) -> Arc<Mutex<bool>> {
+  pub(crate) fn mutex(
+    &self,
+    recipe: &Recipe,
+    arguments: &[Value],
+  ) -> Arc<Mutex<Option<Result<Fingerprint, ()>>>> {
     self
       .0
       .lock()
@@ -9,6 +9,7 @@ pub(crate) struct Recipe<'src, D = Dependency<'src>> {
   pub(crate) doc: Option<String>,
   #[serde(skip)]
   pub(crate) file_depth: u32,
+  pub(crate) finally: usize,
   #[serde(skip)]
   pub(crate) import_offsets: Vec<usize>,
   #[serde(skip)]
@@ -201,6 +202,14 @@ impl<'src> Recipe<'src> {
     self.attributes.contains(AttributeKind::NoQuiet)
   }
 
+  fn timeout(&self, config: &Config) -> Option<Duration> {
+    if let Some(Attribute::Timeout(timeout)) = self.attributes.get(AttributeKind::Timeout) {
+      Some(timeout.cooked.parse::<Timespan>().unwrap().0)
+    } else {
+      config.timeout
+    }
+  }
+
   fn timestamp_format(
     &self,
     config: &Config,
@@ -232,19 +241,230 @@ impl<'src> Recipe<'src> {
   pub(crate) fn run<'run>(
     &self,
     context: &ExecutionContext<'src, 'run>,
+    arguments: &[Value],
+    dependencies: &[Fingerprint],
     env: &BTreeMap<String, String>,
     is_dependency: bool,
     positional: &[String],
     scope: &Scope<'src, 'run>,
     cache: &Cache,
-    jobs: &Semaphore,
-  ) -> RunResult<'src> {
-    let _guard = jobs.acquire();
+    jobs: &Jobs,
+    locks: &Locks,
+  ) -> RunResult<'src, Option<CacheRecord>> {
+    // The semaphore is closed when another `--parallel` invocation fails, in
+    // which case the recipe is skipped.
+    let Some(_guards) = jobs.acquire(self) else {
+      return Err(Error::Skipped {
+        recipe: self.name(),
+      });
+    };
+
+    // Locks are acquired after a job slot, since a recipe waiting for a job
+    // slot while holding a lock could prevent the recipes holding every slot
+    // from acquiring it.
+    let _locks = if context.config.dry_run {
+      None
+    } else {
+      locks.acquire(cache, self)?
+    };
+
+    if jobs.is_closed() {
+      return Err(Error::Skipped {
+        recipe: self.name(),
+      });
+    }
+
+    Events::emit(Event::RecipeStart {
+      arguments: positional,
+      dependency: is_dependency,
+      recipe: self.recipe_path(),
+    });
+
+    let name = iter::once(self.recipe_path().to_string())
+      .chain(positional.iter().cloned())
+      .collect::<Vec<String>>()
+      .join(" ");
+
+    // Section markers and annotations are suppressed along with other output.
+    let ci = if context.config.verbosity.quiet() {
+      CiFormat::None
+    } else {
+      context.config.ci_format.resolve()
+    };
+
+    let start = Instant::now();
+
+    let mut capture = Capture::new(
+      context.config,
+      context
+        .config
+        .output
+        .or(context.module.settings.output)
+        .unwrap_or_default(),
+      self.recipe_path(),
+    );
+
+    let section = ci.start(&name);
+
+    if let Some((_section, marker)) = &section {
+      match capture.as_mut() {
+        Some(capture) => capture.mark(marker),
+        None => eprintln!("{marker}"),
+      }
+    }
+
+    let mut up_to_date = false;
+
+    let result = self.execute(
+      context,
+      dependencies,
+      env,
+      is_dependency,
+      positional,
+      scope,
+      cache,
+      jobs,
+      capture.as_mut(),
+      &mut up_to_date,
+    );
+
+    if let Some((section, _marker)) = &section {
+      let marker = ci.finish(section);
+
+      match capture.as_mut() {
+        Some(capture) => capture.mark(&marker),
+        None => eprintln!("{marker}"),
+      }
+    }
+
+    let tail = capture.and_then(Capture::finish);
+
+    let duration = start.elapsed();
+
+    // Recipes skipped because other recipes failed are not annotated, since
+    // they did not fail themselves.
+    if let Err(error) = &result
+      && !matches!(error, Error::Skipped { .. })
+    {
+      let (path, line) = match error {
+        Error::Code {
+          line_number: Some(line),
+          ..
+        }
+        | Error::GuardCode {
+          line_number: line, ..
+        }
+        | Error::Signal {
+          line_number: Some(line),
+          ..
+        }
+        | Error::Unknown {
+          line_number: Some(line),
+          ..
+        } => (self.name.path, *line),
+        _ => error
+          .context()
+          .map_or((self.name.path, self.name.line + 1), |token| {
+            (token.path, token.line + 1)
+          }),
+      };
+
+      ci.error(
+        &context.search.justfile.parent().unwrap().join(path),
+        line,
+        &format!("just {name}"),
+        error
+          .color_display(Color::never())
+          .to_string()
+          .trim_start_matches("error: "),
+      );
+    }
+
+    // Cached recipes which fail did not hit the cache, since hits are not run.
+    let cached = !context.config.dry_run
+      && !context.config.no_cache
+      && self.attributes.contains(AttributeKind::Cache);
+
+    let cache = match &result {
+      Ok(Some(record)) => Some(if record.hit { "hit" } else { "miss" }),
+      Ok(None) => None,
+      Err(_) => cached.then_some("miss"),
+    };
+
+    let code = match &result {
+      Ok(_) => 0,
+      Err(error) => error.code().unwrap_or(EXIT_FAILURE),
+    };
+
+    Events::emit(Event::RecipeFinish {
+      arguments: positional,
+      cache,
+      code,
+      dependency: is_dependency,
+      duration: duration.as_secs_f64(),
+      recipe: self.recipe_path(),
+      success: result.is_ok(),
+    });
 
+    Junit::testcase(Testcase {
+      duration,
+      failure: result
+        .as_ref()
+        .err()
+        .map(|error| error.color_display(Color::never()).to_string()),
+      name,
+      skipped: if result.is_err() {
+        None
+      } else if cache == Some("hit") {
+        Some("cache hit")
+      } else if up_to_date {
+        Some("up to date")
+      } else if context.config.dry_run {
+        Some("dry run")
+      } else {
+        None
+      },
+      stderr: tail.map(|tail| tail.text()).unwrap_or_default(),
+    });
+
+    Trace::span(Span {
+      arguments: arguments.into(),
+      cache,
+      code,
+      dependency: is_dependency,
+      duration,
+      positional: positional.into(),
+      recipe: self.recipe_path().clone(),
+      start,
+    });
+
+    result
+  }
+
+  fn execute<'run>(
+    &self,
+    context: &ExecutionContext<'src, 'run>,
+    dependencies: &[Fingerprint],
+    env: &BTreeMap<String, String>,
+    is_dependency: bool,
+    positional: &[String],
+    scope: &Scope<'src, 'run>,
+    cache: &Cache,
+    jobs: &Jobs,
+    mut capture: Option<&mut Capture>,
+    up_to_date: &mut bool,
+  ) -> RunResult<'src, Option<CacheRecord>> {
     let color = context.config.color.stderr().banner();
     let prefix = color.prefix();
     let suffix = color.suffix();
 
+    let mut evaluator = Evaluator::new(context, env.clone(), is_dependency, Some(self.name), scope);
+
+    if self.up_to_date(context, &mut evaluator, cache)? {
+      *up_to_date = true;
+      return Ok(None);
+    }
+
     if context.config.verbosity.loquacious() {
       eprintln!(
         "{prefix}===> running recipe `{}`...{suffix}",
@@ -258,14 +478,121 @@ impl<'src> Recipe<'src> {
       eprintln!("{prefix}#### {doc}{suffix}");
     }
 
-    let evaluator = Evaluator::new(context, env.clone(), is_dependency, Some(self.name), scope);
-
     let start = Instant::now();
-    let result = if self.is_script(&context.module.settings) {
-      self.run_script(context, env, evaluator, positional, scope, cache)
-    } else {
-      self.run_shell(context, env, evaluator, positional, scope)
+
+    let mut attempt = 1;
+    let mut evaluated = Vec::new();
+    let mut resume = 0;
+
+    let result = loop {
+      let result = if self.is_script(&context.module.settings) {
+        self.run_script(
+          context,
+          dependencies,
+          env,
+          evaluator,
+          positional,
+          scope,
+          cache,
+          capture.as_deref_mut(),
+        )
+      } else {
+        self.run_shell(
+          context,
+          dependencies,
+          env,
+          &mut evaluated,
+          evaluator,
+          positional,
+          resume,
+          scope,
+          cache,
+          capture.as_deref_mut(),
+        )
+      };
+
+      let Err(error) = &result else {
+        break result;
+      };
+
+      let Some(Attribute::Retry {
+        attempts,
+        backoff,
+        delay,
+        on,
+        resume: resume_from_failure,
+      }) = self.attributes.get(AttributeKind::Retry)
+      else {
+        break result;
+      };
+
+      // Only failed commands are retried, not evaluation errors or
+      // interruptions. Commands killed by SIGHUP, SIGINT, or SIGQUIT were
+      // interrupted by the user, even if `just` did not catch the signal.
+      let retryable = match error {
+        Error::Signal { signal, .. } => !matches!(
+          u8::try_from(*signal)
+            .ok()
+            .and_then(|signal| Signal::try_from(signal).ok()),
+          Some(Signal::Hangup | Signal::Interrupt | Signal::Quit),
+        ),
+        Error::Code { .. } | Error::Timeout { .. } | Error::Unknown { .. } => true,
+        _ => false,
+      } && (on.is_empty() || error.code().is_some_and(|code| on.contains(&code)));
+
+      if !retryable || attempt >= *attempts || SignalHandler::caught().is_some() {
+        break result;
+      }
+
+      let delay = delay
+        .as_ref()
+        .map(|delay| delay.cooked.parse::<Timespan>().unwrap().0)
+        .unwrap_or_default();
+
+      let backoff = backoff
+        .as_ref()
+        .map_or(1.0, |backoff| backoff.cooked.parse::<f64>().unwrap());
+
+      let delay = Duration::try_from_secs_f64(
+        delay.as_secs_f64() * backoff.powi(i32::try_from(attempt - 1).unwrap_or(i32::MAX)),
+      )
+      .unwrap_or(Duration::MAX);
+
+      if *resume_from_failure
+        && let Error::Code { line_number, .. }
+        | Error::Signal { line_number, .. }
+        | Error::Unknown { line_number, .. } = error
+      {
+        resume = line_number.unwrap_or_default();
+      }
+
+      attempt += 1;
+
+      if !context.config.verbosity.quiet() {
+        if error.print_message() {
+          eprintln!("{}", error.color_display(context.config.color.stderr()));
+        }
+
+        let mut message = format!("===> retrying recipe `{}`", self.recipe_path());
+
+        if resume > 0 {
+          message.push_str(&format!(" from line {resume}"));
+        }
+
+        if !delay.is_zero() {
+          message.push_str(&format!(" in {}", Timespan(delay)));
+        }
+
+        eprintln!("{prefix}{message} (attempt {attempt} of {attempts}){suffix}");
+      }
+
+      if let Err(error) = self.retry_delay(delay, jobs) {
+        break Err(error);
+      }
+
+      evaluator = Evaluator::new(context, env.clone(), is_dependency, Some(self.name), scope);
     };
+
     let elapsed = start.elapsed();
 
     if context.config.time {
@@ -289,61 +616,140 @@ impl<'src> Recipe<'src> {
     result
   }
 
+  // Wait for `delay` before retrying, returning early if a signal is caught or
+  // `--parallel` invocations have stopped.
+  fn retry_delay(&self, delay: Duration, jobs: &Jobs) -> RunResult<'src> {
+    const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+    let deadline = Instant::now().checked_add(delay);
+
+    loop {
+      if let Some(signal) = SignalHandler::caught() {
+        return Err(Error::Interrupted { signal });
+      }
+
+      if jobs.is_closed() {
+        return Err(Error::Skipped {
+          recipe: self.name(),
+        });
+      }
+
+      let remaining = deadline.map_or(POLL_INTERVAL, |deadline| {
+        deadline.saturating_duration_since(Instant::now())
+      });
+
+      if remaining.is_zero() {
+        return Ok(());
+      }
+
+      thread::sleep(remaining.min(POLL_INTERVAL));
+    }
+  }
+
   fn run_shell<'run>(
     &self,
     context: &ExecutionContext<'src, 'run>,
+    dependencies: &[Fingerprint],
     env: &BTreeMap<String, String>,
+    previous: &mut Vec<(usize, String, BTreeSet<Sigil>)>,
     mut evaluator: Evaluator<'src, 'run>,
     positional: &[String],
+    resume: usize,
     scope: &Scope<'src, 'run>,
-  ) -> RunResult<'src> {
+    cache: &Cache,
+    mut capture: Option<&mut Capture>,
+  ) -> RunResult<'src, Option<CacheRecord>> {
     let config = &context.config;
     let settings = &context.module.settings;
 
     let mut lines = self.body.iter().peekable();
-    let mut line_number = self.line_number() + 1;
 
     let working_directory = self.working_directory(context, &mut evaluator)?;
 
     let timestamp_format = self.timestamp_format(config, &mut evaluator)?;
 
-    loop {
-      let Some(line) = lines.peek() else {
-        return Ok(());
-      };
+    let environment = Environment::new(context.dotenv, scope, settings, &context.module.unexports);
 
-      let mut evaluated = String::new();
-      let mut continued = false;
+    // Lines before the line which failed on the previous attempt are not
+    // evaluated again, so that their backticks are not run twice.
+    Self::skip_lines(&mut lines, resume);
 
-      let comment_line = settings.ignore_comments && line.is_comment();
-      let sigils = line.sigils(settings);
+    // Cached recipes must evaluate every line up front to build the cache key,
+    // other recipes evaluate each line immediately before running it. Skipped
+    // lines of cached recipes keep their values from the previous attempt.
+    let (mut evaluated, cache_lock) =
+      if !config.dry_run && !config.no_cache && self.attributes.contains(AttributeKind::Cache) {
+        let mut evaluated = mem::take(previous);
 
-      loop {
-        if lines.peek().is_none() {
-          break;
-        }
-        let line = lines.next().unwrap();
-        line_number = line.number + 1;
-        if !comment_line {
-          evaluated += &evaluator.evaluate_line(line, continued)?;
+        evaluated.retain(|(line_number, _command, _sigils)| *line_number < resume);
+
+        while let Some(line) = Self::evaluate_shell_line(&mut lines, &mut evaluator, settings)? {
+          evaluated.push(line);
         }
-        if line.is_continuation() && !comment_line {
-          continued = true;
-          evaluated.pop();
-        } else {
-          break;
+
+        previous.clone_from(&evaluated);
+
+        let (commands, sigils) = evaluated
+          .iter()
+          .map(|(_line_number, command, sigils)| (command.clone(), sigils.clone()))
+          .unzip::<_, _, Vec<String>, Vec<BTreeSet<Sigil>>>();
+
+        let (command, arguments) = settings.shell(config);
+
+        let executor = Executor::Command(Interpreter {
+          command: command.into(),
+          arguments: arguments.into_iter().map(Into::into).collect(),
+        });
+
+        let mut environment = Environment {
+          variables: environment.variables.clone(),
+        };
+
+        for (name, value) in env {
+          environment
+            .variables
+            .insert(name.clone(), Some(value.clone()));
         }
-      }
 
-      if comment_line {
-        continue;
-      }
+        let cache_lock = match self.cache_status(
+          context,
+          &mut evaluator,
+          cache,
+          &commands,
+          dependencies,
+          &environment,
+          &executor,
+          None,
+          positional,
+          Some(&sigils),
+          working_directory.as_deref(),
+        )? {
+          Some(CacheStatus::Hit(record)) => return Ok(Some(record)),
+          Some(CacheStatus::Miss(lock)) => Some(lock),
+          None => None,
+        };
+
+        (Some(evaluated.into_iter()), cache_lock)
+      } else {
+        (None, None)
+      };
+
+    let timeout = self.timeout(config);
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
 
-      let mut command = evaluated.as_str();
+    loop {
+      let next = match &mut evaluated {
+        Some(evaluated) => evaluated.next(),
+        None => Self::evaluate_shell_line(&mut lines, &mut evaluator, settings)?,
+      };
 
-      command = &command[sigils.len()..];
+      let Some((line_number, command, sigils)) = next else {
+        break;
+      };
 
-      if command.is_empty() {
+      // Lines before the line which failed on the previous attempt are not
+      // run again.
+      if line_number < resume {
         continue;
       }
 
@@ -370,24 +776,35 @@ impl<'src> Recipe<'src> {
         }
         .stderr();
 
+        let mut echo = color.paint(&command).to_string();
+
         if let Some(timestamp) = timestamp {
-          eprint!("[{}] ", color.paint(&timestamp));
+          echo.insert_str(0, &format!("[{}] ", color.paint(&timestamp)));
         }
 
-        eprintln!("{}", color.paint(command));
+        match capture.as_deref_mut() {
+          Some(capture) => capture.echo(&echo),
+          None => eprintln!("{echo}"),
+        }
       }
 
       if config.dry_run {
         continue;
       }
 
+      Events::emit(Event::CommandStart {
+        command: &command,
+        line: line_number,
+        recipe: self.recipe_path(),
+      });
+
       let mut cmd = settings.shell_command(config);
 
       if let Some(working_directory) = &working_directory {
         cmd.current_dir(working_directory);
       }
 
-      cmd.shell_arg(command);
+      cmd.shell_arg(&command);
 
       if self.takes_positional_arguments(settings) {
         cmd.arg(self.name.lexeme());
@@ -399,16 +816,23 @@ impl<'src> Recipe<'src> {
         cmd.stdout(Stdio::null());
       }
 
-      let environment =
-        Environment::new(context.dotenv, scope, settings, &context.module.unexports);
-
       environment.export(&mut cmd);
 
       for (key, value) in env {
         cmd.env(key, value);
       }
 
-      let (result, caught) = cmd.status_guard();
+      let (result, caught, timed_out) = match capture.as_deref_mut() {
+        Some(capture) => capture.run(cmd, deadline),
+        None => cmd.status_guard_deadline(deadline),
+      };
+
+      if let Some(timeout) = timeout.filter(|_| timed_out) {
+        return Err(Error::Timeout {
+          recipe: self.name(),
+          timeout,
+        });
+      }
 
       match result {
         Ok(exit_status) => {
@@ -416,7 +840,7 @@ impl<'src> Recipe<'src> {
             if code != 0 {
               if guard {
                 if code == 1 {
-                  return Ok(());
+                  break;
                 }
 
                 return Err(Error::GuardCode {
@@ -459,17 +883,94 @@ impl<'src> Recipe<'src> {
         }
       }
     }
+
+    cache_lock
+      .map(|lock| self.save_cache(cache, lock))
+      .transpose()
+  }
+
+  // Skip the lines which end before line `resume`, without evaluating them.
+  fn skip_lines(lines: &mut iter::Peekable<slice::Iter<Line<'src>>>, resume: usize) {
+    loop {
+      let mut next = lines.clone();
+      let mut end = None;
+
+      for line in next.by_ref() {
+        end = Some(line.number + 1);
+        if !line.is_continuation() {
+          break;
+        }
+      }
+
+      match end {
+        Some(end) if end < resume => *lines = next,
+        _ => break,
+      }
+    }
+  }
+
+  // Evaluate the next non-empty, non-comment command in `lines`, joining
+  // continuation lines, and return its line number, its text with sigils
+  // stripped, and its sigils.
+  fn evaluate_shell_line<'run>(
+    lines: &mut iter::Peekable<slice::Iter<Line<'src>>>,
+    evaluator: &mut Evaluator<'src, 'run>,
+    settings: &Settings,
+  ) -> RunResult<'src, Option<(usize, String, BTreeSet<Sigil>)>> {
+    loop {
+      let Some(line) = lines.peek() else {
+        return Ok(None);
+      };
+
+      let mut line_number = line.number + 1;
+      let mut evaluated = String::new();
+      let mut continued = false;
+
+      let comment_line = settings.ignore_comments && line.is_comment();
+      let sigils = line.sigils(settings);
+
+      loop {
+        if lines.peek().is_none() {
+          break;
+        }
+        let line = lines.next().unwrap();
+        line_number = line.number + 1;
+        if !comment_line {
+          evaluated += &evaluator.evaluate_line(line, continued)?;
+        }
+        if line.is_continuation() && !comment_line {
+          continued = true;
+          evaluated.pop();
+        } else {
+          break;
+        }
+      }
+
+      if comment_line {
+        continue;
+      }
+
+      let command = &evaluated[sigils.len()..];
+
+      if command.is_empty() {
+        continue;
+      }
+
+      return Ok(Some((line_number, command.into(), sigils)));
+    }
   }
 
   pub(crate) fn run_script<'run>(
     &self,
     context: &ExecutionContext<'src, 'run>,
+    dependencies: &[Fingerprint],
     env: &BTreeMap<String, String>,
     mut evaluator: Evaluator<'src, 'run>,
     positional: &[String],
     scope: &Scope<'src, 'run>,
     cache: &Cache,
-  ) -> RunResult<'src> {
+    mut capture: Option<&mut Capture>,
+  ) -> RunResult<'src, Option<CacheRecord>> {
     let config = &context.config;
 
     if let Some(format) = self.timestamp_format(config, &mut evaluator)? {
@@ -483,7 +984,12 @@ impl<'src> Recipe<'src> {
       }
       .stderr();
 
-      eprintln!("[{}] {}", color.paint(&timestamp), self.name);
+      let echo = format!("[{}] {}", color.paint(&timestamp), self.name);
+
+      match capture.as_deref_mut() {
+        Some(capture) => capture.echo(&echo),
+        None => eprintln!("{echo}"),
+      }
     }
 
     let mut evaluated_lines = Vec::new();
@@ -500,12 +1006,17 @@ impl<'src> Recipe<'src> {
       .stderr();
 
       for line in &evaluated_lines {
-        eprintln!("{}", color.paint(line));
+        let echo = color.paint(line).to_string();
+
+        match capture.as_deref_mut() {
+          Some(capture) => capture.echo(&echo),
+          None => eprintln!("{echo}"),
+        }
       }
     }
 
     if config.dry_run {
-      return Ok(());
+      return Ok(None);
     }
 
     let executor = if self.attributes.contains(AttributeKind::Script) {
@@ -566,81 +1077,22 @@ impl<'src> Recipe<'src> {
       }
     });
 
-    let (cache_lock, outputs) = if !config.no_cache
-      && let Some(Attribute::Cache {
-        extra,
-        inputs,
-        outputs,
-      }) = self.attributes.get(AttributeKind::Cache)
-    {
-      let working_directory = match &working_directory {
-        Some(working_directory) => working_directory.to_owned(),
-        None => env::current_dir().map_err(|source| Error::CurrentDirectory { source })?,
-      };
-
-      let extra = extra
-        .as_ref()
-        .map(|extra| evaluator.evaluate_value(extra))
-        .transpose()?;
-
-      let inputs = inputs
-        .as_ref()
-        .map(|inputs| {
-          let inputs = evaluator.evaluate_value(inputs)?;
-          Cache::inputs(inputs, &working_directory)
-        })
-        .transpose()?;
-
-      let outputs = outputs
-        .as_ref()
-        .map(|outputs| -> RunResult<BTreeMap<String, PathBuf>> {
-          let outputs = evaluator.evaluate_value(outputs)?;
-          Ok(
-            outputs
-              .into_elements()
-              .into_iter()
-              .map(|output| (output.clone(), working_directory.join(output)))
-              .collect(),
-          )
-        })
-        .transpose()?
-        .unwrap_or_default();
-
-      let key = CacheKey {
-        body: &evaluated_lines,
-        environment: &environment,
-        executor: &executor,
-        extension,
-        extra,
-        inputs,
-        positional: self
-          .takes_positional_arguments(&context.module.settings)
-          .then_some(positional),
-        recipe: self.recipe_path(),
-        working_directory: Some(&working_directory),
-      };
-
-      let lock = match cache.status(config, key, &outputs)? {
-        CacheStatus::Hit => {
-          if config.verbosity.loquacious() {
-            eprintln!(
-              "{}",
-              context
-                .config
-                .color
-                .stderr()
-                .banner()
-                .paint("===> cache hit, skipping invocation"),
-            );
-          }
-          return Ok(());
-        }
-        CacheStatus::Miss(lock) => lock,
-      };
-
-      (Some(lock), outputs)
-    } else {
-      (None, BTreeMap::new())
+    let cache_lock = match self.cache_status(
+      context,
+      &mut evaluator,
+      cache,
+      &evaluated_lines,
+      dependencies,
+      &environment,
+      &executor,
+      extension,
+      positional,
+      None,
+      working_directory.as_deref(),
+    )? {
+      Some(CacheStatus::Hit(record)) => return Ok(Some(record)),
+      Some(CacheStatus::Miss(lock)) => Some(lock),
+      None => None,
     };
 
     let tempdir = context.tempdir(self)?;
@@ -672,8 +1124,22 @@ impl<'src> Recipe<'src> {
 
     environment.export(&mut command);
 
+    let timeout = self.timeout(config);
+
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
     // run it!
-    let (result, caught) = command.status_guard();
+    let (result, caught, timed_out) = match capture {
+      Some(capture) => capture.run(command, deadline),
+      None => command.status_guard_deadline(deadline),
+    };
+
+    if let Some(timeout) = timeout.filter(|_| timed_out) {
+      return Err(Error::Timeout {
+        recipe: self.name(),
+        timeout,
+      });
+    }
 
     match result {
       Ok(exit_status) => exit_status.code().map_or_else(
@@ -709,18 +1175,224 @@ impl<'src> Recipe<'src> {
       }
     }
 
-    for (output, path) in outputs {
-      if !filesystem::exists(&path)? {
+    cache_lock
+      .map(|lock| self.save_cache(cache, lock))
+      .transpose()
+  }
+
+  // Check whether this invocation's `[stale]` targets are newer than its
+  // sources, in which case it is skipped.
+  fn up_to_date<'run>(
+    &self,
+    context: &ExecutionContext<'src, 'run>,
+    evaluator: &mut Evaluator<'src, 'run>,
+    cache: &Cache,
+  ) -> RunResult<'src, bool> {
+    let config = &context.config;
+
+    let Some(Attribute::Stale { sources, targets }) = self.attributes.get(AttributeKind::Stale)
+    else {
+      return Ok(false);
+    };
+
+    let working_directory = match self.working_directory(context, evaluator)? {
+      Some(working_directory) => working_directory,
+      None => env::current_dir().map_err(|source| Error::CurrentDirectory { source })?,
+    };
+
+    let sources = sources
+      .as_ref()
+      .map(|sources| evaluator.evaluate_value(sources))
+      .transpose()?
+      .unwrap_or_default();
+
+    let targets = evaluator.evaluate_value(targets)?;
+
+    let stale = Stale::check(cache, sources, targets, &working_directory)?;
+
+    let banner = config.color.stderr().banner();
+
+    match &stale {
+      None if config.dry_run || config.explain || config.verbosity.loquacious() => {
+        eprintln!(
+          "{}",
+          banner.paint(&format!(
+            "===> recipe `{}` is up to date",
+            self.recipe_path()
+          )),
+        );
+      }
+      Some(stale) if config.explain || config.verbosity.loquacious() => {
+        eprintln!(
+          "{}",
+          banner.paint(&format!(
+            "===> recipe `{}` is stale, {stale}",
+            self.recipe_path()
+          )),
+        );
+      }
+      _ => {}
+    }
+
+    Ok(stale.is_none())
+  }
+
+  // Look up this invocation in the cache, returning `None` if the recipe is
+  // not cached or caching is disabled.
+  fn cache_status<'run>(
+    &self,
+    context: &ExecutionContext<'src, 'run>,
+    evaluator: &mut Evaluator<'src, 'run>,
+    cache: &Cache,
+    body: &[String],
+    dependencies: &[Fingerprint],
+    environment: &Environment,
+    executor: &Executor,
+    extension: Option<&str>,
+    positional: &[String],
+    sigils: Option<&[BTreeSet<Sigil>]>,
+    working_directory: Option<&Path>,
+  ) -> RunResult<'src, Option<CacheStatus>> {
+    let config = &context.config;
+
+    let Some(Attribute::Cache {
+      env: variables,
+      exclude,
+      extra,
+      gitignore,
+      inputs,
+      no_exports,
+      outputs,
+    }) = self.attributes.get(AttributeKind::Cache)
+    else {
+      return Ok(None);
+    };
+
+    if config.no_cache {
+      return Ok(None);
+    }
+
+    let working_directory = match working_directory {
+      Some(working_directory) => working_directory.to_owned(),
+      None => env::current_dir().map_err(|source| Error::CurrentDirectory { source })?,
+    };
+
+    // When variables are selected, only they are included in the key, taking
+    // their values from justfile exports, unless excluded, and then from the
+    // process environment.
+    let selected;
+    let environment = if variables.is_some() || *no_exports {
+      let variables = variables
+        .as_ref()
+        .map(|variables| evaluator.evaluate_value(variables))
+        .transpose()?
+        .unwrap_or_default();
+
+      selected = Environment {
+        variables: variables
+          .into_elements()
+          .into_iter()
+          .map(|name| {
+            let value = match environment.variables.get(&name) {
+              Some(value) if !*no_exports => value.clone(),
+              _ => env::var(&name).ok(),
+            };
+            (name, value)
+          })
+          .collect(),
+      };
+
+      &selected
+    } else {
+      environment
+    };
+
+    let extra = extra
+      .as_ref()
+      .map(|extra| evaluator.evaluate_value(extra))
+      .transpose()?;
+
+    let exclude = exclude
+      .as_ref()
+      .map(|exclude| evaluator.evaluate_value(exclude))
+      .transpose()?
+      .unwrap_or_default();
+
+    let inputs = inputs
+      .as_ref()
+      .map(|inputs| {
+        let inputs = evaluator.evaluate_value(inputs)?;
+        cache.inputs(inputs, exclude, *gitignore, &working_directory)
+      })
+      .transpose()?;
+
+    let outputs = outputs
+      .as_ref()
+      .map(|outputs| -> RunResult<BTreeMap<String, PathBuf>> {
+        let outputs = evaluator.evaluate_value(outputs)?;
+        Ok(
+          outputs
+            .into_elements()
+            .into_iter()
+            .map(|output| (output.clone(), working_directory.join(output)))
+            .collect(),
+        )
+      })
+      .transpose()?
+      .unwrap_or_default();
+
+    // Record the working directory relative to the justfile, so that checkouts
+    // in different locations can share a cache directory.
+    let relative_working_directory =
+      match working_directory.strip_prefix(context.search.justfile_parent()) {
+        Ok(relative) if relative.as_os_str().is_empty() => Path::new("."),
+        Ok(relative) => relative,
+        Err(_) => &working_directory,
+      };
+
+    let key = CacheKey {
+      body,
+      dependencies,
+      environment,
+      executor,
+      extension,
+      extra,
+      inputs,
+      positional: self
+        .takes_positional_arguments(&context.module.settings)
+        .then_some(positional),
+      recipe: self.recipe_path(),
+      sigils,
+      working_directory: Some(relative_working_directory),
+    };
+
+    let status = cache.status(config, positional, key, outputs, working_directory.clone())?;
+
+    if matches!(status, CacheStatus::Hit(_)) && config.verbosity.loquacious() {
+      eprintln!(
+        "{}",
+        config
+          .color
+          .stderr()
+          .banner()
+          .paint("===> cache hit, skipping invocation"),
+      );
+    }
+
+    Ok(Some(status))
+  }
+
+  fn save_cache(&self, cache: &Cache, lock: CacheLock) -> RunResult<'src, CacheRecord> {
+    for (output, path) in &lock.outputs {
+      if !filesystem::exists(path)? {
         return Err(Error::CacheOutputMissing {
           recipe: self.name(),
-          output,
+          output: output.clone(),
         });
       }
     }
 
-    cache_lock.map(CacheLock::save).transpose()?;
-
-    Ok(())
+    lock.save(cache)
   }
 
   pub(crate) fn groups(&self) -> BTreeSet<String> {
@@ -736,12 +1408,16 @@ impl<'src> Recipe<'src> {
     self.doc.as_deref()
   }
 
+  pub(crate) fn finally(&self) -> &[Dependency<'src>] {
+    &self.dependencies[self.dependencies.len() - self.finally..]
+  }
+
   pub(crate) fn priors(&self) -> &[Dependency<'src>] {
     &self.dependencies[..self.priors]
   }
 
   pub(crate) fn subsequents(&self) -> &[Dependency<'src>] {
-    &self.dependencies[self.priors..]
+    &self.dependencies[self.priors..self.dependencies.len() - self.finally]
   }
 }
 
@@ -758,8 +1434,12 @@ impl<D: Display> ColorDisplay for Recipe<'_, D> {
     }
     write!(f, ":")?;
 
+    let finally = self.dependencies.len() - self.finally;
+
     for (i, dependency) in self.dependencies.iter().enumerate() {
-      if i == self.priors {
+      if i == finally {
+        write!(f, " &&!")?;
+      } else if i == self.priors {
         write!(f, " &&")?;
       }
 
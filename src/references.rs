@@ -61,6 +61,10 @@ impl<'src> Iterator for References<'_, 'src> {
         Expression::Group { contents } => {
           self.stack.push(contents);
         }
+        Expression::Index { index, value, .. } => {
+          self.stack.push(index);
+          self.stack.push(value);
+        }
         Expression::Join { lhs, rhs, .. } => {
           self.stack.push(rhs);
           if let Some(lhs) = lhs {
@@ -72,6 +76,12 @@ impl<'src> Iterator for References<'_, 'src> {
             self.stack.push(element);
           }
         }
+        Expression::Map { entries, .. } => {
+          for (key, value) in entries.iter().rev() {
+            self.stack.push(value);
+            self.stack.push(key);
+          }
+        }
         Expression::Not { operand } => {
           self.stack.push(operand);
         }
@@ -23,6 +23,9 @@ pub fn run(args: impl Iterator<Item = impl Into<OsString> + Clone>) -> Result<()
   config
     .and_then(|config| {
       SignalHandler::install(config.verbosity)?;
+      Events::install(&config)?;
+      Junit::install(&config);
+      Trace::install(&config);
       config.subcommand.execute(&config, &loader)
     })
     .map_err(|error| {
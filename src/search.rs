@@ -243,7 +243,7 @@ impl Search {
 
   /// Search upwards from `directory` for a file whose name matches one of
   /// `JUSTFILE_NAMES`
-  fn justfile(config: &Config, directory: &Path) -> SearchResult<PathBuf> {
+  pub(crate) fn justfile(config: &Config, directory: &Path) -> SearchResult<PathBuf> {
     for directory in directory.ancestors() {
       let mut candidates = BTreeSet::new();
 
@@ -1,37 +1,57 @@
 use super::*;
 
-pub(crate) struct Semaphore(Condvar, Mutex<u64>);
+// Limits the number of simultaneously running recipes. Once closed, waiting
+// and future calls to `acquire` return `None`, so that no more recipes start,
+// unless acquiring for a finally dependency, which is no longer limited.
+pub(crate) struct Semaphore(Condvar, Mutex<Option<u64>>);
 
 pub(crate) struct Guard<'a>(&'a Semaphore);
 
 impl Drop for Guard<'_> {
   fn drop(&mut self) {
-    *self.0.mutex().lock().unwrap() += 1;
+    if let Some(count) = &mut *self.0.mutex().lock().unwrap() {
+      *count += 1;
+    }
     self.0.condvar().notify_one();
   }
 }
 
 impl Semaphore {
   pub(crate) fn new(resource: NonZeroU64) -> Self {
-    Self(Condvar::new(), Mutex::new(resource.into()))
+    Self(Condvar::new(), Mutex::new(Some(resource.into())))
   }
 
   fn condvar(&self) -> &Condvar {
     &self.0
   }
 
-  fn mutex(&self) -> &Mutex<u64> {
+  fn mutex(&self) -> &Mutex<Option<u64>> {
     &self.1
   }
 
-  pub(crate) fn acquire(&self) -> Guard {
+  pub(crate) fn acquire(&self, finally: bool) -> Option<Guard> {
     let mut count = self
       .condvar()
-      .wait_while(self.mutex().lock().unwrap(), |count| *count == 0)
+      .wait_while(self.mutex().lock().unwrap(), |count| *count == Some(0))
       .unwrap();
 
-    *count -= 1;
+    match count.as_mut() {
+      Some(count) => *count -= 1,
+      None if finally => {}
+      None => return None,
+    }
 
-    Guard(self)
+    Some(Guard(self))
+  }
+
+  pub(crate) fn close(&self) {
+    *self.mutex().lock().unwrap() = None;
+    self.condvar().notify_all();
+  }
+
+  // Whether the semaphore has been closed, for recipes which were waiting on
+  // something else when it was.
+  pub(crate) fn is_closed(&self) -> bool {
+    self.mutex().lock().unwrap().is_none()
   }
 }
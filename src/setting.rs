@@ -1,9 +1,13 @@
 use super::*;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, EnumDiscriminants)]
+#[strum_discriminants(name(SettingKind))]
+#[strum_discriminants(derive(EnumIter, IntoStaticStr))]
+#[strum_discriminants(strum(serialize_all = "kebab-case"))]
 pub(crate) enum Setting<'src> {
   AllowDuplicateRecipes(bool),
   AllowDuplicateVariables(bool),
+  CacheDirectory(Expression<'src>),
   DefaultList(bool),
   DefaultScript(bool),
   DotenvCommand(Expression<'src>),
@@ -17,11 +21,13 @@ pub(crate) enum Setting<'src> {
   Guards(bool),
   IgnoreComments(bool),
   Indentation(StringLiteral<'src>, Indentation),
+  JobLimits(Expression<'src>),
   Lazy(bool),
   Lists(bool),
   MinimumVersion(StringLiteral<'src>),
   NoCd(bool),
   NoExitMessage(bool),
+  Output(StringLiteral<'src>, OutputMode),
   PositionalArguments(bool),
   Quiet(bool),
   ScriptInterpreter(Interpreter<Expression<'src>>),
@@ -55,21 +61,63 @@ impl<'src> Setting<'src> {
       | Self::Quiet(value)
       | Self::Unstable(value)
       | Self::WindowsPowerShell(value) => *value,
-      Self::DotenvCommand(_value)
+      Self::CacheDirectory(_value)
+      | Self::DotenvCommand(_value)
       | Self::DotenvFilename(_value)
       | Self::DotenvPath(_value)
+      | Self::JobLimits(_value)
       | Self::Tempdir(_value)
       | Self::WorkingDirectory(_value) => false,
-      Self::Indentation(..) | Self::MinimumVersion(_) => false,
+      Self::Indentation(..) | Self::MinimumVersion(_) | Self::Output(..) => false,
       Self::ScriptInterpreter(_value) | Self::Shell(_value) | Self::WindowsShell(_value) => false,
     }
   }
 
+  // Whether the setting has the same value as when it is not set at all.
+  pub(crate) fn is_redundant(&self) -> bool {
+    match self {
+      Self::AllowDuplicateRecipes(value)
+      | Self::AllowDuplicateVariables(value)
+      | Self::DefaultList(value)
+      | Self::DefaultScript(value)
+      | Self::DotenvLoad(value)
+      | Self::DotenvOverride(value)
+      | Self::DotenvRequired(value)
+      | Self::Export(value)
+      | Self::Fallback(value)
+      | Self::Guards(value)
+      | Self::IgnoreComments(value)
+      | Self::Lazy(value)
+      | Self::Lists(value)
+      | Self::NoCd(value)
+      | Self::NoExitMessage(value)
+      | Self::PositionalArguments(value)
+      | Self::Quiet(value)
+      | Self::Unstable(value)
+      | Self::WindowsPowerShell(value) => !*value,
+      Self::Output(_literal, mode) => *mode == OutputMode::Interleaved,
+      Self::CacheDirectory(_)
+      | Self::DotenvCommand(_)
+      | Self::DotenvFilename(_)
+      | Self::DotenvPath(_)
+      | Self::Indentation(..)
+      | Self::JobLimits(_)
+      | Self::MinimumVersion(_)
+      | Self::ScriptInterpreter(_)
+      | Self::Shell(_)
+      | Self::Tempdir(_)
+      | Self::WindowsShell(_)
+      | Self::WorkingDirectory(_) => false,
+    }
+  }
+
   pub(crate) fn expressions_mut(&mut self) -> impl Iterator<Item = &mut Expression<'src>> {
     let (first, rest) = match self {
-      Self::DotenvCommand(value)
+      Self::CacheDirectory(value)
+      | Self::DotenvCommand(value)
       | Self::DotenvFilename(value)
       | Self::DotenvPath(value)
+      | Self::JobLimits(value)
       | Self::Tempdir(value)
       | Self::WorkingDirectory(value) => (Some(value), None),
       Self::ScriptInterpreter(value) | Self::Shell(value) | Self::WindowsShell(value) => (
@@ -123,14 +171,18 @@ impl Display for Setting<'_> {
       | Self::Quiet(value)
       | Self::Unstable(value)
       | Self::WindowsPowerShell(value) => write!(f, "{value}"),
-      Self::DotenvCommand(value)
+      Self::CacheDirectory(value)
+      | Self::DotenvCommand(value)
       | Self::DotenvFilename(value)
       | Self::DotenvPath(value)
+      | Self::JobLimits(value)
       | Self::Tempdir(value)
       | Self::WorkingDirectory(value) => {
         write!(f, "{value}")
       }
-      Self::Indentation(value, _) | Self::MinimumVersion(value) => write!(f, "{value}"),
+      Self::Indentation(value, _) | Self::MinimumVersion(value) | Self::Output(value, _) => {
+        write!(f, "{value}")
+      }
       Self::ScriptInterpreter(value) | Self::Shell(value) | Self::WindowsShell(value) => {
         write!(f, "[{value}]")
       }
@@ -20,6 +20,7 @@ where
 pub(crate) struct Settings {
   pub(crate) allow_duplicate_recipes: bool,
   pub(crate) allow_duplicate_variables: bool,
+  pub(crate) cache_directory: Option<String>,
   pub(crate) default_list: bool,
   pub(crate) default_script: bool,
   pub(crate) dotenv_command: Value,
@@ -35,10 +36,12 @@ pub(crate) struct Settings {
   pub(crate) guards: bool,
   pub(crate) ignore_comments: bool,
   pub(crate) indentation: Option<Indentation>,
+  pub(crate) job_limits: BTreeMap<String, NonZeroU64>,
   pub(crate) lazy: bool,
   pub(crate) lists: bool,
   pub(crate) no_cd: bool,
   pub(crate) no_exit_message: bool,
+  pub(crate) output: Option<OutputMode>,
   pub(crate) positional_arguments: bool,
   pub(crate) quiet: bool,
   #[serde(skip)]
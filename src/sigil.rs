@@ -1,4 +1,7 @@
-#[derive(Eq, Ord, PartialEq, PartialOrd)]
+use super::*;
+
+#[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub(crate) enum Sigil {
   Guard,
   Infallible,
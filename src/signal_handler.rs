@@ -1,13 +1,17 @@
 use super::*;
 
 pub(crate) struct SignalHandler {
+  cancelled: bool,
   caught: Option<Signal>,
   children: BTreeMap<i32, Command>,
   initialized: bool,
+  process_groups: BTreeSet<i32>,
   verbosity: Verbosity,
 }
 
 impl SignalHandler {
+  const TIMEOUT_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
   pub(crate) fn install(verbosity: Verbosity) -> RunResult<'static> {
     let mut instance = Self::instance();
     instance.verbosity = verbosity;
@@ -19,7 +23,9 @@ impl SignalHandler {
   }
 
   pub(crate) fn clear() {
-    Self::instance().caught = None;
+    let mut instance = Self::instance();
+    instance.cancelled = false;
+    instance.caught = None;
   }
 
   pub(crate) fn instance() -> MutexGuard<'static, Self> {
@@ -40,14 +46,18 @@ impl SignalHandler {
 
   const fn new() -> Self {
     Self {
+      cancelled: false,
       caught: None,
       children: BTreeMap::new(),
       initialized: false,
+      process_groups: BTreeSet::new(),
       verbosity: Verbosity::default(),
     }
   }
 
   fn handle(&mut self, signal: Signal) {
+    Events::emit(Event::Signal { signal });
+
     if signal.is_fatal() {
       if self.children.is_empty() {
         process::exit(signal.code());
@@ -62,8 +72,18 @@ impl SignalHandler {
       // SIGHUP, SIGINT, and SIGQUIT are normally sent on terminal close,
       // ctrl-c, and ctrl-\, respectively, and are sent to all processes in the
       // foreground process group. this includes child processes, so we ignore
-      // the signal and wait for them to exit
-      Signal::Hangup | Signal::Interrupt | Signal::Quit => {}
+      // the signal and wait for them to exit. children in their own process
+      // group do not receive the signal, so it is forwarded to them
+      Signal::Hangup | Signal::Interrupt | Signal::Quit => {
+        #[cfg(not(windows))]
+        for &process_group in &self.process_groups {
+          nix::sys::signal::killpg(
+            nix::unistd::Pid::from_raw(process_group),
+            Some(signal.into()),
+          )
+          .ok();
+        }
+      }
       #[cfg(any(
         target_os = "dragonfly",
         target_os = "freebsd",
@@ -94,31 +114,110 @@ impl SignalHandler {
       }
       // SIGTERM is the default signal sent by kill. forward it to child
       // processes and wait for them to exit
-      Signal::Terminate =>
-      {
-        #[cfg(not(windows))]
-        for &child in self.children.keys() {
-          if self.verbosity.loquacious() {
-            eprintln!("just: sending SIGTERM to child process {child}");
-          }
-          nix::sys::signal::kill(
-            nix::unistd::Pid::from_raw(child),
-            Some(Signal::Terminate.into()),
-          )
-          .ok();
-        }
-      }
+      Signal::Terminate => self.terminate_children(),
     }
   }
 
+  // Stop the current run as if `just` had received SIGTERM, used by `--watch`
+  // to restart when watched files change. Commands spawned afterwards are
+  // also sent SIGTERM, unless the signal was taken to run finally
+  // dependencies, until the handler is cleared.
+  pub(crate) fn terminate() {
+    let mut instance = Self::instance();
+    instance.cancelled = true;
+    instance.caught.get_or_insert(Signal::Terminate);
+    instance.terminate_children();
+  }
+
+  fn terminate_children(&self) {
+    #[cfg(not(windows))]
+    for &child in self.children.keys() {
+      self.terminate_child(child);
+    }
+  }
+
+  #[cfg(not(windows))]
+  fn terminate_child(&self, child: i32) {
+    if self.verbosity.loquacious() {
+      eprintln!("just: sending SIGTERM to child process {child}");
+    }
+    if self.process_groups.contains(&child) {
+      nix::sys::signal::killpg(
+        nix::unistd::Pid::from_raw(child),
+        Some(Signal::Terminate.into()),
+      )
+      .ok();
+    } else {
+      nix::sys::signal::kill(
+        nix::unistd::Pid::from_raw(child),
+        Some(Signal::Terminate.into()),
+      )
+      .ok();
+    }
+  }
+
+  pub(crate) fn caught() -> Option<Signal> {
+    Self::instance().caught
+  }
+
+  // Restore a signal cleared with `take`, unless another signal has since
+  // been caught.
+  pub(crate) fn restore(caught: Option<Signal>) {
+    let mut instance = Self::instance();
+    instance.caught = instance.caught.or(caught);
+  }
+
+  // Clear and return the caught signal, so that commands may be run after
+  // the signal was received.
+  pub(crate) fn take() -> Option<Signal> {
+    Self::instance().caught.take()
+  }
+
   pub(crate) fn spawn<T>(
-    mut command: Command,
-    f: impl Fn(process::Child) -> io::Result<T>,
+    command: Command,
+    f: impl FnOnce(process::Child) -> io::Result<T>,
   ) -> (io::Result<T>, Option<Signal>) {
+    let (result, caught, _timed_out) = Self::spawn_inner(command, None, f, |_| None);
+    (result, caught)
+  }
+
+  // Spawn `command` and call `f` with the child. If `deadline` passes before
+  // `f` returns, the child's process group is terminated, and killed if it
+  // does not exit within the grace period, and `true` is returned.
+  //
+  // If standard input is a terminal and `just` is in its foreground process
+  // group, the child's process group is made the foreground process group
+  // until the child exits, since a background process which reads from the
+  // terminal is stopped. Signals sent by the terminal then only reach the
+  // child, so if the child is killed by SIGHUP, SIGINT, or SIGQUIT, the
+  // signal is recorded as if `just` had caught it.
+  pub(crate) fn spawn_with_deadline(
+    command: Command,
+    deadline: Option<Instant>,
+    f: impl FnOnce(process::Child) -> io::Result<ExitStatus>,
+  ) -> (io::Result<ExitStatus>, Option<Signal>, bool) {
+    Self::spawn_inner(command, deadline, f, |status| {
+      Platform::signal_from_exit_status(*status)
+        .and_then(|signal| u8::try_from(signal).ok())
+        .and_then(|signal| Signal::try_from(signal).ok())
+        .filter(|signal| matches!(signal, Signal::Hangup | Signal::Interrupt | Signal::Quit))
+    })
+  }
+
+  fn spawn_inner<T>(
+    mut command: Command,
+    deadline: Option<Instant>,
+    f: impl FnOnce(process::Child) -> io::Result<T>,
+    terminal_signal: impl FnOnce(&T) -> Option<Signal>,
+  ) -> (io::Result<T>, Option<Signal>, bool) {
+    if deadline.is_some() {
+      Platform::set_process_group(&mut command);
+    }
+
     let mut instance = Self::instance();
 
     let child = match command.spawn() {
-      Err(err) => return (Err(err), None),
+      Err(err) => return (Err(err), None, false),
       Ok(child) => child,
     };
 
@@ -127,6 +226,7 @@ impl SignalHandler {
         return (
           Err(io::Error::other(format!("invalid child PID: {err}"))),
           None,
+          false,
         );
       }
       Ok(pid) => pid,
@@ -134,14 +234,76 @@ impl SignalHandler {
 
     instance.children.insert(pid, command);
 
+    if deadline.is_some() {
+      instance.process_groups.insert(pid);
+    }
+
+    #[cfg(not(windows))]
+    if instance.cancelled && instance.caught.is_some() {
+      instance.terminate_child(pid);
+    }
+
     drop(instance);
 
-    let result = f(child);
+    let foreground = deadline.is_some() && Platform::set_foreground_process_group(pid);
+
+    let (result, timed_out) = thread::scope(|scope| {
+      let (exited, receiver) = mpsc::channel::<()>();
+
+      let watchdog =
+        deadline.map(|deadline| scope.spawn(move || Self::watchdog(pid, deadline, &receiver)));
+
+      let result = f(child);
+
+      drop(exited);
+
+      let timed_out = watchdog.is_some_and(|watchdog| watchdog.join().unwrap_or_default());
+
+      (result, timed_out)
+    });
+
+    if foreground {
+      Platform::restore_foreground_process_group(pid);
+    }
 
     let mut instance = Self::instance();
 
     instance.children.remove(&pid);
+    instance.process_groups.remove(&pid);
+
+    if foreground
+      && let Ok(value) = &result
+      && let Some(signal) = terminal_signal(value)
+    {
+      instance.caught.get_or_insert(signal);
+    }
+
+    (result, instance.caught, timed_out)
+  }
+
+  fn watchdog(pid: i32, deadline: Instant, exited: &mpsc::Receiver<()>) -> bool {
+    let remaining = deadline.saturating_duration_since(Instant::now());
+
+    if exited.recv_timeout(remaining) != Err(RecvTimeoutError::Timeout) {
+      return false;
+    }
+
+    let loquacious = Self::instance().verbosity.loquacious();
+
+    if loquacious {
+      eprintln!("just: timeout expired, terminating child process group {pid}");
+    }
+
+    Platform::terminate_process_group(pid, false);
+
+    if exited.recv_timeout(Self::TIMEOUT_GRACE_PERIOD) == Err(RecvTimeoutError::Timeout) {
+      if loquacious {
+        eprintln!("just: grace period expired, killing child process group {pid}");
+      }
+
+      Platform::terminate_process_group(pid, true);
+    }
 
-    (result, instance.caught)
+    true
   }
 }
@@ -0,0 +1,116 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub(crate) struct Size(pub(crate) u64);
+
+impl Size {
+  const UNITS: [(&str, u64); 4] = [
+    ("T", 1 << 40),
+    ("G", 1 << 30),
+    ("M", 1 << 20),
+    ("K", 1 << 10),
+  ];
+}
+
+impl Display for Size {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    for (unit, bytes) in Self::UNITS {
+      if self.0 >= bytes {
+        #[allow(clippy::cast_precision_loss)]
+        return write!(f, "{:.1} {unit}iB", self.0 as f64 / bytes as f64);
+      }
+    }
+
+    write!(f, "{} B", self.0)
+  }
+}
+
+impl FromStr for Size {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let error = || format!("invalid size `{s}`, expected a number followed by B, K, M, G, or T");
+
+    let digits = s
+      .find(|c: char| !c.is_ascii_digit() && c != '.')
+      .unwrap_or(s.len());
+
+    let (number, unit) = s.split_at(digits);
+
+    let number = number.parse::<f64>().map_err(|_| error())?;
+
+    let unit = unit.to_ascii_uppercase();
+
+    let unit = unit
+      .strip_suffix("IB")
+      .or_else(|| unit.strip_suffix('B'))
+      .unwrap_or(&unit);
+
+    let multiplier = if unit.is_empty() {
+      1
+    } else {
+      Self::UNITS
+        .iter()
+        .find(|(name, _)| *name == unit)
+        .map(|(_, bytes)| *bytes)
+        .ok_or_else(error)?
+    };
+
+    #[allow(
+      clippy::cast_possible_truncation,
+      clippy::cast_precision_loss,
+      clippy::cast_sign_loss
+    )]
+    Ok(Self((number * multiplier as f64) as u64))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn from_str() {
+    #[track_caller]
+    fn case(s: &str, bytes: u64) {
+      assert_eq!(s.parse::<Size>().unwrap(), Size(bytes));
+    }
+
+    case("0", 0);
+    case("100", 100);
+    case("100B", 100);
+    case("1K", 1024);
+    case("1k", 1024);
+    case("1KB", 1024);
+    case("1KiB", 1024);
+    case("1.5M", 1024 * 1024 * 3 / 2);
+    case("2G", 2 << 30);
+    case("1T", 1 << 40);
+  }
+
+  #[test]
+  fn from_str_error() {
+    #[track_caller]
+    fn case(s: &str) {
+      assert_eq!(
+        s.parse::<Size>().unwrap_err(),
+        format!("invalid size `{s}`, expected a number followed by B, K, M, G, or T"),
+      );
+    }
+
+    case("");
+    case("G");
+    case("1X");
+    case("1 G");
+    case("-1");
+  }
+
+  #[test]
+  fn display() {
+    assert_eq!(Size(0).to_string(), "0 B");
+    assert_eq!(Size(1023).to_string(), "1023 B");
+    assert_eq!(Size(1024).to_string(), "1.0 KiB");
+    assert_eq!(Size(1536).to_string(), "1.5 KiB");
+    assert_eq!(Size(2 << 30).to_string(), "2.0 GiB");
+  }
+}
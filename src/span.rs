@@ -0,0 +1,13 @@
+use super::*;
+
+// A recipe run, recorded with `--trace`.
+pub(crate) struct Span {
+  pub(crate) arguments: Vec<Value>,
+  pub(crate) cache: Option<&'static str>,
+  pub(crate) code: i32,
+  pub(crate) dependency: bool,
+  pub(crate) duration: Duration,
+  pub(crate) positional: Vec<String>,
+  pub(crate) recipe: Modulepath,
+  pub(crate) start: Instant,
+}
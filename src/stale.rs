@@ -0,0 +1,186 @@
+use super::*;
+
+// The reason a recipe with the `[stale]` attribute must run. Like make, an
+// invocation is up to date if every target exists and is strictly newer than
+// every source.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Stale {
+  NoTargets,
+  SourceNewer { source: String, target: String },
+  TargetMissing { target: String },
+}
+
+impl Stale {
+  // Check `targets` against `sources`, returning `None` if the invocation is
+  // up to date.
+  pub(crate) fn check(
+    cache: &Cache,
+    sources: Value,
+    targets: Value,
+    working_directory: &Path,
+  ) -> RunResult<'static, Option<Self>> {
+    // Sources are expanded first, so that they are recorded for `--watch`
+    // even if a target is missing.
+    let mut expanded = Vec::new();
+    for source in sources {
+      expanded.extend(Self::expand(cache, &source, working_directory, true)?);
+    }
+
+    let mut oldest = None::<(String, SystemTime)>;
+
+    for target in targets {
+      let paths = Self::expand(cache, &target, working_directory, false)?;
+
+      if paths.is_empty() {
+        return Ok(Some(Self::TargetMissing { target }));
+      }
+
+      for (target, path) in paths {
+        let modified = Self::modified(&path)?;
+        if oldest
+          .as_ref()
+          .is_none_or(|(_target, oldest)| modified < *oldest)
+        {
+          oldest = Some((target, modified));
+        }
+      }
+    }
+
+    let Some((target, oldest)) = oldest else {
+      return Ok(Some(Self::NoTargets));
+    };
+
+    // Unlike make, a source with the same modification time as the oldest
+    // target makes the recipe stale. Filesystem timestamps can be coarse, so
+    // a source edited in the same tick that a target was written may be newer
+    // than the target without appearing to be.
+    for (source, path) in expanded {
+      if Self::modified(&path)? >= oldest {
+        return Ok(Some(Self::SourceNewer { source, target }));
+      }
+    }
+
+    Ok(None)
+  }
+
+  // Expand `pattern` into matching paths, using the same walk as cache inputs
+  // so that both agree on which files match. Literal directory sources are
+  // walked, since a directory's modification time does not reflect changes to
+  // files nested inside it, but literal targets are used as-is.
+  fn expand(
+    cache: &Cache,
+    pattern: &str,
+    working_directory: &Path,
+    source: bool,
+  ) -> RunResult<'static, BTreeMap<String, PathBuf>> {
+    let mut paths = BTreeMap::new();
+
+    if pattern.contains(cache::GLOB_METACHARACTERS) {
+      let (base, rest) = Cache::split_pattern(pattern);
+
+      let matcher = Cache::glob(rest)
+        .map_err(|source| Error::StalePattern {
+          pattern: pattern.into(),
+          source,
+        })?
+        .compile_matcher();
+
+      let root = working_directory.join(base);
+
+      if source {
+        cache.watch(&root);
+      }
+
+      if filesystem::exists(&root)? {
+        Self::walk(
+          cache,
+          base,
+          &root,
+          working_directory,
+          |relative| matcher.is_match(relative),
+          &mut paths,
+        )?;
+      }
+
+      if source && paths.is_empty() {
+        return Err(Error::StaleSourceUnmatched {
+          pattern: pattern.into(),
+        });
+      }
+
+      return Ok(paths);
+    }
+
+    let path = working_directory.join(pattern);
+
+    if source {
+      cache.watch(&path);
+    }
+
+    if !filesystem::exists(&path)? {
+      if source {
+        return Err(Error::StaleSourceMissing { path });
+      }
+      return Ok(paths);
+    }
+
+    if source && path.is_dir() {
+      Self::walk(
+        cache,
+        pattern,
+        &path,
+        working_directory,
+        |_relative| true,
+        &mut paths,
+      )?;
+    } else {
+      paths.insert(pattern.into(), path);
+    }
+
+    Ok(paths)
+  }
+
+  fn modified(path: &Path) -> RunResult<'static, SystemTime> {
+    fs::metadata(path)
+      .and_then(|metadata| metadata.modified())
+      .map_err(|source| Error::FilesystemIo {
+        source,
+        path: path.into(),
+      })
+  }
+
+  fn walk(
+    cache: &Cache,
+    base: &str,
+    root: &Path,
+    working_directory: &Path,
+    filter: impl Fn(&Path) -> bool,
+    paths: &mut BTreeMap<String, PathBuf>,
+  ) -> RunResult<'static> {
+    cache
+      .walk(
+        base,
+        root,
+        &GlobSet::empty(),
+        false,
+        working_directory,
+        filter,
+        paths,
+      )
+      .map_err(|source| Error::StaleWalk { source })?;
+
+    Ok(())
+  }
+}
+
+impl Display for Stale {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::NoTargets => write!(f, "no targets"),
+      Self::SourceNewer { source, target } => {
+        write!(f, "source `{source}` is newer than target `{target}`")
+      }
+      Self::TargetMissing { target } => write!(f, "target `{target}` does not exist"),
+    }
+  }
+}
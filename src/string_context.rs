@@ -4,6 +4,9 @@ use super::*;
 pub(crate) enum StringContext<'src> {
   EnvKey(Name<'src>),
   Function(Name<'src>),
+  Index(Token<'src>),
+  MapKey(Token<'src>),
+  MapValue(Token<'src>),
   Setting(Name<'src>),
   TimestampAttribute(Name<'src>),
   WorkingDirectoryAttribute(Name<'src>),
@@ -17,6 +20,7 @@ impl<'src> StringContext<'src> {
       | Self::Setting(name)
       | Self::TimestampAttribute(name)
       | Self::WorkingDirectoryAttribute(name) => name.token,
+      Self::Index(token) | Self::MapKey(token) | Self::MapValue(token) => *token,
     }
   }
 }
@@ -26,6 +30,9 @@ impl Display for StringContext<'_> {
     match self {
       Self::EnvKey(_) => write!(f, "used as `env` attribute name"),
       Self::Function(name) => write!(f, "passed to `{name}()`"),
+      Self::Index(_) => write!(f, "used as index"),
+      Self::MapKey(_) => write!(f, "used as map key"),
+      Self::MapValue(_) => write!(f, "used as map value"),
       Self::Setting(name) => write!(f, "assigned to `{name}` setting"),
       Self::TimestampAttribute(_) => {
         write!(f, "used as a `[timestamp]` attribute")
@@ -14,6 +14,12 @@ const CHOOSER_CANCELLED_EXIT_STATUS: i32 = 130;
 #[derive(PartialEq, Clone, Debug, IntoStaticStr)]
 #[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
 pub(crate) enum Subcommand {
+  CacheList,
+  CachePrune {
+    max_size: Option<Size>,
+    older_than: Option<Timespan>,
+  },
+  CacheStats,
   Changelog,
   Choose {
     chooser: Option<PathBuf>,
@@ -42,6 +48,7 @@ pub(crate) enum Subcommand {
   List {
     path: Modulepath,
   },
+  Lsp,
   Man,
   Request {
     request: Request,
@@ -81,6 +88,7 @@ impl Subcommand {
         return Ok(());
       }
       Init => return Self::init(config),
+      Lsp => return crate::Lsp::run(config),
       Man => return Self::man(),
       Request { request } => return Self::request(request),
       _ => {}
@@ -100,6 +108,12 @@ impl Subcommand {
     let justfile = &compilation.justfile;
 
     match self {
+      CacheList => Cache::new(config, &search, &justfile.settings).list()?,
+      CachePrune {
+        max_size,
+        older_than,
+      } => Cache::new(config, &search, &justfile.settings).prune(config, *max_size, *older_than)?,
+      CacheStats => Cache::new(config, &search, &justfile.settings).stats()?,
       Choose { chooser } => {
         Self::choose(
           chooser.as_deref(),
@@ -110,9 +124,15 @@ impl Subcommand {
         )?;
       }
       Command { .. } | Evaluate { .. } => {
-        justfile.run(config, &search, &[], &compilation.overrides)?;
+        justfile.run(
+          config,
+          &search,
+          &Cache::new(config, &search, &justfile.settings),
+          &[],
+          &compilation.overrides,
+        )?;
       }
-      Clean { path } => Self::clean(config, &search, path.as_ref())?,
+      Clean { path } => Self::clean(config, &search, &justfile.settings, path.as_ref())?,
       Dump { format } => Self::dump(config, compilation, *format)?,
       Groups => Self::groups(config, justfile),
       List { path } => Self::list(config, justfile, path)?,
@@ -121,7 +141,7 @@ impl Subcommand {
       Summary => Self::summary(config, justfile),
       Usage { path } => Self::usage(config, justfile, path)?,
       Variables => Self::variables(justfile),
-      Changelog | Completions { .. } | Edit | Format | Init | Man | Request { .. } => {
+      Changelog | Completions { .. } | Edit | Format | Init | Lsp | Man | Request { .. } => {
         unreachable!()
       }
     }
@@ -190,7 +210,35 @@ impl Subcommand {
         return Self::list(config, justfile, &path);
       }
 
-      let result = justfile.run(config, &search, arguments, &compilation.overrides);
+      let result = if config.watch {
+        let mut initial = Some(compilation);
+
+        Watch::new(config).run(
+          || {
+            // The justfile is compiled again before each run after the first,
+            // so that changes to it take effect.
+            let compilation = match initial.take() {
+              Some(compilation) => compilation,
+              None => Self::compile(config, loader, &search)?,
+            };
+            let cache = Cache::new(config, &search, &compilation.justfile.settings);
+            Ok((compilation, cache))
+          },
+          |compilation, cache| {
+            compilation
+              .justfile
+              .run(config, &search, cache, arguments, &compilation.overrides)
+          },
+        )
+      } else {
+        justfile.run(
+          config,
+          &search,
+          &Cache::new(config, &search, &justfile.settings),
+          arguments,
+          &compilation.overrides,
+        )
+      };
 
       if fallback
         && let Err(err @ (Error::UnknownRecipe { .. } | Error::UnknownSubmodule { .. })) = result
@@ -242,15 +290,11 @@ impl Subcommand {
     loader: &'src Loader,
     search: &Search,
   ) -> RunResult<'src, Compilation<'src>> {
-    let compilation = Compiler::compile(config, loader, &search.justfile)?;
+    let mut compilation = Compiler::compile(config, loader, &search.justfile)?;
 
     compilation.justfile.check_unstable(config)?;
 
-    if config.verbosity.loud() {
-      for warning in &compilation.justfile.warnings {
-        eprintln!("{}", warning.color_display(config.color.stderr()));
-      }
-    }
+    Self::lint(config, &mut compilation.justfile)?;
 
     Ok(compilation)
   }
@@ -348,16 +392,27 @@ impl Subcommand {
         .map(str::to_owned)
         .collect::<Vec<String>>();
 
-      justfile.run(config, search, &arguments, overrides)?;
+      justfile.run(
+        config,
+        search,
+        &Cache::new(config, search, &justfile.settings),
+        &arguments,
+        overrides,
+      )?;
     }
 
     Ok(())
   }
 
-  fn clean(config: &Config, search: &Search, prefix: Option<&Modulepath>) -> RunResult<'static> {
+  fn clean(
+    config: &Config,
+    search: &Search,
+    settings: &Settings,
+    prefix: Option<&Modulepath>,
+  ) -> RunResult<'static> {
     let entry_re = Regex::new(r"^[0-9a-f]{64}\.json$").unwrap();
 
-    let path = Cache::dir(search);
+    let path = Cache::dir(config, search, settings);
 
     let context = |source| Error::FilesystemIo {
       source,
@@ -412,6 +467,18 @@ impl Subcommand {
       removed += 1;
     }
 
+    if prefix.is_none() {
+      let blobs = path.join(Cache::BLOBS);
+      if let Err(err) = fs::remove_dir_all(&blobs)
+        && err.kind() != io::ErrorKind::NotFound
+      {
+        return Err(Error::FilesystemIo {
+          source: err,
+          path: blobs,
+        });
+      }
+    }
+
     if let Err(err) = fs::remove_dir(&path)
       && err.kind() != io::ErrorKind::DirectoryNotEmpty
     {
@@ -481,6 +548,51 @@ impl Subcommand {
     Ok(())
   }
 
+  /// Report warnings about `justfile` if linting, and discard them otherwise,
+  /// so that dumps only include warnings when linting
+  fn lint(config: &Config, justfile: &mut Justfile) -> RunResult<'static> {
+    let lints = config.lints();
+
+    // Private recipes named on the command line are used by being run, so
+    // they are not reported as unused.
+    let invoked = |recipe: &str| match &config.subcommand {
+      Self::Run { arguments } => arguments
+        .iter()
+        .any(|argument| argument.rsplit("::").next() == Some(recipe)),
+      _ => false,
+    };
+
+    // Dumps contain the justfile itself, and JSON dumps include its warnings,
+    // so warnings are not printed when dumping.
+    let print = config.verbosity.loud() && !matches!(config.subcommand, Self::Dump { .. });
+
+    let mut warnings = 0;
+    let mut stack = vec![justfile];
+    while let Some(module) = stack.pop() {
+      module.warnings.retain(|warning| {
+        lints
+          && !matches!(
+            warning,
+            Warning::UnusedPrivateRecipe { recipe } if invoked(recipe.lexeme()),
+          )
+      });
+
+      for warning in &module.warnings {
+        if print {
+          eprintln!("{}", warning.color_display(config.color.stderr()));
+        }
+        warnings += 1;
+      }
+      stack.extend(module.modules.values_mut().rev());
+    }
+
+    if config.deny_warnings && warnings > 0 {
+      return Err(Error::DeniedWarnings { count: warnings });
+    }
+
+    Ok(())
+  }
+
   fn format<'src>(config: &Config, loader: &'src Loader, search: &Search) -> RunResult<'src> {
     let root = search.justfile_parent();
 
@@ -1015,13 +1127,17 @@ impl Subcommand {
 
   pub(crate) fn takes_arguments(&self) -> bool {
     match self {
-      Self::Changelog
+      Self::CacheList
+      | Self::CachePrune { .. }
+      | Self::CacheStats
+      | Self::Changelog
       | Self::Completions { .. }
       | Self::Dump { .. }
       | Self::Edit
       | Self::Format
       | Self::Groups
       | Self::Init
+      | Self::Lsp
       | Self::Man
       | Self::Summary
       | Self::Variables => false,
@@ -12,7 +12,7 @@ impl Switch {
     recipe: &Recipe<'src>,
     long: &BTreeMap<&str, usize>,
     short: &BTreeMap<char, usize>,
-    arguments: &mut [Value],
+    arguments: &mut [Vec<String>],
     rest: &[&str],
     i: &mut usize,
     value: Option<&str>,
@@ -67,7 +67,7 @@ impl Switch {
       });
     }
 
-    group.push(value);
+    group.push(value.to_owned());
 
     Ok(())
   }
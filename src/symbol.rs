@@ -0,0 +1,71 @@
+use super::*;
+
+/// The definition which a name in a justfile refers to
+#[derive(Clone, Copy)]
+pub(crate) enum Symbol<'src: 'run, 'run> {
+  Assignment(&'run Assignment<'src>),
+  Builtin(&'src str),
+  Function(&'run FunctionDefinition<'src>),
+  FunctionParameter(Name<'src>),
+  Import(&'run Path),
+  Module(&'run Justfile<'src>),
+  Recipe(&'run Recipe<'src>),
+  RecipeParameter(&'run Parameter<'src>),
+}
+
+impl<'src> Symbol<'src, '_> {
+  /// The token which defines the symbol, if it is defined in a justfile
+  pub(crate) fn definition(&self) -> Option<Token<'src>> {
+    match self {
+      Self::Assignment(assignment) => Some(assignment.name.token),
+      Self::Builtin(_) | Self::Import(_) | Self::Module(_) => None,
+      Self::Function(function) => Some(function.name.token),
+      Self::FunctionParameter(name) => Some(name.token),
+      Self::Recipe(recipe) => Some(recipe.name.token),
+      Self::RecipeParameter(parameter) => Some(parameter.name.token),
+    }
+  }
+
+  /// The source file which the symbol refers to, if it refers to one
+  pub(crate) fn file(&self) -> Option<&Path> {
+    match self {
+      Self::Import(path) => Some(path),
+      Self::Module(module) => Some(&module.source),
+      _ => None,
+    }
+  }
+
+  /// Markdown describing the symbol
+  pub(crate) fn hover(&self) -> String {
+    let (signature, doc) = match self {
+      Self::Assignment(assignment) => (assignment.to_string(), None),
+      Self::Builtin(name) => (format!("{name}()"), Some("built-in function")),
+      Self::Function(function) => (function.to_string(), None),
+      Self::FunctionParameter(name) => (name.to_string(), None),
+      Self::Import(path) => (format!("import '{}'", path.display()), None),
+      Self::Module(module) => (format!("mod {}", module.name()), module.doc.as_deref()),
+      Self::Recipe(recipe) => (
+        RecipeSignature {
+          name: recipe.name(),
+          recipe,
+        }
+        .color_display(Color::never())
+        .to_string(),
+        recipe.doc(),
+      ),
+      Self::RecipeParameter(parameter) => (
+        parameter.color_display(Color::never()).to_string(),
+        parameter.help.as_deref(),
+      ),
+    };
+
+    let mut hover = format!("```just\n{signature}\n```");
+
+    if let Some(doc) = doc {
+      hover.push_str("\n\n");
+      hover.push_str(doc);
+    }
+
+    hover
+  }
+}
@@ -0,0 +1,231 @@
+use super::*;
+
+/// The names in a source file and the symbols they refer to
+pub(crate) struct SymbolIndex<'src: 'run, 'run> {
+  module: &'run Justfile<'src>,
+  occurrences: Vec<(Token<'src>, Symbol<'src, 'run>)>,
+}
+
+impl<'src: 'run, 'run> SymbolIndex<'src, 'run> {
+  /// Index the source file at `path`, resolving names in the module which
+  /// it belongs to
+  pub(crate) fn new(compilation: &'run Compilation<'src>, path: &Path) -> Option<Self> {
+    let ((modulepath, _path), ast) = compilation
+      .asts
+      .iter()
+      .filter(|((_modulepath, ast_path), _ast)| ast_path == path)
+      .min_by_key(|((modulepath, _path), _ast)| modulepath)?;
+
+    let mut index = Self {
+      module: compilation.justfile.submodule(modulepath)?,
+      occurrences: Vec::new(),
+    };
+
+    for item in &ast.items {
+      index.item(item);
+    }
+
+    Some(index)
+  }
+
+  /// The name at `offset` and the symbol it refers to
+  pub(crate) fn get(&self, offset: usize) -> Option<&(Token<'src>, Symbol<'src, 'run>)> {
+    self
+      .occurrences
+      .iter()
+      .find(|(token, _symbol)| token.offset <= offset && offset <= token.offset + token.length)
+  }
+
+  fn expression(
+    &mut self,
+    expression: &'run Expression<'src>,
+    scope: &BTreeMap<&'src str, Symbol<'src, 'run>>,
+  ) {
+    for reference in expression.references() {
+      let (name, symbol) = match reference {
+        Reference::Call { name, .. } => (
+          name,
+          self
+            .module
+            .functions
+            .get(name.lexeme())
+            .map(Symbol::Function)
+            .or_else(|| function::get(name.lexeme()).map(|_| Symbol::Builtin(name.lexeme()))),
+        ),
+        Reference::Variable(name) => (
+          name,
+          scope.get(name.lexeme()).copied().or_else(|| {
+            self
+              .module
+              .assignments
+              .get(name.lexeme())
+              .map(Symbol::Assignment)
+          }),
+        ),
+      };
+
+      if let Some(symbol) = symbol {
+        self.occurrences.push((name.token, symbol));
+      }
+    }
+  }
+
+  fn item(&mut self, item: &'run Item<'src>) {
+    let global = BTreeMap::new();
+
+    match item {
+      Item::Alias(alias) => {
+        if let Some(recipe) = self.recipe_path(&alias.target) {
+          self
+            .occurrences
+            .push((alias.name.token, Symbol::Recipe(recipe)));
+        }
+      }
+      Item::Assignment(assignment) => {
+        if let Some(assignment) = self.module.assignments.get(assignment.name.lexeme()) {
+          self
+            .occurrences
+            .push((assignment.name.token, Symbol::Assignment(assignment)));
+        }
+        self.expression(&assignment.value, &global);
+      }
+      Item::Function(function) => {
+        self
+          .occurrences
+          .push((function.name.token, Symbol::Function(function)));
+
+        let mut scope = BTreeMap::new();
+
+        for (parameter, _number) in &function.parameters {
+          self
+            .occurrences
+            .push((parameter.token, Symbol::FunctionParameter(*parameter)));
+          scope.insert(parameter.lexeme(), Symbol::FunctionParameter(*parameter));
+        }
+
+        self.expression(&function.body, &scope);
+      }
+      Item::Import {
+        absolute: Some(absolute),
+        relative,
+        ..
+      } => self
+        .occurrences
+        .push((relative.token, Symbol::Import(absolute))),
+      Item::Module { name, relative, .. } => {
+        if let Some(module) = self.module.modules.get(name.lexeme()) {
+          self.occurrences.push((name.token, Symbol::Module(module)));
+          if let Some(relative) = relative {
+            self
+              .occurrences
+              .push((relative.token, Symbol::Module(module)));
+          }
+        }
+      }
+      Item::Recipe(recipe) => self.recipe(recipe),
+      Item::Comment(_)
+      | Item::Import { absolute: None, .. }
+      | Item::Newline
+      | Item::Setting(_)
+      | Item::Unexport { .. } => {}
+    }
+  }
+
+  fn recipe(&mut self, recipe: &'run UnresolvedRecipe<'src>) {
+    if let Some(resolved) = self.module.recipes.get(recipe.name.lexeme()) {
+      self
+        .occurrences
+        .push((recipe.name.token, Symbol::Recipe(resolved)));
+    }
+
+    let global = BTreeMap::new();
+    let mut scope = BTreeMap::new();
+
+    for parameter in &recipe.parameters {
+      for expression in parameter.default.iter().chain(&parameter.value) {
+        self.expression(expression, &scope);
+      }
+      self
+        .occurrences
+        .push((parameter.name.token, Symbol::RecipeParameter(parameter)));
+      scope.insert(parameter.name.lexeme(), Symbol::RecipeParameter(parameter));
+    }
+
+    for dependency in &recipe.dependencies {
+      self.recipe_path(&dependency.recipe);
+      for argument in &dependency.arguments {
+        self.expression(&argument.expression, &scope);
+      }
+    }
+
+    // Expressions in `[arg]`, `[doc]`, and `[env]` attributes are evaluated
+    // before parameters are bound, so parameters are not in scope.
+    for attribute in &recipe.attributes {
+      match attribute {
+        Attribute::Arg {
+          help_property,
+          pattern_property,
+          ..
+        } => {
+          for (_key, expression) in help_property.iter().chain(pattern_property) {
+            self.expression(expression, &global);
+          }
+        }
+        Attribute::Cache {
+          env,
+          exclude,
+          extra,
+          inputs,
+          outputs,
+          ..
+        } => {
+          for expression in [env, exclude, extra, inputs, outputs].into_iter().flatten() {
+            self.expression(expression, &scope);
+          }
+        }
+        Attribute::Confirm(Some(expression))
+        | Attribute::Timestamp(Some(expression))
+        | Attribute::WorkingDirectory(expression) => self.expression(expression, &scope),
+        Attribute::Doc(Some(expression)) => self.expression(expression, &global),
+        Attribute::Env(key, value) => {
+          self.expression(key, &global);
+          self.expression(value, &global);
+        }
+        Attribute::Stale { sources, targets } => {
+          if let Some(sources) = sources {
+            self.expression(sources, &scope);
+          }
+          self.expression(targets, &scope);
+        }
+        _ => {}
+      }
+    }
+
+    for line in &recipe.body {
+      for fragment in &line.fragments {
+        if let Fragment::Interpolation { expression } = fragment {
+          self.expression(expression, &scope);
+        }
+      }
+    }
+  }
+
+  fn recipe_path(&mut self, path: &Namepath<'src>) -> Option<&'run Recipe<'src>> {
+    let (name, modules) = path.split_last();
+
+    let mut module = self.module;
+
+    for component in modules {
+      module = module.modules.get(component.lexeme())?;
+      self
+        .occurrences
+        .push((component.token, Symbol::Module(module)));
+    }
+
+    let recipe = module.recipes.get(name.lexeme())?;
+
+    self.occurrences.push((name.token, Symbol::Recipe(recipe)));
+
+    Some(recipe)
+  }
+}
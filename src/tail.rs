@@ -0,0 +1,37 @@
+// The end of the standard error of a recipe's commands, captured for
+// `--junit` reports. Only the last `LIMIT` bytes are retained.
+#[derive(Default)]
+pub(crate) struct Tail {
+  bytes: Vec<u8>,
+  truncated: bool,
+}
+
+impl Tail {
+  const LIMIT: usize = 8192;
+
+  pub(crate) fn new() -> Self {
+    Self::default()
+  }
+
+  pub(crate) fn push(&mut self, bytes: &[u8]) {
+    self.bytes.extend_from_slice(bytes);
+
+    if self.bytes.len() > Self::LIMIT {
+      self.bytes.drain(..self.bytes.len() - Self::LIMIT);
+      self.truncated = true;
+    }
+  }
+
+  pub(crate) fn text(&self) -> String {
+    // The first line may have been truncated, so it is removed.
+    let text = String::from_utf8_lossy(&self.bytes);
+
+    if self.truncated
+      && let Some((_first, rest)) = text.split_once('\n')
+    {
+      rest.into()
+    } else {
+      text.into()
+    }
+  }
+}
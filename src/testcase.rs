@@ -0,0 +1,10 @@
+use super::*;
+
+// A recipe run, recorded with `--junit`.
+pub(crate) struct Testcase {
+  pub(crate) duration: Duration,
+  pub(crate) failure: Option<String>,
+  pub(crate) name: String,
+  pub(crate) skipped: Option<&'static str>,
+  pub(crate) stderr: String,
+}
@@ -114,10 +114,13 @@ macro_rules! run_error {
       let search = $crate::testing::search(&config);
 
       if let Subcommand::Run{ arguments } = &config.subcommand {
-        match $crate::testing::compile(&$crate::unindent::unindent($src))
+        let src = $crate::unindent::unindent($src);
+        let justfile = $crate::testing::compile(&src);
+        match justfile
           .run(
             &config,
             &search,
+            &Cache::new(&config, &search, &justfile.settings),
             &arguments,
             &HashMap::new(),
           ).expect_err("Expected runtime error") {
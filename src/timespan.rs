@@ -0,0 +1,135 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Timespan(pub(crate) Duration);
+
+impl Timespan {
+  const UNITS: [(&str, u128); 6] = [
+    ("w", 7 * 24 * 60 * 60 * 1000),
+    ("d", 24 * 60 * 60 * 1000),
+    ("h", 60 * 60 * 1000),
+    ("m", 60 * 1000),
+    ("s", 1000),
+    ("ms", 1),
+  ];
+}
+
+impl Display for Timespan {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let mut milliseconds = self.0.as_millis();
+
+    if milliseconds == 0 {
+      return write!(f, "0ms");
+    }
+
+    for (unit, length) in Self::UNITS {
+      if milliseconds >= length {
+        write!(f, "{}{unit}", milliseconds / length)?;
+        milliseconds %= length;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+impl FromStr for Timespan {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let error =
+      || format!("invalid duration `{s}`, expected numbers followed by units ms, s, m, h, d, or w");
+
+    if s.is_empty() {
+      return Err(error());
+    }
+
+    let mut milliseconds = 0u128;
+    let mut rest = s;
+
+    while !rest.is_empty() {
+      let digits = rest.find(|c: char| !c.is_ascii_digit()).ok_or_else(error)?;
+
+      let number = rest[..digits].parse::<u64>().map_err(|_| error())?;
+
+      rest = &rest[digits..];
+
+      let letters = rest
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(rest.len());
+
+      let length = Self::UNITS
+        .iter()
+        .find(|(name, _)| *name == &rest[..letters])
+        .map(|(_, length)| *length)
+        .ok_or_else(error)?;
+
+      milliseconds = u128::from(number)
+        .checked_mul(length)
+        .and_then(|span| milliseconds.checked_add(span))
+        .ok_or_else(error)?;
+
+      rest = &rest[letters..];
+    }
+
+    Ok(Self(Duration::from_millis(
+      milliseconds.try_into().map_err(|_| error())?,
+    )))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn from_str() {
+    #[track_caller]
+    fn case(s: &str, seconds: u64) {
+      assert_eq!(
+        s.parse::<Timespan>().unwrap(),
+        Timespan(Duration::from_secs(seconds)),
+      );
+    }
+
+    case("0s", 0);
+    case("30s", 30);
+    case("10m", 600);
+    case("2h", 7200);
+    case("7d", 7 * 86400);
+    case("1w", 7 * 86400);
+    case("1h30m", 5400);
+
+    assert_eq!(
+      "1s500ms".parse::<Timespan>().unwrap(),
+      Timespan(Duration::from_millis(1500)),
+    );
+  }
+
+  #[test]
+  fn from_str_error() {
+    #[track_caller]
+    fn case(s: &str) {
+      assert_eq!(
+        s.parse::<Timespan>().unwrap_err(),
+        format!("invalid duration `{s}`, expected numbers followed by units ms, s, m, h, d, or w"),
+      );
+    }
+
+    case("");
+    case("10");
+    case("m");
+    case("10x");
+    case("1 h");
+    case("10mss");
+    case("99999999999999999999s");
+  }
+
+  #[test]
+  fn display() {
+    assert_eq!(Timespan(Duration::from_secs(600)).to_string(), "10m");
+    assert_eq!(Timespan(Duration::from_secs(5400)).to_string(), "1h30m");
+    assert_eq!(Timespan(Duration::from_millis(500)).to_string(), "500ms");
+    assert_eq!(Timespan(Duration::from_millis(1500)).to_string(), "1s500ms");
+  }
+}
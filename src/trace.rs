@@ -0,0 +1,254 @@
+use super::*;
+
+static TRACE: Mutex<Option<Trace>> = Mutex::new(None);
+
+type Key = (Modulepath, Vec<Value>);
+
+// Records recipe runs with `--trace`, and writes them in Chrome Trace Event
+// Format, for viewing in `chrome://tracing` or Perfetto. Like events, the
+// trace is global, so that spans can be recorded from the threads on which
+// recipes run.
+pub(crate) struct Trace {
+  edges: BTreeSet<(Key, Key)>,
+  path: PathBuf,
+  spans: Vec<(Span, u64)>,
+  start: Instant,
+  threads: HashMap<thread::ThreadId, (u64, String)>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TraceFile<'a> {
+  display_time_unit: &'static str,
+  trace_events: Vec<TraceEvent<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "ph")]
+enum TraceEvent<'a> {
+  #[serde(rename = "X")]
+  Complete {
+    args: SpanArgs<'a>,
+    cat: &'static str,
+    dur: f64,
+    name: String,
+    pid: u32,
+    tid: u64,
+    ts: f64,
+  },
+  #[serde(rename = "f")]
+  FlowFinish {
+    bp: &'static str,
+    cat: &'static str,
+    id: usize,
+    name: &'static str,
+    pid: u32,
+    tid: u64,
+    ts: f64,
+  },
+  #[serde(rename = "s")]
+  FlowStart {
+    cat: &'static str,
+    id: usize,
+    name: &'static str,
+    pid: u32,
+    tid: u64,
+    ts: f64,
+  },
+  #[serde(rename = "M")]
+  Metadata {
+    args: ThreadName<'a>,
+    name: &'static str,
+    pid: u32,
+    tid: u64,
+  },
+}
+
+#[derive(Serialize)]
+struct SpanArgs<'a> {
+  arguments: &'a [String],
+  cache: Option<&'static str>,
+  code: i32,
+  dependency: bool,
+}
+
+#[derive(Serialize)]
+struct ThreadName<'a> {
+  name: &'a str,
+}
+
+impl Trace {
+  pub(crate) fn install(config: &Config) {
+    *TRACE.lock().unwrap() = config.trace.as_ref().map(|path| Self {
+      edges: BTreeSet::new(),
+      path: path.clone(),
+      spans: Vec::new(),
+      start: Instant::now(),
+      threads: HashMap::new(),
+    });
+  }
+
+  // Record that `dependencies` ran before `recipe`.
+  pub(crate) fn priors(recipe: &Recipe, arguments: &[Value], dependencies: &[Fingerprint]) {
+    if let Some(trace) = TRACE.lock().unwrap().as_mut() {
+      for dependency in dependencies {
+        trace.edges.insert((
+          (dependency.recipe.clone(), dependency.arguments.clone()),
+          (recipe.recipe_path().clone(), arguments.into()),
+        ));
+      }
+    }
+  }
+
+  // Record that `dependencies` ran after `recipe`.
+  pub(crate) fn subsequents(recipe: &Recipe, arguments: &[Value], dependencies: &[Fingerprint]) {
+    if let Some(trace) = TRACE.lock().unwrap().as_mut() {
+      for dependency in dependencies {
+        trace.edges.insert((
+          (recipe.recipe_path().clone(), arguments.into()),
+          (dependency.recipe.clone(), dependency.arguments.clone()),
+        ));
+      }
+    }
+  }
+
+  // Record `span`, which ran on the current thread.
+  pub(crate) fn span(span: Span) {
+    if let Some(trace) = TRACE.lock().unwrap().as_mut() {
+      let current = thread::current();
+
+      let count = trace.threads.len();
+
+      let (thread, _name) = trace.threads.entry(current.id()).or_insert_with(|| {
+        let thread = u64::try_from(count).unwrap_or(u64::MAX);
+        let name = current
+          .name()
+          .map_or_else(|| format!("thread {thread}"), str::to_owned);
+        (thread, name)
+      });
+
+      let thread = *thread;
+
+      trace.spans.push((span, thread));
+    }
+  }
+
+  // Write the trace recorded so far. With `--watch`, this is called after each
+  // run, so the trace covers every run.
+  pub(crate) fn save() -> RunResult<'static> {
+    let trace = TRACE.lock().unwrap();
+
+    let Some(trace) = trace.as_ref() else {
+      return Ok(());
+    };
+
+    let pid = process::id();
+
+    let timestamp =
+      |instant: Instant| instant.saturating_duration_since(trace.start).as_secs_f64() * 1e6;
+
+    let mut events = Vec::new();
+
+    let mut threads = trace.threads.values().collect::<Vec<&(u64, String)>>();
+
+    threads.sort();
+
+    for (thread, name) in threads {
+      events.push(TraceEvent::Metadata {
+        args: ThreadName { name },
+        name: "thread_name",
+        pid,
+        tid: *thread,
+      });
+    }
+
+    let mut runs = BTreeMap::<(&Modulepath, &[Value]), Vec<&(Span, u64)>>::new();
+
+    for run in &trace.spans {
+      let (span, thread) = run;
+
+      runs
+        .entry((&span.recipe, &span.arguments))
+        .or_default()
+        .push(run);
+
+      events.push(TraceEvent::Complete {
+        args: SpanArgs {
+          arguments: &span.positional,
+          cache: span.cache,
+          code: span.code,
+          dependency: span.dependency,
+        },
+        cat: if span.cache == Some("hit") {
+          "recipe,cache_hit"
+        } else {
+          "recipe"
+        },
+        dur: span.duration.as_secs_f64() * 1e6,
+        name: span.recipe.to_string(),
+        pid,
+        tid: *thread,
+        ts: timestamp(span.start),
+      });
+    }
+
+    // Recipes may run more than once, for example with `--watch`, so each run
+    // of the later recipe is connected to the most recent run of the earlier
+    // recipe which finished before it started.
+    let mut id = 0;
+    for ((from, from_arguments), (to, to_arguments)) in &trace.edges {
+      let (Some(before), Some(after)) = (
+        runs.get(&(from, from_arguments.as_slice())),
+        runs.get(&(to, to_arguments.as_slice())),
+      ) else {
+        continue;
+      };
+
+      for (after, after_thread) in after {
+        let Some((before, before_thread)) = before
+          .iter()
+          .filter(|(before, _thread)| before.start + before.duration <= after.start)
+          .max_by_key(|(before, _thread)| before.start + before.duration)
+        else {
+          continue;
+        };
+
+        // Flow events are bound to the slice which encloses them, so the
+        // start of the flow is placed just before the end of the earlier run.
+        let end = timestamp(before.start + before.duration);
+
+        events.push(TraceEvent::FlowStart {
+          cat: "dependency",
+          id,
+          name: "dependency",
+          pid,
+          tid: *before_thread,
+          ts: (end - 1.0).max(timestamp(before.start)),
+        });
+
+        events.push(TraceEvent::FlowFinish {
+          bp: "e",
+          cat: "dependency",
+          id,
+          name: "dependency",
+          pid,
+          tid: *after_thread,
+          ts: timestamp(after.start),
+        });
+
+        id += 1;
+      }
+    }
+
+    let json = serde_json::to_string(&TraceFile {
+      display_time_unit: "ms",
+      trace_events: events,
+    })
+    .unwrap();
+
+    fs::write(&trace.path, json).map_err(|source| Error::FilesystemIo {
+      path: trace.path.clone(),
+      source,
+    })
+  }
+}
@@ -74,11 +74,34 @@ impl<'src> UnresolvedRecipe<'src> {
               variable_resolver.resolve_expression(expression, &empty, &mut variable_references)?;
             }
           }
+          Attribute::Stale { sources, targets } => {
+            if let Some(sources) = sources {
+              variable_resolver.resolve_expression(
+                sources,
+                &parameters,
+                &mut variable_references,
+              )?;
+            }
+            variable_resolver.resolve_expression(targets, &parameters, &mut variable_references)?;
+          }
           Attribute::Cache {
+            env,
+            exclude,
             extra,
             inputs,
             outputs,
+            ..
           } => {
+            if let Some(env) = env {
+              variable_resolver.resolve_expression(env, &parameters, &mut variable_references)?;
+            }
+            if let Some(exclude) = exclude {
+              variable_resolver.resolve_expression(
+                exclude,
+                &parameters,
+                &mut variable_references,
+              )?;
+            }
             if let Some(extra) = extra {
               variable_resolver.resolve_expression(extra, &parameters, &mut variable_references)?;
             }
@@ -113,7 +136,8 @@ impl<'src> UnresolvedRecipe<'src> {
             variable_resolver.resolve_expression(key, &empty, &mut variable_references)?;
             variable_resolver.resolve_expression(value, &empty, &mut variable_references)?;
           }
-          Attribute::Android
+          Attribute::Allow(_)
+          | Attribute::Android
           | Attribute::Confirm(None)
           | Attribute::Continue(_)
           | Attribute::Default
@@ -124,6 +148,7 @@ impl<'src> UnresolvedRecipe<'src> {
           | Attribute::Freebsd
           | Attribute::Group(_)
           | Attribute::Linux
+          | Attribute::Lock { .. }
           | Attribute::Macos
           | Attribute::Metadata(_)
           | Attribute::Netbsd
@@ -134,8 +159,10 @@ impl<'src> UnresolvedRecipe<'src> {
           | Attribute::Parallel
           | Attribute::PositionalArguments
           | Attribute::Private
+          | Attribute::Retry { .. }
           | Attribute::Script(_)
           | Attribute::Shell
+          | Attribute::Timeout(_)
           | Attribute::Timestamp(None)
           | Attribute::Unix
           | Attribute::Windows => {}
@@ -251,6 +278,7 @@ impl<'src> UnresolvedRecipe<'src> {
       dependencies,
       doc: self.doc,
       file_depth: self.file_depth,
+      finally: self.finally,
       import_offsets: self.import_offsets,
       module_path: Some(modulepath.clone()),
       name: self.name,
@@ -1,9 +1,12 @@
 use super::*;
 
-#[derive(Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, Serialize)]
-#[serde(transparent)]
+/// A list of strings, or a map from strings to strings. Maps are stored as
+/// their sorted keys, along with the value of each key, so that a map used as
+/// a list is the list of its keys.
+#[derive(Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
 pub(crate) struct Value {
   elements: Vec<String>,
+  values: Option<Box<[String]>>,
 }
 
 impl Value {
@@ -11,6 +14,25 @@ impl Value {
     Self::default()
   }
 
+  pub(crate) fn get(&self, key: &str) -> Option<&str> {
+    let values = self.values.as_ref()?;
+
+    let i = self
+      .elements
+      .binary_search_by(|element| element.as_str().cmp(key))
+      .ok()?;
+
+    Some(&values[i])
+  }
+
+  pub(crate) fn is_map(&self) -> bool {
+    self.values.is_some()
+  }
+
+  pub(crate) fn values(&self) -> Option<&[String]> {
+    self.values.as_deref()
+  }
+
   pub(crate) fn elements(&self) -> &[String] {
     &self.elements
   }
@@ -19,10 +41,6 @@ impl Value {
     self.elements.len()
   }
 
-  pub(crate) fn push(&mut self, element: &str) {
-    self.elements.push(element.into());
-  }
-
   pub(crate) fn join(&self) -> String {
     self.elements.join(" ")
   }
@@ -33,6 +51,9 @@ impl Value {
     operator: ListOperator,
     token: Token<'src>,
   ) -> RunResult<'src, Self> {
+    self.list(token)?;
+    other.list(token)?;
+
     let separator = operator.separator();
     let (a, b) = (&self.elements, &other.elements);
     match (a.len(), b.len()) {
@@ -61,6 +82,19 @@ impl Value {
     }
   }
 
+  // Check that `self` is not a map before it is used as an operand of
+  // `operator`, since maps would otherwise be treated as lists of their keys.
+  pub(crate) fn list<'src>(&self, operator: Token<'src>) -> RunResult<'src, &[String]> {
+    if self.is_map() {
+      return Err(Error::MapOperand {
+        token: Box::new(operator),
+        value: self.clone(),
+      });
+    }
+
+    Ok(&self.elements)
+  }
+
   pub(crate) fn into_elements(self) -> Vec<String> {
     self.elements
   }
@@ -80,7 +114,24 @@ impl Value {
 
 impl ColorDisplay for Value {
   fn fmt(&self, f: &mut Formatter, color: Color) -> fmt::Result {
-    if self.elements.len() == 1 {
+    if let Some(values) = &self.values {
+      write!(f, "{{")?;
+
+      for (i, (key, value)) in self.elements.iter().zip(values).enumerate() {
+        if i > 0 {
+          write!(f, ", ")?;
+        }
+
+        write!(
+          f,
+          "{}: {}",
+          Element(key).color_display(color),
+          Element(value).color_display(color),
+        )?;
+      }
+
+      write!(f, "}}")
+    } else if self.elements.len() == 1 {
       write!(f, "{}", Element(&self.elements[0]).color_display(color))
     } else {
       write!(f, "[")?;
@@ -100,9 +151,7 @@ impl ColorDisplay for Value {
 
 impl From<&String> for Value {
   fn from(element: &String) -> Self {
-    Self {
-      elements: vec![element.clone()],
-    }
+    element.clone().into()
   }
 }
 
@@ -122,25 +171,34 @@ impl From<&str> for Value {
   }
 }
 
-impl From<String> for Value {
-  fn from(element: String) -> Self {
+impl From<BTreeMap<String, String>> for Value {
+  fn from(map: BTreeMap<String, String>) -> Self {
+    let (elements, values) = map.into_iter().unzip::<_, _, _, Vec<String>>();
     Self {
-      elements: vec![element],
+      elements,
+      values: Some(values.into()),
     }
   }
 }
 
+impl From<String> for Value {
+  fn from(element: String) -> Self {
+    vec![element].into()
+  }
+}
+
 impl From<Vec<String>> for Value {
   fn from(elements: Vec<String>) -> Self {
-    Self { elements }
+    Self {
+      elements,
+      values: None,
+    }
   }
 }
 
 impl FromIterator<String> for Value {
   fn from_iter<I: IntoIterator<Item = String>>(elements: I) -> Self {
-    Self {
-      elements: elements.into_iter().collect(),
-    }
+    elements.into_iter().collect::<Vec<String>>().into()
   }
 }
 
@@ -162,6 +220,23 @@ impl IntoIterator for Value {
   }
 }
 
+impl Serialize for Value {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    if let Some(values) = &self.values {
+      let mut map = serializer.serialize_map(Some(values.len()))?;
+      for (key, value) in self.elements.iter().zip(values) {
+        map.serialize_entry(key, value)?;
+      }
+      map.end()
+    } else {
+      self.elements.serialize(serializer)
+    }
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -197,6 +272,40 @@ mod tests {
     case(&["\\", "\n"], r#"["\\", "\n"]"#);
   }
 
+  #[test]
+  fn map() {
+    let value = Value::from(
+      [("b", "1"), ("a", "2")]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect::<BTreeMap<String, String>>(),
+    );
+
+    assert!(value.is_map());
+    assert_eq!(value.elements(), ["a", "b"]);
+    assert_eq!(value.values().unwrap(), ["2", "1"]);
+    assert_eq!(value.get("a"), Some("2"));
+    assert_eq!(value.get("c"), None);
+    assert_eq!(
+      value.color_display(Color::never()).to_string(),
+      r#"{"a": "2", "b": "1"}"#,
+    );
+    assert_eq!(
+      serde_json::to_string(&value).unwrap(),
+      r#"{"a":"2","b":"1"}"#,
+    );
+
+    let empty = Value::from(BTreeMap::new());
+
+    assert!(empty.is_map());
+    assert!(!empty.is_truthy());
+    assert_eq!(empty.color_display(Color::never()).to_string(), "{}");
+    assert_ne!(empty, Value::new());
+
+    assert!(!Value::from("a").is_map());
+    assert_eq!(Value::from("a").get("a"), None);
+  }
+
   #[test]
   fn color_display() {
     assert_eq!(
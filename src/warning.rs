@@ -1,22 +1,67 @@
 use super::*;
 
 #[derive(Clone, Debug, PartialEq)]
-pub(crate) enum Warning {}
+pub(crate) enum Warning<'src> {
+  DuplicateDependency {
+    dependency: Namepath<'src>,
+    recipe: &'src str,
+  },
+  ParameterShadowsVariable {
+    parameter: Name<'src>,
+    recipe: &'src str,
+  },
+  RedundantSetting {
+    setting: Name<'src>,
+  },
+  UnusedParameter {
+    parameter: Name<'src>,
+    recipe: &'src str,
+  },
+  UnusedPrivateRecipe {
+    recipe: Name<'src>,
+  },
+  UnusedVariable {
+    variable: Name<'src>,
+  },
+}
 
-impl Warning {
-  #[allow(clippy::unused_self)]
-  fn context(&self) -> Option<&Token> {
-    None
+impl<'src> Warning<'src> {
+  pub(crate) fn context(&self) -> Option<&Token<'src>> {
+    match self {
+      Self::DuplicateDependency { dependency, .. } => Some(&dependency.last().token),
+      Self::ParameterShadowsVariable {
+        parameter: name, ..
+      }
+      | Self::RedundantSetting { setting: name }
+      | Self::UnusedParameter {
+        parameter: name, ..
+      }
+      | Self::UnusedPrivateRecipe { recipe: name }
+      | Self::UnusedVariable { variable: name } => Some(&name.token),
+    }
+  }
+
+  pub(crate) fn lint(&self) -> Lint {
+    match self {
+      Self::DuplicateDependency { .. } => Lint::DuplicateDependency,
+      Self::ParameterShadowsVariable { .. } => Lint::ParameterShadowsVariable,
+      Self::RedundantSetting { .. } => Lint::RedundantSetting,
+      Self::UnusedParameter { .. } => Lint::UnusedParameter,
+      Self::UnusedPrivateRecipe { .. } => Lint::UnusedPrivateRecipe,
+      Self::UnusedVariable { .. } => Lint::UnusedVariable,
+    }
   }
 }
 
-impl ColorDisplay for Warning {
+impl ColorDisplay for Warning<'_> {
   fn fmt(&self, f: &mut Formatter, color: Color) -> fmt::Result {
     let warning = color.warning();
     let message = color.message();
 
     write!(f, "{} {}", warning.paint("warning:"), message.prefix())?;
 
+    write!(f, "{self}")?;
+
     write!(f, "{}", message.suffix())?;
 
     if let Some(token) = self.context() {
@@ -28,14 +73,44 @@ impl ColorDisplay for Warning {
   }
 }
 
-impl Serialize for Warning {
+impl Display for Warning<'_> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::DuplicateDependency { dependency, recipe } => {
+        write!(
+          f,
+          "recipe `{recipe}` depends on `{dependency}` more than once"
+        )
+      }
+      Self::ParameterShadowsVariable { parameter, recipe } => write!(
+        f,
+        "parameter `{parameter}` of recipe `{recipe}` shadows variable `{parameter}`",
+      ),
+      Self::RedundantSetting { setting } => write!(
+        f,
+        "setting `{setting}` is set to its default value and has no effect",
+      ),
+      Self::UnusedParameter { parameter, recipe } => {
+        write!(
+          f,
+          "parameter `{parameter}` of recipe `{recipe}` is never used"
+        )
+      }
+      Self::UnusedPrivateRecipe { recipe } => write!(f, "private recipe `{recipe}` is never used"),
+      Self::UnusedVariable { variable } => write!(f, "private variable `{variable}` is never used"),
+    }
+  }
+}
+
+impl Serialize for Warning<'_> {
   fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
   where
     S: Serializer,
   {
     let mut map = serializer.serialize_map(None)?;
 
-    map.serialize_entry("message", &self.color_display(Color::never()).to_string())?;
+    map.serialize_entry("lint", &self.lint())?;
+    map.serialize_entry("message", &self.to_string())?;
 
     map.end()
   }
@@ -0,0 +1,335 @@
+use super::*;
+
+// Modification times and sizes of the files beneath each watched path.
+type Snapshot = BTreeMap<PathBuf, BTreeMap<PathBuf, Option<(SystemTime, u64)>>>;
+
+// The modification time of a directory when it was read, and its entries,
+// with whether each is a symlink.
+type Listing = (SystemTime, Vec<(PathBuf, bool)>);
+
+// Runs invocations repeatedly, re-running them when watched files change.
+// Changes are detected by polling every `--watch-interval`, so that no
+// platform-specific file notification APIs are required.
+pub(crate) struct Watch<'a> {
+  config: &'a Config,
+  listings: Mutex<HashMap<PathBuf, Listing>>,
+}
+
+impl<'a> Watch<'a> {
+  // How often to check whether a run has finished.
+  const FINISHED_INTERVAL: Duration = Duration::from_millis(50);
+
+  // Directories modified more recently than this are read again, even if
+  // their modification time has not changed, since a change made in the same
+  // tick of a coarse filesystem clock would not update it.
+  const RACY_INTERVAL: Duration = Duration::from_secs(2);
+
+  pub(crate) fn new(config: &'a Config) -> Self {
+    Self {
+      config,
+      listings: Mutex::new(HashMap::new()),
+    }
+  }
+
+  // Call `load` and then `run` with its result, repeatedly. Paths recorded in
+  // the cache during a run are watched, and if `load` fails, the error is
+  // printed and the paths recorded during the previous run are watched.
+  pub(crate) fn run<'src, T: Sync>(
+    &self,
+    mut load: impl FnMut() -> RunResult<'src, (T, Cache)>,
+    run: impl Fn(&T, &Cache) -> RunResult<'src> + Sync,
+  ) -> RunResult<'src> {
+    let mut recorded = BTreeSet::new();
+    let mut cache_dir = None;
+
+    loop {
+      SignalHandler::clear();
+
+      let (loaded, cache) = match load() {
+        Ok(loaded) => loaded,
+        Err(error) => {
+          if self.paths(&recorded).is_empty() {
+            return Err(error);
+          }
+
+          self.print(&error);
+
+          let cache_dir = cache_dir.as_deref();
+          let mut snapshot = self.snapshot(&recorded, cache_dir);
+          self.wait(&mut snapshot, &recorded, cache_dir);
+
+          continue;
+        }
+      };
+
+      let mut snapshot = self.snapshot(&BTreeSet::new(), Some(cache.path()));
+
+      let (result, changed) = thread::scope(|scope| {
+        let handle = scope.spawn(|| run(&loaded, &cache));
+
+        let mut changed = false;
+        let mut checked = Instant::now();
+
+        while !handle.is_finished() {
+          thread::sleep(Self::FINISHED_INTERVAL);
+
+          if checked.elapsed() < self.config.watch_interval {
+            continue;
+          }
+
+          checked = Instant::now();
+
+          // Running commands are only terminated once, so that finally
+          // dependencies, which run after the signal is taken, may finish.
+          if !changed && self.changed(&mut snapshot, &cache.watched(), Some(cache.path())) {
+            self.debounce(&mut snapshot, &cache.watched(), Some(cache.path()));
+            self.banner("change detected, restarting");
+            SignalHandler::terminate();
+            changed = true;
+          }
+        }
+
+        let result = handle
+          .join()
+          .map_err(|_| Error::internal("watch thread panicked"))
+          .and_then(|result| result);
+
+        (result, changed)
+      });
+
+      recorded = cache.watched();
+      cache_dir = Some(cache.path().to_owned());
+
+      if changed {
+        continue;
+      }
+
+      if self.paths(&recorded).is_empty() {
+        result?;
+        return Err(Error::WatchNoPaths);
+      }
+
+      if let Err(error) = &result {
+        if SignalHandler::caught().is_some() {
+          return result;
+        }
+
+        self.print(error);
+      }
+
+      // Paths recorded since the last check are snapshotted before waiting,
+      // so that changes made while waiting are not missed, and the run is
+      // restarted if previously snapshotted paths have already changed.
+      if self.changed(&mut snapshot, &recorded, cache_dir.as_deref()) {
+        self.debounce(&mut snapshot, &recorded, cache_dir.as_deref());
+        self.banner("change detected, restarting");
+        continue;
+      }
+
+      self.banner("waiting for changes...");
+
+      self.wait(&mut snapshot, &recorded, cache_dir.as_deref());
+    }
+  }
+
+  fn banner(&self, message: &str) {
+    if self.config.verbosity.loud() {
+      eprintln!(
+        "{}",
+        self
+          .config
+          .color
+          .stderr()
+          .banner()
+          .paint(&format!("===> {message}")),
+      );
+    }
+  }
+
+  // Check for changes to watched paths and update `snapshot`. Paths which were
+  // not previously watched, for example because they were recorded during the
+  // current run, are added without being considered changed.
+  fn changed(
+    &self,
+    snapshot: &mut Snapshot,
+    recorded: &BTreeSet<PathBuf>,
+    cache_dir: Option<&Path>,
+  ) -> bool {
+    let current = self.snapshot(recorded, cache_dir);
+
+    let changed = current
+      .iter()
+      .any(|(path, files)| snapshot.get(path).is_some_and(|previous| previous != files));
+
+    *snapshot = current;
+
+    changed
+  }
+
+  // Wait until watched paths have not changed for the debounce duration.
+  fn debounce(
+    &self,
+    snapshot: &mut Snapshot,
+    recorded: &BTreeSet<PathBuf>,
+    cache_dir: Option<&Path>,
+  ) {
+    loop {
+      thread::sleep(self.config.watch_debounce);
+
+      if !self.changed(snapshot, recorded, cache_dir) {
+        break;
+      }
+    }
+  }
+
+  fn paths(&self, recorded: &BTreeSet<PathBuf>) -> BTreeSet<PathBuf> {
+    let mut paths = recorded.clone();
+
+    for path in &self.config.watch_paths {
+      paths.insert(self.config.invocation_directory.join(path).clean());
+    }
+
+    paths
+  }
+
+  fn print(&self, error: &Error) {
+    if !self.config.verbosity.quiet() && error.print_message() {
+      eprintln!("{}", error.color_display(self.config.color.stderr()));
+    }
+  }
+
+  fn snapshot(&self, recorded: &BTreeSet<PathBuf>, cache_dir: Option<&Path>) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+
+    for path in self.paths(recorded) {
+      let mut files = BTreeMap::new();
+
+      let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+
+      self.walk(&path, canonical, cache_dir, &mut Vec::new(), &mut files);
+
+      // Record missing paths, so that their creation is detected.
+      if files.is_empty() {
+        files.insert(path.clone(), None);
+      }
+
+      snapshot.insert(path, files);
+    }
+
+    snapshot
+  }
+
+  // Wait for a change to watched paths.
+  fn wait(&self, snapshot: &mut Snapshot, recorded: &BTreeSet<PathBuf>, cache_dir: Option<&Path>) {
+    loop {
+      thread::sleep(self.config.watch_interval);
+
+      if self.changed(snapshot, recorded, cache_dir) {
+        self.debounce(snapshot, recorded, cache_dir);
+        self.banner("change detected, restarting");
+        break;
+      }
+    }
+  }
+
+  // Record the modification time and size of `path`, and if it is a
+  // directory, of the files beneath it. Like cache inputs, hidden and ignored
+  // files are not filtered, and symlinks are followed, unless they point to a
+  // directory which is being walked. Directories are only read again if their
+  // modification time has changed, since modifying a file does not change the
+  // modification time of its directory, but adding or removing one does.
+  fn walk(
+    &self,
+    path: &Path,
+    canonical: PathBuf,
+    cache_dir: Option<&Path>,
+    ancestors: &mut Vec<PathBuf>,
+    files: &mut BTreeMap<PathBuf, Option<(SystemTime, u64)>>,
+  ) {
+    if Some(path) == cache_dir {
+      return;
+    }
+
+    let Ok(metadata) = fs::metadata(path) else {
+      return;
+    };
+
+    let modified = metadata.modified().ok();
+
+    files.insert(
+      path.to_owned(),
+      modified.map(|modified| (modified, metadata.len())),
+    );
+
+    if !metadata.is_dir() {
+      return;
+    }
+
+    let cached = self
+      .listings
+      .lock()
+      .unwrap()
+      .get(path)
+      .filter(|(time, _entries)| Some(*time) == modified)
+      .map(|(_time, entries)| entries.clone());
+
+    let entries = if let Some(entries) = cached {
+      entries
+    } else {
+      let Ok(read_dir) = fs::read_dir(path) else {
+        return;
+      };
+
+      let entries = read_dir
+        .flatten()
+        .map(|entry| {
+          (
+            entry.path(),
+            entry
+              .file_type()
+              .is_ok_and(|file_type| file_type.is_symlink()),
+          )
+        })
+        .collect::<Vec<(PathBuf, bool)>>();
+
+      if let Some(modified) = modified
+        && modified
+          .elapsed()
+          .is_ok_and(|elapsed| elapsed > Self::RACY_INTERVAL)
+      {
+        self
+          .listings
+          .lock()
+          .unwrap()
+          .insert(path.to_owned(), (modified, entries.clone()));
+      }
+
+      entries
+    };
+
+    ancestors.push(canonical);
+
+    for (entry, symlink) in entries {
+      let canonical = if symlink {
+        let Ok(canonical) = entry.canonicalize() else {
+          continue;
+        };
+
+        if ancestors.contains(&canonical) {
+          continue;
+        }
+
+        canonical
+      } else {
+        ancestors
+          .last()
+          .unwrap()
+          .join(entry.file_name().unwrap_or_default())
+      };
+
+      self.walk(&entry, canonical, cache_dir, ancestors, files);
+    }
+
+    ancestors.pop();
+  }
+}
@@ -823,6 +823,58 @@ fn cache_outputs_dump() {
     .success();
 }
 
+#[test]
+fn cache_exclude_and_gitignore_dump() {
+  Test::new()
+    .justfile(
+      "
+        [cache(inputs = 'src', exclude = 'src/gen/**', gitignore)]
+        [script]
+        baz:
+          echo baz
+      ",
+    )
+    .unstable()
+    .arg("--dump")
+    .stdout(
+      "
+        [cache(exclude='src/gen/**', gitignore, inputs='src')]
+        [script]
+        baz:
+            echo baz
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn cache_env_and_no_exports_dump() {
+  Test::new()
+    .justfile(
+      "
+        set lists
+
+        [cache(no-exports, env = ['CC', 'CFLAGS'])]
+        [script]
+        baz:
+          echo baz
+      ",
+    )
+    .unstable()
+    .arg("--dump")
+    .stdout(
+      "
+        set lists
+
+        [cache(env=['CC', 'CFLAGS'], no-exports)]
+        [script]
+        baz:
+            echo baz
+      ",
+    )
+    .success();
+}
+
 #[test]
 fn unknown_keyword() {
   Test::new()
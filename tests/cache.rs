@@ -16,84 +16,58 @@ fn cache_attribute_is_unstable() {
 }
 
 #[test]
-fn cache_attribute_requires_script_recipe() {
+fn linewise_hit_skips_execution() {
   Test::new()
     .justfile(
       "
         [cache]
         foo:
-          echo bar
+          @echo bar
+          @echo baz
       ",
     )
     .unstable()
-    .stderr(
-      "
-        error: shell recipe `foo` has script recipe attribute `cache`
-         ——▶ justfile:2:1
-          │
-        2 │ foo:
-          │ ^^^
-      ",
-    )
-    .failure();
+    .stdout("bar\nbaz\n")
+    .success()
+    .test()
+    .unstable()
+    .success();
 }
 
 #[test]
-fn entry_is_created_with_recipe_name() {
-  let output = Test::new()
+fn linewise_body_change_invalidates_cache() {
+  Test::new()
     .justfile(
       "
         [cache]
-        [script]
         foo:
-          echo bar
+          @echo bar
       ",
     )
     .unstable()
     .stdout("bar\n")
-    .success();
-
-  let entries = fs::read_dir(output.tempdir.path().join(".justcache"))
-    .unwrap()
-    .map(|entry| entry.unwrap().path())
-    .collect::<Vec<PathBuf>>();
-
-  assert_eq!(entries.len(), 1);
-  assert_eq!(entries[0].extension().unwrap(), "json");
-  assert_eq!(
-    fs::read_to_string(&entries[0]).unwrap(),
-    r#"{"recipe":"foo"}"#
-  );
-}
-
-#[test]
-fn hit_skips_execution() {
-  Test::new()
+    .success()
+    .test()
     .justfile(
       "
         [cache]
-        [script]
         foo:
-          echo bar
+          @echo baz
       ",
     )
     .unstable()
-    .stdout("bar\n")
-    .success()
-    .test()
-    .unstable()
+    .stdout("baz\n")
     .success();
 }
 
 #[test]
-fn body_change_invalidates_cache() {
+fn linewise_sigil_change_invalidates_cache() {
   Test::new()
     .justfile(
       "
         [cache]
-        [script]
         foo:
-          echo bar
+          @echo bar
       ",
     )
     .unstable()
@@ -103,155 +77,212 @@ fn body_change_invalidates_cache() {
     .justfile(
       "
         [cache]
-        [script]
         foo:
-          echo baz
+          -@echo bar
       ",
     )
     .unstable()
-    .stdout("baz\n")
+    .stdout("bar\n")
     .success();
 }
 
 #[test]
-fn different_recipes_do_not_share_entries() {
+fn linewise_shell_change_invalidates_cache() {
   Test::new()
     .justfile(
       "
+        set shell := ['sh', '-c']
+
         [cache]
-        [script]
         foo:
-          echo bar
+          @echo bar
       ",
     )
+    .shell(false)
     .unstable()
-    .arg("foo")
     .stdout("bar\n")
     .success()
     .test()
     .justfile(
       "
+        set shell := ['sh', '-eu', '-c']
+
         [cache]
-        [script]
-        bob:
-          echo bar
+        foo:
+          @echo bar
       ",
     )
+    .shell(false)
     .unstable()
-    .arg("bob")
     .stdout("bar\n")
     .success();
 }
 
 #[test]
-fn positional_arguments_invalidate_cache() {
+fn linewise_environment_invalidates_cache() {
   Test::new()
     .justfile(
       "
+        export value := 'default'
+
         [cache]
-        [positional-arguments]
-        [script]
-        foo *args:
-          echo $1
+        foo:
+          @echo $value
       ",
     )
     .unstable()
-    .args(["foo", "bar"])
+    .args(["value=bar", "foo"])
     .stdout("bar\n")
     .success()
     .test()
     .unstable()
-    .args(["foo", "baz"])
+    .args(["value=bar", "foo"])
+    .success()
+    .test()
+    .unstable()
+    .args(["value=baz", "foo"])
     .stdout("baz\n")
     .success();
 }
 
 #[test]
-fn environment_invalidates_cache() {
+fn linewise_continuation_lines_are_cached() {
   Test::new()
     .justfile(
       "
-        export value := 'default'
-
         [cache]
-        [script]
         foo:
-          echo $value
+          @echo \\
+            bar
       ",
     )
     .unstable()
-    .args(["value=bar", "foo"])
     .stdout("bar\n")
     .success()
     .test()
     .unstable()
-    .args(["value=baz", "foo"])
-    .stdout("baz\n")
     .success();
 }
 
 #[test]
-fn unexported_variable_does_not_invalidate_cache() {
+fn linewise_failure_is_not_cached() {
   Test::new()
     .justfile(
       "
-        value := 'default'
-
         [cache]
-        [script]
         foo:
-          echo bar
+          @echo bar
+          @exit 1
       ",
     )
     .unstable()
-    .args(["value=bar", "foo"])
     .stdout("bar\n")
-    .success()
+    .stderr("error: recipe `foo` failed on line 4 with exit code 1\n")
+    .failure()
     .test()
     .unstable()
-    .args(["value=baz", "foo"])
+    .stdout("bar\n")
+    .stderr("error: recipe `foo` failed on line 4 with exit code 1\n")
+    .failure();
+}
+
+#[test]
+fn linewise_outputs_are_restored() {
+  let output = Test::new()
+    .justfile(
+      "
+        [cache(outputs = 'foo')]
+        bar:
+          @echo bar
+          @echo baz > foo
+      ",
+    )
+    .unstable()
+    .stdout("bar\n")
     .success();
+
+  fs::remove_file(output.tempdir.path().join("foo")).unwrap();
+
+  let output = output.test().unstable().success();
+
+  assert_eq!(
+    fs::read_to_string(output.tempdir.path().join("foo")).unwrap(),
+    "baz\n",
+  );
 }
 
 #[test]
-fn interpreter_invalidates_cache() {
+fn linewise_dry_run_skips_cache() {
   Test::new()
     .justfile(
       "
-        set script-interpreter := ['sh', '-eu']
-
-        [cache]
-        [script]
-        foo:
+        [cache(inputs = 'foo')]
+        bar:
           echo bar
       ",
     )
     .unstable()
-    .stdout("bar\n")
-    .success()
-    .test()
+    .arg("--dry-run")
+    .stderr("echo bar\n")
+    .success();
+}
+
+#[test]
+fn prints_linewise_cache_key() {
+  Test::new()
     .justfile(
       "
-        set script-interpreter := ['sh', '-u']
-
         [cache]
-        [script]
         foo:
-          echo bar
+          -@echo bar
       ",
     )
     .unstable()
+    .arg("-vv")
     .stdout("bar\n")
+    .stderr_regex(unindent(
+      r#"
+        ===> running recipe `foo`...
+        ===> cache key [[:xdigit:]]{64}:
+        \{
+          "body": \[
+            "echo bar"
+          \],
+          "dependencies": \[\],
+          "environment": \{\},
+          "executor": \{
+            "type": "command",
+            "command": "bash",
+            "arguments": \[
+              "-cu"
+            \]
+          \},
+          "extension": null,
+          "extra": null,
+          "inputs": null,
+          "positional": null,
+          "recipe": "foo",
+          "sigils": \[
+            \[
+              "infallible",
+              "quiet"
+            \]
+          \],
+          "working_directory": "\."
+        \}
+        ===> cache miss for recipe `foo`, no previous entry
+        echo bar
+      "#,
+    ))
     .success();
 }
 
 #[test]
-fn extension_invalidates_cache() {
-  Test::new()
+fn entry_is_created_with_recipe_name() {
+  let output = Test::new()
     .justfile(
       "
         [cache]
-        [extension('.aaa')]
         [script]
         foo:
           echo bar
@@ -259,12 +290,29 @@ fn extension_invalidates_cache() {
     )
     .unstable()
     .stdout("bar\n")
-    .success()
-    .test()
+    .success();
+
+  let entries = fs::read_dir(output.tempdir.path().join(".justcache"))
+    .unwrap()
+    .map(|entry| entry.unwrap().path())
+    .collect::<Vec<PathBuf>>();
+
+  assert_eq!(entries.len(), 1);
+  assert_eq!(entries[0].extension().unwrap(), "json");
+
+  let entry = serde_json::from_str::<Value>(&fs::read_to_string(&entries[0]).unwrap()).unwrap();
+
+  assert_eq!(entry["recipe"], "foo");
+  assert_eq!(entry["key"]["recipe"], "foo");
+  assert_eq!(entry["key"]["body"], json!(["echo bar"]));
+}
+
+#[test]
+fn hit_skips_execution() {
+  Test::new()
     .justfile(
       "
         [cache]
-        [extension('.bbb')]
         [script]
         foo:
           echo bar
@@ -272,438 +320,409 @@ fn extension_invalidates_cache() {
     )
     .unstable()
     .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
     .success();
 }
 
 #[test]
-fn working_directory_invalidates_cache() {
+fn body_change_invalidates_cache() {
   Test::new()
     .justfile(
       "
         [cache]
-        [working-directory('a')]
         [script]
         foo:
           echo bar
       ",
     )
     .unstable()
-    .create_dir("a")
     .stdout("bar\n")
     .success()
     .test()
     .justfile(
       "
         [cache]
-        [working-directory('b')]
         [script]
         foo:
-          echo bar
+          echo baz
       ",
     )
     .unstable()
-    .create_dir("b")
-    .stdout("bar\n")
+    .stdout("baz\n")
     .success();
 }
 
 #[test]
-fn extra_invalidates_cache() {
+fn different_recipes_do_not_share_entries() {
   Test::new()
     .justfile(
       "
-        value := 'default'
-
-        [cache(extra = value)]
+        [cache]
         [script]
         foo:
           echo bar
       ",
     )
     .unstable()
-    .args(["value=a", "foo"])
+    .arg("foo")
     .stdout("bar\n")
     .success()
     .test()
+    .justfile(
+      "
+        [cache]
+        [script]
+        bob:
+          echo bar
+      ",
+    )
     .unstable()
-    .args(["value=a", "foo"])
-    .success()
-    .test()
-    .unstable()
-    .args(["value=b", "foo"])
+    .arg("bob")
     .stdout("bar\n")
     .success();
 }
 
 #[test]
-fn input_invalidates_cache() {
+fn positional_arguments_invalidate_cache() {
   Test::new()
     .justfile(
       "
-        [cache(inputs = 'foo')]
+        [cache]
+        [positional-arguments]
         [script]
-        bar:
-          echo bar
+        foo *args:
+          echo $1
       ",
     )
     .unstable()
-    .write("foo", "a")
+    .args(["foo", "bar"])
     .stdout("bar\n")
     .success()
     .test()
     .unstable()
-    .success()
-    .test()
-    .unstable()
-    .write("foo", "b")
-    .stdout("bar\n")
+    .args(["foo", "baz"])
+    .stdout("baz\n")
     .success();
 }
 
 #[test]
-fn multiple_inputs_invalidate_cache() {
+fn environment_invalidates_cache() {
   Test::new()
     .justfile(
       "
-        set lists
+        export value := 'default'
 
-        [cache(inputs = ['foo', 'baz'])]
+        [cache]
         [script]
-        bar:
-          echo bar
+        foo:
+          echo $value
       ",
     )
     .unstable()
-    .write("foo", "a")
-    .write("baz", "a")
+    .args(["value=bar", "foo"])
     .stdout("bar\n")
     .success()
     .test()
     .unstable()
-    .write("baz", "b")
-    .stdout("bar\n")
+    .args(["value=baz", "foo"])
+    .stdout("baz\n")
     .success();
 }
 
 #[test]
-fn input_expression_evaluated_with_arguments() {
+fn env_selects_exported_variables() {
   Test::new()
     .justfile(
       "
-        [cache(inputs = file)]
+        export FOO := 'a'
+        export BAR := 'a'
+
+        [cache(env = 'FOO')]
         [script]
-        bar file:
-          echo bar
+        foo:
+          echo $FOO
       ",
     )
     .unstable()
-    .write("foo", "a")
-    .args(["bar", "foo"])
-    .stdout("bar\n")
+    .stdout("a\n")
     .success()
     .test()
     .unstable()
-    .args(["bar", "foo"])
+    .args(["BAR=b", "foo"])
     .success()
     .test()
     .unstable()
-    .write("foo", "b")
-    .args(["bar", "foo"])
-    .stdout("bar\n")
+    .args(["FOO=b", "foo"])
+    .stdout("b\n")
     .success();
 }
 
 #[test]
-fn symlink_to_file_is_followed() {
+fn env_includes_process_environment() {
   Test::new()
     .justfile(
       "
-        [cache(inputs = 'link')]
+        [cache(env = 'CC')]
         [script]
-        bar:
-          echo bar
+        foo:
+          echo $CC
       ",
     )
+    .env("CC", "gcc")
     .unstable()
-    .write("foo", "a")
-    .symlink("foo", "link")
-    .stdout("bar\n")
+    .stdout("gcc\n")
     .success()
     .test()
+    .env("CC", "gcc")
     .unstable()
-    .success();
-}
-
-#[test]
-fn missing_input_is_an_error() {
-  Test::new()
-    .justfile(
+    .success()
+    .test()
+    .env("CC", "clang")
+    .unstable()
+    .arg("--explain-cache")
+    .stdout("clang\n")
+    .stderr(
       "
-        [cache(inputs = 'foo')]
-        [script]
-        bar:
-          echo bar
+        ===> cache miss for recipe `foo`:
+          environment variable `CC` changed from `\"gcc\"` to `\"clang\"`
       ",
     )
-    .unstable()
-    .stderr_regex(r"error: cache input does not exist: `.*foo`\n")
-    .failure();
+    .success();
 }
 
 #[test]
-fn directory_input_is_an_error() {
+fn env_unset_variable_is_null() {
   Test::new()
     .justfile(
       "
-        [cache(inputs = 'foo')]
+        [cache(env = 'JUST_TEST_UNSET')]
         [script]
-        bar:
+        foo:
           echo bar
       ",
     )
     .unstable()
-    .create_dir("foo")
-    .stderr_regex(r"error: cache input is directory: `.*foo`\n")
-    .failure();
+    .arg("-vv")
+    .stdout("bar\n")
+    .stderr_regex(r#"(?s).*"environment": \{\n    "JUST_TEST_UNSET": null\n  \},.*"#)
+    .success();
 }
 
 #[test]
-fn symlink_to_directory_is_an_error() {
+fn env_exports_take_precedence_over_process_environment() {
   Test::new()
     .justfile(
       "
-        [cache(inputs = 'link')]
+        export CC := 'gcc'
+
+        [cache(env = 'CC')]
         [script]
-        bar:
-          echo bar
+        foo:
+          echo $CC
       ",
     )
+    .env("CC", "clang")
     .unstable()
-    .create_dir("foo")
-    .symlink("foo", "link")
-    .stderr_regex(r"error: cache input is directory: `.*link`\n")
-    .failure();
+    .stdout("gcc\n")
+    .success()
+    .test()
+    .env("CC", "tcc")
+    .unstable()
+    .success();
 }
 
 #[test]
-fn dry_run_skips_input_checking() {
+fn env_expression_evaluated_with_arguments() {
   Test::new()
     .justfile(
       "
-        [cache(inputs = 'foo')]
+        [cache(env = name)]
         [script]
-        bar:
+        foo name:
           echo bar
       ",
     )
+    .env("FOO", "a")
     .unstable()
-    .arg("--dry-run")
-    .stderr("echo bar\n")
+    .args(["foo", "FOO"])
+    .stdout("bar\n")
+    .success()
+    .test()
+    .env("FOO", "b")
+    .unstable()
+    .args(["foo", "FOO"])
+    .stdout("bar\n")
     .success();
 }
 
 #[test]
-fn missing_output_invalidates_cache() {
-  let output = Test::new()
+fn no_exports_excludes_exported_variables() {
+  Test::new()
     .justfile(
       "
-        [cache(outputs = 'foo')]
+        export FOO := 'a'
+
+        [cache(no-exports)]
         [script]
-        bar:
-          echo bar
-          touch foo
+        foo:
+          echo $FOO
       ",
     )
     .unstable()
-    .stdout("bar\n")
+    .stdout("a\n")
     .success()
     .test()
     .unstable()
+    .args(["FOO=b", "foo"])
     .success();
-
-  fs::remove_file(output.tempdir.path().join("foo")).unwrap();
-
-  output.test().unstable().stdout("bar\n").success();
 }
 
 #[test]
-fn output_expression_evaluated_with_arguments() {
-  let output = Test::new()
+fn no_exports_with_env_uses_process_environment() {
+  Test::new()
     .justfile(
       "
-        [cache(outputs = file)]
+        export CC := 'gcc'
+
+        [cache(env = 'CC', no-exports)]
         [script]
-        bar file:
+        foo:
           echo bar
-          touch {{file}}
       ",
     )
+    .env("CC", "clang")
     .unstable()
-    .args(["bar", "foo"])
     .stdout("bar\n")
     .success()
     .test()
+    .env("CC", "clang")
     .unstable()
-    .args(["bar", "foo"])
-    .success();
-
-  fs::remove_file(output.tempdir.path().join("foo")).unwrap();
-
-  output
+    .args(["CC=tcc", "foo"])
+    .success()
     .test()
+    .env("CC", "icc")
     .unstable()
-    .args(["bar", "foo"])
     .stdout("bar\n")
     .success();
 }
 
 #[test]
-fn multiple_outputs() {
-  let output = Test::new()
+fn no_exports_takes_no_value() {
+  Test::new()
     .justfile(
       "
-        set lists
-
-        [cache(outputs = ['foo', 'baz'])]
+        [cache(no-exports = 'true')]
         [script]
-        bar:
+        foo:
           echo bar
-          touch foo baz
       ",
     )
-    .unstable()
-    .stdout("bar\n")
-    .success()
-    .test()
-    .unstable()
-    .success();
-
-  fs::remove_file(output.tempdir.path().join("baz")).unwrap();
-
-  output.test().unstable().stdout("bar\n").success();
+    .stderr(
+      "
+        error: attribute key `no-exports` takes no value
+         ——▶ justfile:1:8
+          │
+        1 │ [cache(no-exports = 'true')]
+          │        ^^^^^^^^^^
+      ",
+    )
+    .failure();
 }
 
 #[test]
-fn output_directory_is_allowed() {
+fn unexported_variable_does_not_invalidate_cache() {
   Test::new()
     .justfile(
       "
-        [cache(outputs = 'foo')]
+        value := 'default'
+
+        [cache]
         [script]
-        bar:
+        foo:
           echo bar
-          mkdir -p foo
       ",
     )
     .unstable()
+    .args(["value=bar", "foo"])
     .stdout("bar\n")
     .success()
     .test()
     .unstable()
+    .args(["value=baz", "foo"])
     .success();
 }
 
 #[test]
-fn outputs_resolve_against_working_directory() {
-  let output = Test::new()
+fn interpreter_invalidates_cache() {
+  Test::new()
     .justfile(
       "
-        [cache(outputs = 'foo')]
-        [working-directory('sub')]
+        set script-interpreter := ['sh', '-eu']
+
+        [cache]
         [script]
-        bar:
+        foo:
           echo bar
-          touch foo
       ",
     )
     .unstable()
-    .create_dir("sub")
     .stdout("bar\n")
     .success()
     .test()
-    .unstable()
-    .success();
-
-  fs::remove_file(output.tempdir.path().join("sub/foo")).unwrap();
-
-  output.test().unstable().stdout("bar\n").success();
-}
-
-#[cfg(unix)]
-#[test]
-fn dangling_symlink_output_invalidates_cache() {
-  let output = Test::new()
     .justfile(
       "
-        [cache(outputs = 'link')]
+        set script-interpreter := ['sh', '-u']
+
+        [cache]
         [script]
-        bar:
+        foo:
           echo bar
-          touch foo
-          ln -sf foo link
       ",
     )
     .unstable()
     .stdout("bar\n")
-    .success()
-    .test()
-    .unstable()
     .success();
-
-  fs::remove_file(output.tempdir.path().join("foo")).unwrap();
-
-  output.test().unstable().stdout("bar\n").success();
 }
 
 #[test]
-fn missing_output_after_run_is_an_error() {
-  let output = Test::new()
+fn extension_invalidates_cache() {
+  Test::new()
     .justfile(
       "
-        [cache(outputs = 'foo')]
+        [cache]
+        [extension('.aaa')]
         [script]
-        bar:
+        foo:
           echo bar
       ",
     )
     .unstable()
     .stdout("bar\n")
-    .stderr_regex(r"error: recipe `bar` failed to create cache output `foo`\n")
-    .failure();
-
-  let entries = fs::read_dir(output.tempdir.path().join(".justcache"))
-    .unwrap()
-    .map(|entry| entry.unwrap().path())
-    .collect::<Vec<PathBuf>>();
-
-  assert_eq!(entries.len(), 1);
-  assert_eq!(fs::read_to_string(&entries[0]).unwrap(), "");
-}
-
-#[test]
-fn dry_run_skips_output_checking() {
-  Test::new()
+    .success()
+    .test()
     .justfile(
       "
-        [cache(outputs = 'foo')]
+        [cache]
+        [extension('.bbb')]
         [script]
-        bar:
+        foo:
           echo bar
       ",
     )
     .unstable()
-    .arg("--dry-run")
-    .stderr("echo bar\n")
+    .stdout("bar\n")
     .success();
 }
 
 #[test]
-fn current_directory_invalidates_cache() {
+fn working_directory_invalidates_cache() {
   Test::new()
     .justfile(
       "
         [cache]
-        [no-cd]
+        [working-directory('a')]
         [script]
         foo:
           echo bar
@@ -711,94 +730,1744 @@ fn current_directory_invalidates_cache() {
     )
     .unstable()
     .create_dir("a")
-    .create_dir("b")
-    .current_dir("a")
     .stdout("bar\n")
     .success()
     .test()
+    .justfile(
+      "
+        [cache]
+        [working-directory('b')]
+        [script]
+        foo:
+          echo bar
+      ",
+    )
     .unstable()
-    .current_dir("b")
+    .create_dir("b")
     .stdout("bar\n")
     .success();
 }
 
 #[test]
-fn clean_removes_cache_directory() {
-  let output = Test::new()
+fn extra_invalidates_cache() {
+  Test::new()
     .justfile(
       "
-        [cache]
+        value := 'default'
+
+        [cache(extra = value)]
         [script]
         foo:
           echo bar
       ",
     )
     .unstable()
+    .args(["value=a", "foo"])
     .stdout("bar\n")
-    .success();
-
-  let cache = output.tempdir.path().join(".justcache");
-  assert!(cache.exists());
-
-  let output = output
+    .success()
     .test()
     .unstable()
-    .arg("--clean")
-    .stderr("removed 1 cache entry\n")
+    .args(["value=a", "foo"])
+    .success()
+    .test()
+    .unstable()
+    .args(["value=b", "foo"])
+    .stdout("bar\n")
     .success();
-
-  assert!(!output.tempdir.path().join(".justcache").exists());
 }
 
 #[test]
-fn clean_removes_entries_but_leaves_unexpected_entries() {
-  let output = Test::new()
+fn input_invalidates_cache() {
+  Test::new()
     .justfile(
       "
-        [cache]
+        [cache(inputs = 'foo')]
         [script]
-        foo:
+        bar:
           echo bar
       ",
     )
     .unstable()
+    .write("foo", "a")
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .success()
+    .test()
+    .unstable()
+    .write("foo", "b")
+    .stdout("bar\n")
+    .success();
+}
+
+#[test]
+fn multiple_inputs_invalidate_cache() {
+  Test::new()
+    .justfile(
+      "
+        set lists
+
+        [cache(inputs = ['foo', 'baz'])]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .unstable()
+    .write("foo", "a")
+    .write("baz", "a")
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .write("baz", "b")
+    .stdout("bar\n")
+    .success();
+}
+
+#[test]
+fn input_expression_evaluated_with_arguments() {
+  Test::new()
+    .justfile(
+      "
+        [cache(inputs = file)]
+        [script]
+        bar file:
+          echo bar
+      ",
+    )
+    .unstable()
+    .write("foo", "a")
+    .args(["bar", "foo"])
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .args(["bar", "foo"])
+    .success()
+    .test()
+    .unstable()
+    .write("foo", "b")
+    .args(["bar", "foo"])
+    .stdout("bar\n")
+    .success();
+}
+
+#[test]
+fn symlink_to_file_is_followed() {
+  Test::new()
+    .justfile(
+      "
+        [cache(inputs = 'link')]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .unstable()
+    .write("foo", "a")
+    .symlink("foo", "link")
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .success();
+}
+
+#[test]
+fn missing_input_is_an_error() {
+  Test::new()
+    .justfile(
+      "
+        [cache(inputs = 'foo')]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .unstable()
+    .stderr_regex(r"error: cache input does not exist: `.*foo`\n")
+    .failure();
+}
+
+#[test]
+fn directory_input_invalidates_cache() {
+  Test::new()
+    .justfile(
+      "
+        [cache(inputs = 'foo')]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .unstable()
+    .write("foo/a", "a")
+    .write("foo/b/c", "c")
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .success()
+    .test()
+    .unstable()
+    .write("foo/b/c", "d")
+    .stdout("bar\n")
+    .success();
+}
+
+#[test]
+fn new_file_in_directory_input_invalidates_cache() {
+  Test::new()
+    .justfile(
+      "
+        [cache(inputs = 'foo')]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .unstable()
+    .write("foo/a", "a")
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .write("foo/b", "b")
+    .stdout("bar\n")
+    .success();
+}
+
+#[test]
+fn symlink_to_directory_is_followed() {
+  Test::new()
+    .justfile(
+      "
+        [cache(inputs = 'link')]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .unstable()
+    .write("foo/a", "a")
+    .symlink("foo", "link")
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .success()
+    .test()
+    .unstable()
+    .write("foo/a", "b")
+    .stdout("bar\n")
+    .success();
+}
+
+#[test]
+fn directory_input_keys_are_file_paths() {
+  Test::new()
+    .justfile(
+      "
+        [cache(inputs = 'foo/')]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .unstable()
+    .write("foo/b/c", "c")
+    .write("foo/a", "a")
+    .arg("-vv")
+    .stdout("bar\n")
+    .stderr_regex(r#"(?s).*"inputs": \{\n    "foo/a": \[.*\],\n    "foo/b/c": \[.*\]\n  \},.*"#)
+    .success();
+}
+
+#[test]
+fn current_directory_input_skips_cache_directory() {
+  Test::new()
+    .justfile(
+      "
+        [cache(inputs = '.')]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .unstable()
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .success();
+}
+
+#[test]
+fn glob_input_invalidates_cache() {
+  Test::new()
+    .justfile(
+      "
+        [cache(inputs = 'src/**/*.rs')]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .unstable()
+    .write("src/main.rs", "a")
+    .write("src/foo/lib.rs", "a")
+    .write("src/foo/README.md", "a")
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .write("src/foo/README.md", "b")
+    .success()
+    .test()
+    .unstable()
+    .write("src/foo/lib.rs", "b")
+    .stdout("bar\n")
+    .success();
+}
+
+#[test]
+fn glob_input_single_star_does_not_cross_directories() {
+  Test::new()
+    .justfile(
+      "
+        [cache(inputs = '*.txt')]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .unstable()
+    .write("a.txt", "a")
+    .write("foo/b.txt", "a")
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .write("foo/b.txt", "b")
+    .success()
+    .test()
+    .unstable()
+    .write("a.txt", "b")
+    .stdout("bar\n")
+    .success();
+}
+
+#[test]
+fn glob_input_matching_nothing_is_an_error() {
+  Test::new()
+    .justfile(
+      "
+        [cache(inputs = 'src/**/*.rs')]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .unstable()
+    .stderr("error: cache input pattern `src/**/*.rs` matches no files\n")
+    .failure();
+}
+
+#[test]
+fn glob_input_with_no_unexcluded_matches_is_an_error() {
+  Test::new()
+    .justfile(
+      "
+        [cache(inputs = 'src/*.rs', exclude = 'src/foo.rs')]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .unstable()
+    .write("src/foo.rs", "")
+    .stderr("error: cache input pattern `src/*.rs` matches no files\n")
+    .failure();
+}
+
+#[test]
+fn invalid_glob_input_is_an_error() {
+  Test::new()
+    .justfile(
+      "
+        [cache(inputs = 'src/[')]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .unstable()
+    .stderr_regex(r"error: invalid cache input pattern `\[`: .*\n")
+    .failure();
+}
+
+#[test]
+fn excluded_inputs_do_not_invalidate_cache() {
+  Test::new()
+    .justfile(
+      "
+        set lists
+
+        [cache(inputs = 'foo', exclude = ['foo/gen/**', '**/*.log'])]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .unstable()
+    .write("foo/a", "a")
+    .write("foo/gen/b", "a")
+    .write("foo/c.log", "a")
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .write("foo/gen/b", "b")
+    .write("foo/c.log", "b")
+    .write("foo/gen/d", "d")
+    .success()
+    .test()
+    .unstable()
+    .write("foo/a", "b")
+    .stdout("bar\n")
+    .success();
+}
+
+#[test]
+fn gitignored_inputs_do_not_invalidate_cache() {
+  Test::new()
+    .justfile(
+      "
+        [cache(inputs = 'foo', gitignore)]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .unstable()
+    .write(".gitignore", "*.log\n")
+    .write("foo/a", "a")
+    .write("foo/b.log", "a")
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .write("foo/b.log", "b")
+    .success()
+    .test()
+    .unstable()
+    .write("foo/a", "b")
+    .stdout("bar\n")
+    .success();
+}
+
+#[test]
+fn gitignore_is_not_honored_by_default() {
+  Test::new()
+    .justfile(
+      "
+        [cache(inputs = 'foo')]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .unstable()
+    .write(".gitignore", "*.log\n")
+    .write("foo/b.log", "a")
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .write("foo/b.log", "b")
+    .stdout("bar\n")
+    .success();
+}
+
+#[test]
+fn gitignore_takes_no_value() {
+  Test::new()
+    .justfile(
+      "
+        [cache(inputs = 'foo', gitignore = 'true')]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .unstable()
+    .stderr(
+      "
+        error: attribute key `gitignore` takes no value
+         ——▶ justfile:1:24
+          │
+        1 │ [cache(inputs = 'foo', gitignore = 'true')]
+          │                        ^^^^^^^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn dry_run_skips_input_checking() {
+  Test::new()
+    .justfile(
+      "
+        [cache(inputs = 'foo')]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .unstable()
+    .arg("--dry-run")
+    .stderr("echo bar\n")
+    .success();
+}
+
+#[test]
+fn missing_output_is_restored() {
+  let output = Test::new()
+    .justfile(
+      "
+        [cache(outputs = 'foo')]
+        [script]
+        bar:
+          echo bar
+          echo baz > foo
+      ",
+    )
+    .unstable()
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .success();
+
+  fs::remove_file(output.tempdir.path().join("foo")).unwrap();
+
+  let output = output.test().unstable().success();
+
+  assert_eq!(
+    fs::read_to_string(output.tempdir.path().join("foo")).unwrap(),
+    "baz\n",
+  );
+}
+
+#[test]
+fn modified_output_is_restored() {
+  let output = Test::new()
+    .justfile(
+      "
+        [cache(outputs = 'foo')]
+        [script]
+        bar:
+          echo bar
+          echo baz > foo
+      ",
+    )
+    .unstable()
+    .stdout("bar\n")
+    .success();
+
+  fs::write(output.tempdir.path().join("foo"), "edited").unwrap();
+
+  let output = output.test().unstable().success();
+
+  assert_eq!(
+    fs::read_to_string(output.tempdir.path().join("foo")).unwrap(),
+    "baz\n",
+  );
+}
+
+#[test]
+fn switching_configurations_restores_outputs() {
+  let output = Test::new()
+    .justfile(
+      "
+        value := 'a'
+
+        [cache(extra = value, outputs = 'foo')]
+        [script]
+        bar:
+          echo {{value}}
+          echo {{value}} > foo
+      ",
+    )
+    .unstable()
+    .args(["value=a", "bar"])
+    .stdout("a\n")
+    .success()
+    .test()
+    .unstable()
+    .args(["value=b", "bar"])
+    .stdout("b\n")
+    .success()
+    .test()
+    .unstable()
+    .args(["value=a", "bar"])
+    .success();
+
+  assert_eq!(
+    fs::read_to_string(output.tempdir.path().join("foo")).unwrap(),
+    "a\n",
+  );
+}
+
+#[test]
+fn missing_blob_invalidates_cache() {
+  let output = Test::new()
+    .justfile(
+      "
+        [cache(outputs = 'foo')]
+        [script]
+        bar:
+          echo bar
+          echo baz > foo
+      ",
+    )
+    .unstable()
+    .stdout("bar\n")
+    .success();
+
+  fs::remove_dir_all(output.tempdir.path().join(".justcache/blobs")).unwrap();
+  fs::remove_file(output.tempdir.path().join("foo")).unwrap();
+
+  output.test().unstable().stdout("bar\n").success();
+}
+
+#[test]
+fn directory_output_is_restored() {
+  let output = Test::new()
+    .justfile(
+      "
+        [cache(outputs = 'foo')]
+        [script]
+        bar:
+          echo bar
+          mkdir -p foo/baz
+          echo a > foo/a
+          echo b > foo/baz/b
+      ",
+    )
+    .unstable()
+    .stdout("bar\n")
+    .success();
+
+  fs::remove_dir_all(output.tempdir.path().join("foo")).unwrap();
+
+  let output = output.test().unstable().success();
+
+  assert_eq!(
+    fs::read_to_string(output.tempdir.path().join("foo/a")).unwrap(),
+    "a\n",
+  );
+  assert_eq!(
+    fs::read_to_string(output.tempdir.path().join("foo/baz/b")).unwrap(),
+    "b\n",
+  );
+}
+
+#[test]
+fn restore_prints_verbose_message() {
+  let output = Test::new()
+    .justfile(
+      "
+        [cache(outputs = 'foo')]
+        [script]
+        bar:
+          touch foo
+      ",
+    )
+    .unstable()
+    .success();
+
+  fs::remove_file(output.tempdir.path().join("foo")).unwrap();
+
+  output
+    .test()
+    .unstable()
+    .arg("--verbose")
+    .stderr_regex(
+      "===> running recipe `bar`...
+===> restored cache output `.*foo`
+===> cache hit, skipping invocation
+",
+    )
+    .success();
+}
+
+#[test]
+fn entry_records_output_hashes() {
+  let output = Test::new()
+    .justfile(
+      "
+        [cache(outputs = 'foo')]
+        [script]
+        bar:
+          touch foo
+      ",
+    )
+    .unstable()
+    .success();
+
+  let entries = fs::read_dir(output.tempdir.path().join(".justcache"))
+    .unwrap()
+    .map(|entry| entry.unwrap().path())
+    .filter(|path| path.is_file())
+    .collect::<Vec<PathBuf>>();
+
+  assert_eq!(entries.len(), 1);
+
+  let entry = serde_json::from_str::<Value>(&fs::read_to_string(&entries[0]).unwrap()).unwrap();
+
+  assert_eq!(entry["recipe"], "bar");
+  assert!(entry["outputs"]["foo"].is_array());
+}
+
+#[test]
+fn output_expression_evaluated_with_arguments() {
+  let output = Test::new()
+    .justfile(
+      "
+        [cache(outputs = file)]
+        [script]
+        bar file:
+          echo bar
+          touch {{file}}
+      ",
+    )
+    .unstable()
+    .args(["bar", "foo"])
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .args(["bar", "foo"])
+    .success();
+
+  fs::remove_file(output.tempdir.path().join("foo")).unwrap();
+
+  let output = output.test().unstable().args(["bar", "foo"]).success();
+
+  assert!(output.tempdir.path().join("foo").exists());
+}
+
+#[test]
+fn multiple_outputs() {
+  let output = Test::new()
+    .justfile(
+      "
+        set lists
+
+        [cache(outputs = ['foo', 'baz'])]
+        [script]
+        bar:
+          echo bar
+          touch foo baz
+      ",
+    )
+    .unstable()
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .success();
+
+  fs::remove_file(output.tempdir.path().join("baz")).unwrap();
+
+  let output = output.test().unstable().success();
+
+  assert!(output.tempdir.path().join("baz").exists());
+}
+
+#[test]
+fn output_directory_is_allowed() {
+  Test::new()
+    .justfile(
+      "
+        [cache(outputs = 'foo')]
+        [script]
+        bar:
+          echo bar
+          mkdir -p foo
+      ",
+    )
+    .unstable()
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .success();
+}
+
+#[test]
+fn outputs_resolve_against_working_directory() {
+  let output = Test::new()
+    .justfile(
+      "
+        [cache(outputs = 'foo')]
+        [working-directory('sub')]
+        [script]
+        bar:
+          echo bar
+          touch foo
+      ",
+    )
+    .unstable()
+    .create_dir("sub")
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .success();
+
+  fs::remove_file(output.tempdir.path().join("sub/foo")).unwrap();
+
+  let output = output.test().unstable().success();
+
+  assert!(output.tempdir.path().join("sub/foo").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn dangling_symlink_output_is_restored() {
+  let output = Test::new()
+    .justfile(
+      "
+        [cache(outputs = 'link')]
+        [script]
+        bar:
+          echo bar
+          touch foo
+          ln -sf foo link
+      ",
+    )
+    .unstable()
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .success();
+
+  fs::remove_file(output.tempdir.path().join("foo")).unwrap();
+
+  let output = output.test().unstable().success();
+
+  assert!(output.tempdir.path().join("link").is_file());
+}
+
+#[test]
+fn missing_output_after_run_is_an_error() {
+  let output = Test::new()
+    .justfile(
+      "
+        [cache(outputs = 'foo')]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .unstable()
+    .stdout("bar\n")
+    .stderr_regex(r"error: recipe `bar` failed to create cache output `foo`\n")
+    .failure();
+
+  let entries = fs::read_dir(output.tempdir.path().join(".justcache"))
+    .unwrap()
+    .map(|entry| entry.unwrap().path())
+    .collect::<Vec<PathBuf>>();
+
+  assert_eq!(entries.len(), 1);
+  assert_eq!(fs::read_to_string(&entries[0]).unwrap(), "");
+}
+
+#[test]
+fn dry_run_skips_output_checking() {
+  Test::new()
+    .justfile(
+      "
+        [cache(outputs = 'foo')]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .unstable()
+    .arg("--dry-run")
+    .stderr("echo bar\n")
+    .success();
+}
+
+#[test]
+fn current_directory_invalidates_cache() {
+  Test::new()
+    .justfile(
+      "
+        [cache]
+        [no-cd]
+        [script]
+        foo:
+          echo bar
+      ",
+    )
+    .unstable()
+    .create_dir("a")
+    .create_dir("b")
+    .current_dir("a")
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .current_dir("b")
+    .stdout("bar\n")
+    .success();
+}
+
+#[test]
+fn cache_directory_is_shared_between_checkouts() {
+  let justfile = "
+    [cache(inputs = 'input', outputs = 'output')]
+    [script]
+    foo:
+      echo bar
+      cp input output
+  ";
+
+  let output = Test::new()
+    .write("a/justfile", justfile)
+    .write("a/input", "baz")
+    .write("b/justfile", justfile)
+    .write("b/input", "baz")
+    .current_dir("a")
+    .args(["--cache-dir", "../cache"])
+    .unstable()
+    .stdout("bar\n")
+    .success()
+    .test()
+    .current_dir("b")
+    .args(["--cache-dir", "../cache"])
+    .unstable()
+    .success();
+
+  assert_eq!(
+    fs::read_to_string(output.tempdir.path().join("b/output")).unwrap(),
+    "baz",
+  );
+  assert!(!output.tempdir.path().join("a/.justcache").exists());
+  assert!(!output.tempdir.path().join("b/.justcache").exists());
+}
+
+#[test]
+fn cache_directory_environment_variable() {
+  let justfile = "
+    [cache]
+    [script]
+    foo:
+      echo bar
+  ";
+
+  let cache = tempdir();
+
+  let output = Test::new()
+    .write("a/justfile", justfile)
+    .write("b/justfile", justfile)
+    .current_dir("a")
+    .env("JUST_CACHE_DIR", cache.path().to_str().unwrap())
+    .unstable()
+    .stdout("bar\n")
+    .success()
+    .test()
+    .current_dir("b")
+    .env("JUST_CACHE_DIR", cache.path().to_str().unwrap())
+    .unstable()
+    .success();
+
+  assert!(!output.tempdir.path().join("a/.justcache").exists());
+}
+
+#[test]
+fn cache_directory_setting_is_relative_to_justfile() {
+  let output = Test::new()
+    .justfile(
+      "
+        set cache-directory := 'cache'
+
+        [cache]
+        [script]
+        foo:
+          echo bar
+      ",
+    )
+    .create_dir("sub")
+    .current_dir("sub")
+    .unstable()
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .success();
+
+  assert!(output.tempdir.path().join("cache").is_dir());
+  assert!(!output.tempdir.path().join(".justcache").exists());
+  assert!(!output.tempdir.path().join("sub/cache").exists());
+}
+
+#[test]
+fn cache_directory_flag_overrides_setting() {
+  let output = Test::new()
+    .justfile(
+      "
+        set cache-directory := 'setting'
+
+        [cache]
+        [script]
+        foo:
+          echo bar
+      ",
+    )
+    .args(["--cache-dir", "flag"])
+    .unstable()
+    .stdout("bar\n")
+    .success();
+
+  assert!(output.tempdir.path().join("flag").is_dir());
+  assert!(!output.tempdir.path().join("setting").exists());
+}
+
+#[test]
+fn working_directory_is_relative_in_shared_cache() {
+  let justfile = "
+    [cache]
+    [working-directory('sub')]
+    [script]
+    foo:
+      echo bar
+  ";
+
+  Test::new()
+    .write("a/justfile", justfile)
+    .create_dir("a/sub")
+    .write("b/justfile", justfile)
+    .create_dir("b/sub")
+    .current_dir("a")
+    .args(["--cache-dir", "../cache"])
+    .unstable()
+    .stdout("bar\n")
+    .success()
+    .test()
+    .current_dir("b")
+    .args(["--cache-dir", "../cache"])
+    .unstable()
+    .success();
+}
+
+#[test]
+fn cache_is_hit_after_project_is_moved() {
+  let justfile = "
+    export ROOT := justfile_directory()
+
+    [cache]
+    foo:
+      @test -d {{ justfile_directory() }}
+      @test -d $ROOT
+      @echo bar
+  ";
+
+  let output = Test::new()
+    .write("a/justfile", justfile)
+    .current_dir("a")
+    .unstable()
+    .stdout("bar\n")
+    .success();
+
+  fs::rename(
+    output.tempdir.path().join("a"),
+    output.tempdir.path().join("b"),
+  )
+  .unwrap();
+
+  output.test().current_dir("b").unstable().success();
+}
+
+#[test]
+fn clean_removes_custom_cache_directory() {
+  let output = Test::new()
+    .justfile(
+      "
+        set cache-directory := 'cache'
+
+        [cache]
+        [script]
+        foo:
+          echo bar
+      ",
+    )
+    .unstable()
+    .stdout("bar\n")
+    .success();
+
+  assert!(output.tempdir.path().join("cache").is_dir());
+
+  let output = output
+    .test()
+    .unstable()
+    .arg("--clean")
+    .stderr("removed 1 cache entry\n")
+    .success();
+
+  assert!(!output.tempdir.path().join("cache").exists());
+}
+
+#[test]
+fn entry_records_arguments_and_timestamps() {
+  let output = Test::new()
+    .justfile(
+      "
+        [cache]
+        [script]
+        foo bar:
+          echo {{ bar }}
+      ",
+    )
+    .args(["foo", "baz"])
+    .unstable()
+    .stdout("baz\n")
+    .success();
+
+  let path = fs::read_dir(output.tempdir.path().join(".justcache"))
+    .unwrap()
+    .map(|entry| entry.unwrap().path())
+    .find(|path| path.is_file())
+    .unwrap();
+
+  let before = serde_json::from_str::<Value>(&fs::read_to_string(&path).unwrap()).unwrap();
+
+  assert_eq!(before["arguments"], json!(["baz"]));
+  assert!(before["created"].as_u64().unwrap() > 0);
+  assert_eq!(before["last_hit"], Value::Null);
+
+  let _output = output.test().args(["foo", "baz"]).unstable().success();
+
+  let after = serde_json::from_str::<Value>(&fs::read_to_string(path).unwrap()).unwrap();
+
+  assert_eq!(after["created"], before["created"]);
+  assert!(after["last_hit"].as_u64().unwrap() >= before["created"].as_u64().unwrap());
+}
+
+#[test]
+fn cache_list() {
+  Test::new()
+    .justfile(
+      "
+        [cache]
+        [script]
+        foo bar:
+          echo {{ bar }}
+      ",
+    )
+    .args(["foo", "a"])
+    .unstable()
+    .stdout("a\n")
+    .success()
+    .test()
+    .args(["foo", "b"])
+    .unstable()
+    .stdout("b\n")
+    .success()
+    .test()
+    .args(["foo", "a"])
+    .unstable()
+    .success()
+    .test()
+    .arg("--cache-list")
+    .unstable()
+    .stdout_regex(
+      "foo a: created \\d{4}-\\d{2}-\\d{2} \\d{2}:\\d{2}:\\d{2}, \
+       last hit \\d{4}-\\d{2}-\\d{2} \\d{2}:\\d{2}:\\d{2}\n\
+       foo b: created \\d{4}-\\d{2}-\\d{2} \\d{2}:\\d{2}:\\d{2}, last hit never\n",
+    )
+    .success();
+}
+
+#[test]
+fn cache_list_without_cache_directory() {
+  Test::new()
+    .justfile(
+      "
+        [cache]
+        [script]
+        foo:
+          echo bar
+      ",
+    )
+    .arg("--cache-list")
+    .unstable()
+    .success();
+}
+
+#[test]
+fn cache_list_takes_no_arguments() {
+  Test::new()
+    .justfile("foo:")
+    .args(["--cache-list", "foo"])
+    .stderr("error: `--cache_list` used with unexpected argument: `foo`\n")
+    .failure();
+}
+
+#[test]
+fn cache_stats() {
+  Test::new()
+    .justfile(
+      "
+        [cache]
+        [script]
+        foo bar:
+          echo {{ bar }}
+
+        [cache(outputs = 'out')]
+        [script]
+        baz:
+          echo baz > out
+      ",
+    )
+    .args(["foo", "a"])
+    .unstable()
+    .stdout("a\n")
+    .success()
+    .test()
+    .args(["foo", "b"])
+    .unstable()
+    .stdout("b\n")
+    .success()
+    .test()
+    .arg("baz")
+    .unstable()
+    .success()
+    .test()
+    .arg("--cache-stats")
+    .unstable()
+    .stdout_regex(
+      "cache directory: .*\\.justcache\n\
+       entries: 3\n\
+       size: \\d+ B\n  \
+       baz: 1 entry, \\d+ B\n  \
+       foo: 2 entries, \\d+ B\n",
+    )
+    .success();
+}
+
+#[test]
+fn cache_stats_without_cache_directory() {
+  Test::new()
+    .justfile("foo:")
+    .arg("--cache-stats")
+    .unstable()
+    .stdout_regex("cache directory: .*\nentries: 0\nsize: 0 B\n")
+    .success();
+}
+
+#[test]
+fn cache_prune_older_than() {
+  let output = Test::new()
+    .justfile(
+      "
+        [cache]
+        [script]
+        foo bar:
+          echo {{ bar }}
+      ",
+    )
+    .args(["foo", "a"])
+    .unstable()
+    .stdout("a\n")
+    .success()
+    .test()
+    .args(["foo", "b"])
+    .unstable()
+    .stdout("b\n")
+    .success();
+
+  let cache = output.tempdir.path().join(".justcache");
+
+  for entry in fs::read_dir(&cache).unwrap() {
+    let path = entry.unwrap().path();
+    let mut json = serde_json::from_str::<Value>(&fs::read_to_string(&path).unwrap()).unwrap();
+    if json["arguments"] == json!(["a"]) {
+      json["created"] = json!(1);
+      fs::write(&path, json.to_string()).unwrap();
+    }
+  }
+
+  output
+    .test()
+    .args(["--cache-prune", "--older-than", "1d"])
+    .unstable()
+    .stderr_regex("removed 1 cache entry and 0 blobs, freeing \\d+ B\n")
+    .success()
+    .test()
+    .args(["foo", "a"])
+    .unstable()
+    .stdout("a\n")
+    .success()
+    .test()
+    .args(["foo", "b"])
+    .unstable()
+    .success();
+}
+
+#[test]
+fn cache_prune_max_size_evicts_least_recently_used() {
+  let output = Test::new()
+    .justfile(
+      "
+        [cache(outputs = 'out-' + bar)]
+        [script]
+        foo bar:
+          echo {{ bar }}
+          head -c 4096 /dev/zero | tr '\\0' {{ bar }} > out-{{ bar }}
+      ",
+    )
+    .args(["foo", "a"])
+    .unstable()
+    .stdout("a\n")
+    .success()
+    .test()
+    .args(["foo", "b"])
+    .unstable()
+    .stdout("b\n")
+    .success();
+
+  let cache = output.tempdir.path().join(".justcache");
+
+  for entry in fs::read_dir(&cache).unwrap() {
+    let path = entry.unwrap().path();
+    if path.is_file() {
+      let mut json = serde_json::from_str::<Value>(&fs::read_to_string(&path).unwrap()).unwrap();
+      if json["arguments"] == json!(["a"]) {
+        json["created"] = json!(1);
+        fs::write(&path, json.to_string()).unwrap();
+      }
+    }
+  }
+
+  output
+    .test()
+    .args(["--cache-prune", "--max-size", "6K"])
+    .unstable()
+    .stderr_regex("removed 1 cache entry and 1 blob, freeing \\d+\\.\\d KiB\n")
+    .success()
+    .test()
+    .args(["foo", "b"])
+    .unstable()
+    .success()
+    .test()
+    .args(["foo", "a"])
+    .unstable()
+    .stdout("a\n")
+    .success();
+}
+
+#[test]
+fn cache_prune_removes_unreferenced_blobs() {
+  let output = Test::new()
+    .justfile(
+      "
+        [cache(outputs = 'out')]
+        [script]
+        foo:
+          echo foo > out
+      ",
+    )
+    .unstable()
+    .success();
+
+  fs::write(
+    output.tempdir.path().join(".justcache/blobs/stray"),
+    "stray",
+  )
+  .unwrap();
+
+  let output = output
+    .test()
+    .arg("--cache-prune")
+    .unstable()
+    .stderr("removed 0 cache entries and 1 blob, freeing 5 B\n")
+    .success();
+
+  assert!(
+    !output
+      .tempdir
+      .path()
+      .join(".justcache/blobs/stray")
+      .exists()
+  );
+
+  fs::remove_file(output.tempdir.path().join("out")).unwrap();
+
+  let output = output.test().unstable().success();
+
+  assert_eq!(
+    fs::read_to_string(output.tempdir.path().join("out")).unwrap(),
+    "foo\n"
+  );
+}
+
+#[test]
+fn older_than_requires_cache_prune() {
+  Test::new()
+    .justfile("foo:")
+    .args(["--older-than", "1d"])
+    .stderr_regex("error: the following required arguments were not provided:\n  --cache-prune\n.*")
+    .status(2);
+}
+
+#[test]
+fn older_than_invalid_duration() {
+  Test::new()
+    .justfile("foo:")
+    .args(["--cache-prune", "--older-than", "1x"])
+    .stderr_regex(
+      "error: invalid value '1x' for '--older-than <DURATION>': invalid duration `1x`, \
+       expected numbers followed by units ms, s, m, h, d, or w\n.*",
+    )
+    .status(2);
+}
+
+#[test]
+fn clean_removes_cache_directory() {
+  let output = Test::new()
+    .justfile(
+      "
+        [cache]
+        [script]
+        foo:
+          echo bar
+      ",
+    )
+    .unstable()
+    .stdout("bar\n")
+    .success();
+
+  let cache = output.tempdir.path().join(".justcache");
+  assert!(cache.exists());
+
+  let output = output
+    .test()
+    .unstable()
+    .arg("--clean")
+    .stderr("removed 1 cache entry\n")
+    .success();
+
+  assert!(!output.tempdir.path().join(".justcache").exists());
+}
+
+#[test]
+fn clean_removes_blobs() {
+  let output = Test::new()
+    .justfile(
+      "
+        [cache(outputs = 'foo')]
+        [script]
+        bar:
+          touch foo
+      ",
+    )
+    .unstable()
+    .success();
+
+  assert!(output.tempdir.path().join(".justcache/blobs").exists());
+
+  let output = output
+    .test()
+    .unstable()
+    .arg("--clean")
+    .stderr("removed 1 cache entry\n")
+    .success();
+
+  assert!(!output.tempdir.path().join(".justcache").exists());
+}
+
+#[test]
+fn clean_removes_entries_but_leaves_unexpected_entries() {
+  let output = Test::new()
+    .justfile(
+      "
+        [cache]
+        [script]
+        foo:
+          echo bar
+      ",
+    )
+    .unstable()
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .write(".justcache/foo", "bar")
+    .arg("--clean")
+    .stderr("removed 1 cache entry\n")
+    .success();
+
+  let cache = output.tempdir.path().join(".justcache");
+
+  let entries = fs::read_dir(&cache)
+    .unwrap()
+    .map(|entry| entry.unwrap().path())
+    .collect::<Vec<PathBuf>>();
+
+  assert_eq!(entries, &[cache.join("foo")]);
+}
+
+#[test]
+fn clean_succeeds_without_cache_directory() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo:
+          echo bar
+      ",
+    )
+    .arg("--clean")
+    .stderr("recipe cache not found\n")
+    .success();
+
+  assert!(!output.tempdir.path().join(".justcache").exists());
+}
+
+#[test]
+fn clean_quiet_suppresses_count() {
+  Test::new()
+    .justfile(
+      "
+        [cache]
+        [script]
+        foo:
+          echo bar
+      ",
+    )
+    .unstable()
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .args(["--quiet", "--clean"])
+    .success()
+    .test()
+    .unstable()
+    .args(["--quiet", "--clean"])
+    .success();
+}
+
+#[test]
+fn clean_reports_plural_count() {
+  Test::new()
+    .justfile(
+      "
+        [cache]
+        [script]
+        foo:
+          echo foo
+
+        [cache]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .unstable()
+    .arg("foo")
+    .stdout("foo\n")
+    .success()
+    .test()
+    .unstable()
+    .arg("bar")
     .stdout("bar\n")
     .success()
     .test()
     .unstable()
-    .write(".justcache/foo", "bar")
     .arg("--clean")
+    .stderr("removed 2 cache entries\n")
+    .success();
+}
+
+#[test]
+fn clean_path_removes_subtree() {
+  let output = Test::new()
+    .justfile(
+      "
+        mod foo
+
+        [cache]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .write(
+      "foo.just",
+      "
+        [cache]
+        [script]
+        baz:
+          echo baz
+      ",
+    )
+    .unstable()
+    .arg("bar")
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .args(["foo", "baz"])
+    .stdout("baz\n")
+    .success()
+    .test()
+    .unstable()
+    .args(["--clean", "foo"])
+    .stderr("removed 1 cache entry\n")
+    .success();
+
+  let cache = output.tempdir.path().join(".justcache");
+
+  let entries = fs::read_dir(&cache)
+    .unwrap()
+    .map(|entry| {
+      serde_json::from_str::<Value>(&fs::read_to_string(entry.unwrap().path()).unwrap()).unwrap()
+        ["recipe"]
+        .clone()
+    })
+    .collect::<Vec<Value>>();
+
+  assert_eq!(entries, &[json!("bar")]);
+}
+
+#[test]
+fn clean_path_removes_exact_recipe() {
+  let output = Test::new()
+    .justfile(
+      "
+        mod foo
+
+        [cache]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .write(
+      "foo.just",
+      "
+        [cache]
+        [script]
+        baz:
+          echo baz
+      ",
+    )
+    .unstable()
+    .arg("bar")
+    .stdout("bar\n")
+    .success()
+    .test()
+    .unstable()
+    .args(["foo", "baz"])
+    .stdout("baz\n")
+    .success()
+    .test()
+    .unstable()
+    .args(["--clean", "foo::baz"])
     .stderr("removed 1 cache entry\n")
     .success();
 
-  let cache = output.tempdir.path().join(".justcache");
+  let cache = output.tempdir.path().join(".justcache");
+
+  let entries = fs::read_dir(&cache)
+    .unwrap()
+    .map(|entry| {
+      serde_json::from_str::<Value>(&fs::read_to_string(entry.unwrap().path()).unwrap()).unwrap()
+        ["recipe"]
+        .clone()
+    })
+    .collect::<Vec<Value>>();
+
+  assert_eq!(entries, &[json!("bar")]);
+}
+
+#[test]
+fn clean_path_removes_empty_entries() {
+  let output = Test::new()
+    .justfile(
+      "
+        [cache(outputs = 'foo')]
+        [script]
+        bar:
+          echo bar
+      ",
+    )
+    .unstable()
+    .stdout("bar\n")
+    .stderr_regex(r"error: recipe `bar` failed to create cache output `foo`\n")
+    .failure();
 
-  let entries = fs::read_dir(&cache)
+  let entries = fs::read_dir(output.tempdir.path().join(".justcache"))
     .unwrap()
     .map(|entry| entry.unwrap().path())
     .collect::<Vec<PathBuf>>();
 
-  assert_eq!(entries, &[cache.join("foo")]);
+  assert_eq!(entries.len(), 1);
+  assert_eq!(fs::read_to_string(&entries[0]).unwrap(), "");
+
+  let output = output
+    .test()
+    .unstable()
+    .args(["--clean", "bar"])
+    .stderr("removed 1 cache entry\n")
+    .success();
+
+  assert!(!output.tempdir.path().join(".justcache").exists());
 }
 
 #[test]
-fn clean_succeeds_without_cache_directory() {
+fn cache_save_truncates_stale_entry() {
   let output = Test::new()
     .justfile(
       "
-        foo:
-          echo bar
+        [cache(outputs = 'foo')]
+        [script]
+        bar:
+          touch foo
       ",
     )
-    .arg("--clean")
-    .stderr("recipe cache not found\n")
+    .unstable()
     .success();
 
-  assert!(!output.tempdir.path().join(".justcache").exists());
+  let entry = fs::read_dir(output.tempdir.path().join(".justcache"))
+    .unwrap()
+    .map(|entry| entry.unwrap().path())
+    .find(|path| path.is_file())
+    .unwrap();
+
+  fs::remove_file(output.tempdir.path().join("foo")).unwrap();
+  fs::write(&entry, "x".repeat(100)).unwrap();
+
+  let _output = output.test().unstable().success();
+
+  let json = fs::read_to_string(entry).unwrap();
+
+  assert!(json.starts_with(r#"{"created":"#));
+  assert!(json.contains(r#""outputs":{"foo":["#));
+  assert!(json.ends_with(r#"]},"recipe":"bar"}"#));
 }
 
 #[test]
-fn clean_quiet_suppresses_count() {
+fn hit_prints_verbose_message() {
   Test::new()
     .justfile(
       "
@@ -813,99 +2482,275 @@ fn clean_quiet_suppresses_count() {
     .success()
     .test()
     .unstable()
-    .args(["--quiet", "--clean"])
+    .arg("--verbose")
+    .stderr(
+      "
+        ===> running recipe `foo`...
+        ===> cache hit, skipping invocation
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn no_cache_reruns_on_hit() {
+  let output = Test::new()
+    .justfile(
+      "
+        [cache]
+        [script]
+        foo:
+          echo bar
+      ",
+    )
+    .unstable()
+    .stdout("bar\n")
     .success()
     .test()
     .unstable()
-    .args(["--quiet", "--clean"])
+    .args(["--no-cache", "--verbose"])
+    .stdout("bar\n")
+    .stderr("===> running recipe `foo`...\n")
     .success();
+
+  let entries = fs::read_dir(output.tempdir.path().join(".justcache"))
+    .unwrap()
+    .map(|entry| {
+      serde_json::from_str::<Value>(&fs::read_to_string(entry.unwrap().path()).unwrap()).unwrap()
+        ["recipe"]
+        .clone()
+    })
+    .collect::<Vec<Value>>();
+
+  assert_eq!(entries, &[json!("foo")]);
 }
 
 #[test]
-fn clean_reports_plural_count() {
+fn no_cache_does_not_write_cache_entries() {
+  let output = Test::new()
+    .justfile(
+      "
+        [cache]
+        [script]
+        foo:
+          echo bar
+      ",
+    )
+    .unstable()
+    .arg("--no-cache")
+    .stdout("bar\n")
+    .success();
+
+  assert!(!output.tempdir.path().join(".justcache").exists());
+}
+
+#[test]
+fn explain_cache_without_previous_entry() {
   Test::new()
     .justfile(
       "
         [cache]
         [script]
         foo:
-          echo foo
+          echo bar
+      ",
+    )
+    .unstable()
+    .arg("--explain-cache")
+    .stdout("bar\n")
+    .stderr("===> cache miss for recipe `foo`, no previous entry\n")
+    .success();
+}
 
+#[test]
+fn explain_cache_is_silent_on_hit() {
+  Test::new()
+    .justfile(
+      "
         [cache]
         [script]
-        bar:
+        foo:
           echo bar
       ",
     )
     .unstable()
-    .arg("foo")
-    .stdout("foo\n")
+    .stdout("bar\n")
     .success()
     .test()
     .unstable()
-    .arg("bar")
-    .stdout("bar\n")
+    .arg("--explain-cache")
+    .success();
+}
+
+#[test]
+fn explain_cache_body_change() {
+  Test::new()
+    .justfile(
+      "
+        [cache]
+        foo:
+          @echo bar
+          @echo baz
+      ",
+    )
+    .unstable()
+    .stdout("bar\nbaz\n")
     .success()
     .test()
+    .justfile(
+      "
+        [cache]
+        foo:
+          @echo bar
+          @echo bob
+      ",
+    )
     .unstable()
-    .arg("--clean")
-    .stderr("removed 2 cache entries\n")
+    .arg("--explain-cache")
+    .stdout("bar\nbob\n")
+    .stderr(
+      "
+        ===> cache miss for recipe `foo`:
+          body changed:
+             echo bar
+            -echo baz
+            +echo bob
+      ",
+    )
     .success();
 }
 
 #[test]
-fn clean_path_removes_subtree() {
-  let output = Test::new()
+fn explain_cache_input_change() {
+  Test::new()
     .justfile(
       "
-        mod foo
+        set lists
 
-        [cache]
+        [cache(inputs = ['a', 'b', 'c'])]
         [script]
-        bar:
+        foo:
           echo bar
       ",
     )
-    .write(
-      "foo.just",
+    .write("a", "a")
+    .write("b", "b")
+    .write("c", "c")
+    .unstable()
+    .stdout("bar\n")
+    .success()
+    .test()
+    .justfile(
+      "
+        set lists
+
+        [cache(inputs = ['a', 'b', 'd'])]
+        [script]
+        foo:
+          echo bar
+      ",
+    )
+    .write("b", "x")
+    .write("d", "d")
+    .unstable()
+    .arg("--explain-cache")
+    .stdout("bar\n")
+    .stderr(
+      "
+        ===> cache miss for recipe `foo`:
+          input `b` changed
+          input `c` removed
+          input `d` added
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn explain_cache_environment_change() {
+  Test::new()
+    .justfile(
       "
+        export FOO := 'a'
+
         [cache]
         [script]
-        baz:
-          echo baz
+        foo:
+          echo bar
       ",
     )
     .unstable()
-    .arg("bar")
     .stdout("bar\n")
     .success()
     .test()
+    .justfile(
+      "
+        export FOO := 'b'
+        export BAR := 'c'
+
+        [cache]
+        [script]
+        foo:
+          echo bar
+      ",
+    )
     .unstable()
-    .args(["foo", "baz"])
-    .stdout("baz\n")
+    .arg("--explain-cache")
+    .stdout("bar\n")
+    .stderr(
+      "
+        ===> cache miss for recipe `foo`:
+          environment variable `BAR` added
+          environment variable `FOO` changed from `\"a\"` to `\"b\"`
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn explain_cache_field_change() {
+  Test::new()
+    .justfile(
+      "
+        [cache(extra = 'a')]
+        [script]
+        foo:
+          echo bar
+      ",
+    )
+    .unstable()
+    .stdout("bar\n")
     .success()
     .test()
+    .justfile(
+      "
+        [cache(extra = 'b')]
+        [script]
+        foo:
+          echo bar
+      ",
+    )
     .unstable()
-    .args(["--clean", "foo"])
-    .stderr("removed 1 cache entry\n")
+    .arg("--explain-cache")
+    .stdout("bar\n")
+    .stderr(
+      "
+        ===> cache miss for recipe `foo`:
+          `extra` changed from `[\"a\"]` to `[\"b\"]`
+      ",
+    )
     .success();
-
-  let cache = output.tempdir.path().join(".justcache");
-
-  let entries = fs::read_dir(&cache)
-    .unwrap()
-    .map(|entry| fs::read_to_string(entry.unwrap().path()).unwrap())
-    .collect::<Vec<String>>();
-
-  assert_eq!(entries, &[r#"{"recipe":"bar"}"#]);
 }
 
 #[test]
-fn clean_path_removes_exact_recipe() {
-  let output = Test::new()
+fn explain_cache_ignores_other_recipes() {
+  Test::new()
     .justfile(
       "
-        mod foo
+        [cache]
+        [script]
+        foo:
+          echo foo
 
         [cache]
         [script]
@@ -913,175 +2758,190 @@ fn clean_path_removes_exact_recipe() {
           echo bar
       ",
     )
-    .write(
-      "foo.just",
+    .arg("foo")
+    .unstable()
+    .stdout("foo\n")
+    .success()
+    .test()
+    .args(["--explain-cache", "bar"])
+    .unstable()
+    .stdout("bar\n")
+    .stderr("===> cache miss for recipe `bar`, no previous entry\n")
+    .success();
+}
+
+#[test]
+fn explain_cache_compares_entries_with_same_arguments() {
+  Test::new()
+    .justfile(
       "
         [cache]
-        [script]
-        baz:
-          echo baz
+        foo bar:
+          @echo {{ bar }}
       ",
     )
+    .args(["foo", "a"])
     .unstable()
-    .arg("bar")
-    .stdout("bar\n")
+    .stdout("a\n")
     .success()
     .test()
+    .args(["foo", "b"])
     .unstable()
-    .args(["foo", "baz"])
-    .stdout("baz\n")
+    .stdout("b\n")
     .success()
     .test()
+    .justfile(
+      "
+        [cache]
+        foo bar:
+          @echo {{ bar }}
+          @echo c
+      ",
+    )
+    .args(["--explain-cache", "foo", "a"])
     .unstable()
-    .args(["--clean", "foo::baz"])
-    .stderr("removed 1 cache entry\n")
+    .stdout("a\nc\n")
+    .stderr(
+      r#"
+        ===> cache miss for recipe `foo`:
+          body changed:
+             echo a
+            +echo c
+          `sigils` changed from `[["quiet"]]` to `[["quiet"],["quiet"]]`
+      "#,
+    )
     .success();
-
-  let cache = output.tempdir.path().join(".justcache");
-
-  let entries = fs::read_dir(&cache)
-    .unwrap()
-    .map(|entry| fs::read_to_string(entry.unwrap().path()).unwrap())
-    .collect::<Vec<String>>();
-
-  assert_eq!(entries, &[r#"{"recipe":"bar"}"#]);
 }
 
 #[test]
-fn clean_path_removes_empty_entries() {
+fn explain_cache_ignores_hits_when_finding_previous_entry() {
   let output = Test::new()
     .justfile(
       "
-        [cache(outputs = 'foo')]
-        [script]
-        bar:
-          echo bar
+        [cache]
+        foo:
+          @echo bar
       ",
     )
     .unstable()
     .stdout("bar\n")
-    .stderr_regex(r"error: recipe `bar` failed to create cache output `foo`\n")
-    .failure();
-
-  let entries = fs::read_dir(output.tempdir.path().join(".justcache"))
-    .unwrap()
-    .map(|entry| entry.unwrap().path())
-    .collect::<Vec<PathBuf>>();
-
-  assert_eq!(entries.len(), 1);
-  assert_eq!(fs::read_to_string(&entries[0]).unwrap(), "");
-
-  let output = output
+    .success()
     .test()
-    .unstable()
-    .args(["--clean", "bar"])
-    .stderr("removed 1 cache entry\n")
-    .success();
-
-  assert!(!output.tempdir.path().join(".justcache").exists());
-}
-
-#[test]
-fn cache_save_truncates_stale_entry() {
-  let output = Test::new()
     .justfile(
       "
-        [cache(outputs = 'foo')]
-        [script]
-        bar:
-          touch foo
+        [cache]
+        foo:
+          @echo baz
       ",
     )
     .unstable()
+    .stdout("baz\n")
     .success();
 
-  let entry = fs::read_dir(output.tempdir.path().join(".justcache"))
-    .unwrap()
-    .next()
-    .unwrap()
-    .unwrap()
-    .path();
-
-  fs::remove_file(output.tempdir.path().join("foo")).unwrap();
-  fs::write(&entry, "x".repeat(100)).unwrap();
-
-  let _output = output.test().unstable().success();
+  let cache = output.tempdir.path().join(".justcache");
 
-  assert_eq!(fs::read_to_string(entry).unwrap(), r#"{"recipe":"bar"}"#);
-}
+  for entry in fs::read_dir(&cache).unwrap() {
+    let path = entry.unwrap().path();
+    if path.extension().is_none_or(|extension| extension != "json") {
+      continue;
+    }
+    let mut json = serde_json::from_str::<Value>(&fs::read_to_string(&path).unwrap()).unwrap();
+    if json["key"]["body"] == json!(["echo bar"]) {
+      json["created"] = json!(1);
+      fs::write(&path, json.to_string()).unwrap();
+    }
+  }
 
-#[test]
-fn hit_prints_verbose_message() {
-  Test::new()
+  output
+    .test()
     .justfile(
       "
         [cache]
-        [script]
         foo:
-          echo bar
+          @echo bar
       ",
     )
     .unstable()
-    .stdout("bar\n")
     .success()
     .test()
+    .justfile(
+      "
+        [cache]
+        foo:
+          @echo bob
+      ",
+    )
+    .arg("--explain-cache")
     .unstable()
-    .arg("--verbose")
+    .stdout("bob\n")
     .stderr(
       "
-        ===> running recipe `foo`...
-        ===> cache hit, skipping invocation
+        ===> cache miss for recipe `foo`:
+          body changed:
+            -echo baz
+            +echo bob
       ",
     )
     .success();
 }
 
 #[test]
-fn no_cache_reruns_on_hit() {
+fn explain_cache_outputs_not_restored() {
   let output = Test::new()
     .justfile(
       "
-        [cache]
+        [cache(outputs = 'foo')]
         [script]
-        foo:
-          echo bar
+        bar:
+          echo bar > foo
       ",
     )
     .unstable()
-    .stdout("bar\n")
-    .success()
-    .test()
-    .unstable()
-    .args(["--no-cache", "--verbose"])
-    .stdout("bar\n")
-    .stderr("===> running recipe `foo`...\n")
     .success();
 
-  let entries = fs::read_dir(output.tempdir.path().join(".justcache"))
-    .unwrap()
-    .map(|entry| fs::read_to_string(entry.unwrap().path()).unwrap())
-    .collect::<Vec<String>>();
+  fs::remove_dir_all(output.tempdir.path().join(".justcache/blobs")).unwrap();
+  fs::remove_file(output.tempdir.path().join("foo")).unwrap();
 
-  assert_eq!(entries, &[r#"{"recipe":"foo"}"#]);
+  output
+    .test()
+    .unstable()
+    .arg("--explain-cache")
+    .stderr("===> cache miss for recipe `bar`, outputs could not be restored\n")
+    .success();
 }
 
 #[test]
-fn no_cache_does_not_write_cache_entries() {
-  let output = Test::new()
+fn verbose_explains_cache_miss() {
+  Test::new()
     .justfile(
       "
-        [cache]
+        [cache(extra = 'a')]
         [script]
         foo:
           echo bar
       ",
     )
     .unstable()
-    .arg("--no-cache")
     .stdout("bar\n")
+    .success()
+    .test()
+    .justfile(
+      "
+        [cache(extra = 'b')]
+        [script]
+        foo:
+          echo bar
+      ",
+    )
+    .unstable()
+    .arg("-vv")
+    .stdout("bar\n")
+    .stderr_regex(
+      r#"(?s).*===> cache miss for recipe `foo`:
+  `extra` changed from `\["a"\]` to `\["b"\]`
+.*"#,
+    )
     .success();
-
-  assert!(!output.tempdir.path().join(".justcache").exists());
 }
 
 #[test]
@@ -1106,6 +2966,7 @@ fn prints_cache_key() {
           "body": \[
             .*
           \],
+          "dependencies": \[\],
           "environment": \{\},
           "executor": \{
             "type": "command",
@@ -1119,8 +2980,10 @@ fn prints_cache_key() {
           "inputs": null,
           "positional": null,
           "recipe": "foo",
-          "working_directory": ".*"
+          "sigils": null,
+          "working_directory": "\."
         \}
+        ===> cache miss for recipe `foo`, no previous entry
 
 
 
@@ -1179,6 +3042,30 @@ fn cache_inputs_variables_are_resolved() {
     .failure();
 }
 
+#[test]
+fn cache_exclude_variables_are_resolved() {
+  Test::new()
+    .justfile(
+      "
+        [cache(exclude = undefined)]
+        [script('sh')]
+        foo:
+          echo bar
+      ",
+    )
+    .unstable()
+    .stderr(
+      "
+        error: variable `undefined` not defined
+         ——▶ justfile:1:18
+          │
+        1 │ [cache(exclude = undefined)]
+          │                  ^^^^^^^^^
+      ",
+    )
+    .failure();
+}
+
 #[test]
 fn cache_outputs_variables_are_resolved() {
   Test::new()
@@ -1202,3 +3089,170 @@ fn cache_outputs_variables_are_resolved() {
     )
     .failure();
 }
+
+#[test]
+fn dependency_change_invalidates_dependent() {
+  Test::new()
+    .justfile(
+      "
+        [cache(inputs = 'src')]
+        a:
+          @echo a
+
+        [cache]
+        b: a
+          @echo b
+      ",
+    )
+    .write("src", "x")
+    .unstable()
+    .args(["b"])
+    .stdout("a\nb\n")
+    .success()
+    .test()
+    .unstable()
+    .args(["b"])
+    .success()
+    .test()
+    .write("src", "y")
+    .unstable()
+    .args(["b"])
+    .stdout("a\nb\n")
+    .success();
+}
+
+#[test]
+fn dependency_change_invalidates_dependent_transitively() {
+  Test::new()
+    .justfile(
+      "
+        [cache(inputs = 'src')]
+        a:
+          @echo a
+
+        b: a
+          @echo b
+
+        [cache]
+        c: b
+          @echo c
+      ",
+    )
+    .write("src", "x")
+    .unstable()
+    .args(["c"])
+    .stdout("a\nb\nc\n")
+    .success()
+    .test()
+    .unstable()
+    .args(["c"])
+    .stdout("b\n")
+    .success()
+    .test()
+    .write("src", "y")
+    .unstable()
+    .args(["c"])
+    .stdout("a\nb\nc\n")
+    .success();
+}
+
+#[test]
+fn dependency_arguments_are_part_of_dependent_key() {
+  Test::new()
+    .justfile(
+      "
+        a x:
+          @echo a {{ x }}
+
+        [cache]
+        b x: (a x)
+          @echo b
+      ",
+    )
+    .unstable()
+    .args(["b", "foo"])
+    .stdout("a foo\nb\n")
+    .success()
+    .test()
+    .unstable()
+    .args(["b", "foo"])
+    .stdout("a foo\n")
+    .success()
+    .test()
+    .unstable()
+    .args(["b", "bar"])
+    .stdout("a bar\nb\n")
+    .success();
+}
+
+#[test]
+fn explain_cache_dependency_change() {
+  Test::new()
+    .justfile(
+      "
+        [cache(inputs = 'src')]
+        a:
+          @echo a
+
+        [cache]
+        b: a
+          @echo b
+      ",
+    )
+    .write("src", "x")
+    .unstable()
+    .args(["b"])
+    .stdout("a\nb\n")
+    .success()
+    .test()
+    .write("src", "y")
+    .unstable()
+    .args(["--explain-cache", "b"])
+    .stdout("a\nb\n")
+    .stderr(
+      "
+        ===> cache miss for recipe `a`:
+          input `src` changed
+        ===> cache miss for recipe `b`:
+          dependency `a` changed
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn explain_cache_dependency_added() {
+  Test::new()
+    .justfile(
+      "
+        [cache]
+        b:
+          @echo b
+      ",
+    )
+    .unstable()
+    .args(["b"])
+    .stdout("b\n")
+    .success()
+    .test()
+    .justfile(
+      "
+        a x:
+          @echo a
+
+        [cache]
+        b: (a 'foo')
+          @echo b
+      ",
+    )
+    .unstable()
+    .args(["--explain-cache", "b"])
+    .stdout("a\nb\n")
+    .stderr(
+      "
+        ===> cache miss for recipe `b`:
+          dependency `a foo` added
+      ",
+    )
+    .success();
+}
@@ -0,0 +1,300 @@
+use super::*;
+
+#[test]
+fn disabled_by_default() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          echo foo
+      ",
+    )
+    .stdout("foo\n")
+    .stderr("echo foo\n")
+    .success();
+}
+
+#[test]
+fn github() {
+  Test::new()
+    .justfile(
+      "
+        foo: bar
+          echo foo
+
+        bar:
+          @echo bar
+      ",
+    )
+    .args(["--ci-format", "github"])
+    .stdout("bar\nfoo\n")
+    .stderr("::group::bar\n::endgroup::\n::group::foo\necho foo\n::endgroup::\n")
+    .success();
+}
+
+#[test]
+fn github_grouped_output() {
+  Test::new()
+    .justfile(
+      "
+        [parallel]
+        foo: a b
+
+        a:
+          @echo a1 >&2
+          @sleep 0.2
+          @echo a2 >&2
+
+        b:
+          @sleep 0.1
+          @echo b1 >&2
+      ",
+    )
+    .args(["--ci-format", "github", "--output", "grouped"])
+    .stderr("::group::b\nb1\n::endgroup::\n::group::a\na1\na2\n::endgroup::\n::group::foo\n::endgroup::\n")
+    .success();
+}
+
+#[test]
+fn github_prefixed_output() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          @printf foo >&2
+      ",
+    )
+    .args(["--ci-format", "github", "--output", "prefixed"])
+    .stderr("::group::foo\n[foo] foo\n::endgroup::\n")
+    .success();
+}
+
+#[test]
+fn github_detected_from_environment() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          @echo foo
+      ",
+    )
+    .env("GITHUB_ACTIONS", "true")
+    .stdout("foo\n")
+    .stderr("::group::foo\n::endgroup::\n")
+    .success();
+}
+
+#[test]
+fn github_group_names_include_arguments() {
+  Test::new()
+    .justfile(
+      "
+        foo a b:
+      ",
+    )
+    .args(["--ci-format", "github", "foo", "x", "y"])
+    .stderr("::group::foo x y\n::endgroup::\n")
+    .success();
+}
+
+#[test]
+fn github_error_annotations() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          @echo foo
+          @exit 3
+      ",
+    )
+    .args(["--ci-format", "github"])
+    .stdout("foo\n")
+    .stderr_regex(
+      "::group::foo
+::endgroup::
+::error file=.*justfile,line=3,title=just foo::recipe `foo` failed on line 3 with exit code 3
+error: recipe `foo` failed on line 3 with exit code 3
+",
+    )
+    .status(3);
+}
+
+#[test]
+fn github_error_annotations_are_not_written_for_skipped_recipes() {
+  Test::new()
+    .justfile(
+      "
+        a:
+          @sleep 0.5
+          @exit 2
+
+        [retry('2', delay='1h')]
+        b:
+          @exit 1
+      ",
+    )
+    .args(["--ci-format", "github", "--parallel", "a", "b"])
+    .stderr_regex(
+      r"::group::[ab]
+::group::[ab]
+error: recipe `b` failed on line 7 with exit code 1
+===> retrying recipe `b` in 1h \(attempt 2 of 2\)
+::endgroup::
+::error file=.*justfile,line=3,title=just a::recipe `a` failed on line 3 with exit code 2
+::endgroup::
+error: recipe `a` failed on line 3 with exit code 2
+",
+    )
+    .status(2);
+}
+
+#[test]
+fn github_error_annotation_paths_are_relative_to_workspace() {
+  let tempdir = tempdir();
+
+  let workspace = tempdir.path().canonicalize().unwrap();
+
+  Test::with_tempdir(tempdir)
+    .write("foo/mod.just", "bar:\n  @exit 1")
+    .justfile(
+      "
+        mod foo
+      ",
+    )
+    .env("GITHUB_WORKSPACE", workspace.to_str().unwrap())
+    .args(["--ci-format", "github", "foo", "bar"])
+    .stderr(
+      "
+        ::group::foo::bar
+        ::endgroup::
+        ::error file=foo/mod.just,line=2,title=just foo%3A%3Abar::recipe `bar` failed on line 2 with exit code 1
+        error: recipe `bar` failed on line 2 with exit code 1
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn github_evaluation_error_annotations() {
+  let tempdir = tempdir();
+
+  let workspace = tempdir.path().canonicalize().unwrap();
+
+  Test::with_tempdir(tempdir)
+    .justfile(
+      "
+        foo:
+          @echo {{ error('bar') }}
+      ",
+    )
+    .env("GITHUB_WORKSPACE", workspace.to_str().unwrap())
+    .args(["--ci-format", "github"])
+    .stderr_regex(
+      "::group::foo
+::endgroup::
+::error file=justfile,line=2,title=just foo::call to function `error` failed: bar%0A.*
+error: call to function `error` failed: bar
+.*",
+    )
+    .failure();
+}
+
+#[test]
+fn gitlab() {
+  Test::new()
+    .justfile(
+      "
+        foo: bar
+          echo foo
+
+        bar:
+          @echo bar
+      ",
+    )
+    .args(["--ci-format", "gitlab"])
+    .stdout("bar\nfoo\n")
+    .stderr_regex(
+      r"\x1b\[0Ksection_start:[0-9]+:bar_0\[collapsed=true\]\r\x1b\[0Kbar
+\x1b\[0Ksection_end:[0-9]+:bar_0\r\x1b\[0K
+\x1b\[0Ksection_start:[0-9]+:foo_1\[collapsed=true\]\r\x1b\[0Kfoo
+echo foo
+\x1b\[0Ksection_end:[0-9]+:foo_1\r\x1b\[0K
+",
+    )
+    .success();
+}
+
+#[test]
+fn gitlab_detected_from_environment() {
+  Test::new()
+    .write("foo.just", "bar a:")
+    .justfile(
+      "
+        mod foo
+      ",
+    )
+    .env("GITLAB_CI", "true")
+    .args(["foo::bar", "x"])
+    .stderr_regex(
+      r"\x1b\[0Ksection_start:[0-9]+:foo__bar_x_0\[collapsed=true\]\r\x1b\[0Kfoo::bar x
+\x1b\[0Ksection_end:[0-9]+:foo__bar_x_0\r\x1b\[0K
+",
+    )
+    .success();
+}
+
+#[test]
+fn none_overrides_environment() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          @echo foo
+      ",
+    )
+    .env("GITHUB_ACTIONS", "true")
+    .args(["--ci-format", "none"])
+    .stdout("foo\n")
+    .success();
+}
+
+#[test]
+fn environment_variable_overrides_detection() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          @echo foo
+      ",
+    )
+    .env("GITHUB_ACTIONS", "true")
+    .env("JUST_CI_FORMAT", "none")
+    .stdout("foo\n")
+    .success();
+}
+
+#[test]
+fn quiet_disables_groups() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          @echo foo
+      ",
+    )
+    .args(["--ci-format", "github", "--quiet"])
+    .success();
+}
+
+#[test]
+fn quiet_disables_annotations() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          @exit 3
+      ",
+    )
+    .args(["--ci-format", "github", "--quiet"])
+    .status(3);
+}
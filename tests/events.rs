@@ -0,0 +1,388 @@
+use super::*;
+
+// Read events written to `events.jsonl`, checking and removing fields which
+// vary between runs.
+#[track_caller]
+fn events(output: &Output) -> Vec<Value> {
+  read_normalized(output, "events.jsonl", &[])
+    .lines()
+    .map(|line| {
+      let mut event = serde_json::from_str::<Value>(line).unwrap();
+      let event = event.as_object_mut().unwrap();
+      assert!(event.remove("time").unwrap().is_f64());
+      if let Some(duration) = event.remove("duration") {
+        assert!(duration.as_f64().unwrap() >= 0.0);
+      }
+      if let Some(key) = event.remove("key") {
+        assert_eq!(key.as_str().unwrap().len(), 64);
+      }
+      Value::Object(event.clone())
+    })
+    .collect()
+}
+
+#[test]
+fn recipe_events() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo: bar
+          @echo foo
+
+        bar:
+          @echo bar
+      ",
+    )
+    .args(["--events-file", "events.jsonl"])
+    .stdout("bar\nfoo\n")
+    .success();
+
+  assert_eq!(
+    events(&output),
+    [
+      json!({
+        "event": "run_start",
+        "just_version": env!("CARGO_PKG_VERSION"),
+        "recipes": ["foo"],
+        "version": 1,
+      }),
+      json!({
+        "event": "recipe_start",
+        "arguments": [],
+        "dependency": true,
+        "recipe": "bar",
+      }),
+      json!({
+        "event": "command_start",
+        "command": "echo bar",
+        "line": 5,
+        "recipe": "bar",
+      }),
+      json!({
+        "event": "recipe_finish",
+        "arguments": [],
+        "cache": null,
+        "code": 0,
+        "dependency": true,
+        "recipe": "bar",
+        "success": true,
+      }),
+      json!({
+        "event": "recipe_start",
+        "arguments": [],
+        "dependency": false,
+        "recipe": "foo",
+      }),
+      json!({
+        "event": "command_start",
+        "command": "echo foo",
+        "line": 2,
+        "recipe": "foo",
+      }),
+      json!({
+        "event": "recipe_finish",
+        "arguments": [],
+        "cache": null,
+        "code": 0,
+        "dependency": false,
+        "recipe": "foo",
+        "success": true,
+      }),
+      json!({
+        "event": "run_finish",
+        "code": 0,
+        "success": true,
+      }),
+    ],
+  );
+}
+
+#[test]
+fn recipe_arguments() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo a b:
+      ",
+    )
+    .args(["--events-file", "events.jsonl", "foo", "x", "y z"])
+    .success();
+
+  let events = events(&output);
+
+  assert_eq!(events[1]["event"], "recipe_start");
+  assert_eq!(events[1]["arguments"], json!(["x", "y z"]));
+  assert_eq!(events[2]["event"], "recipe_finish");
+  assert_eq!(events[2]["arguments"], json!(["x", "y z"]));
+}
+
+#[test]
+fn submodule_recipe_paths() {
+  let output = Test::new()
+    .write("foo.just", "bar:\n  @echo bar")
+    .justfile(
+      "
+        mod foo
+      ",
+    )
+    .args(["--events-file", "events.jsonl", "foo", "bar"])
+    .stdout("bar\n")
+    .success();
+
+  let events = events(&output);
+
+  assert_eq!(events[0]["recipes"], json!(["foo::bar"]));
+  assert_eq!(events[1]["recipe"], "foo::bar");
+}
+
+#[test]
+fn failed_recipe() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo:
+          @exit 3
+      ",
+    )
+    .args(["--events-file", "events.jsonl"])
+    .stderr("error: recipe `foo` failed on line 2 with exit code 3\n")
+    .status(3);
+
+  assert_eq!(
+    events(&output)[3..],
+    [
+      json!({
+        "event": "recipe_finish",
+        "arguments": [],
+        "cache": null,
+        "code": 3,
+        "dependency": false,
+        "recipe": "foo",
+        "success": false,
+      }),
+      json!({
+        "event": "run_finish",
+        "code": 3,
+        "success": false,
+      }),
+    ],
+  );
+}
+
+#[test]
+fn evaluation_errors_exit_with_failure() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo:
+          @echo {{ error('bar') }}
+      ",
+    )
+    .args(["--events-file", "events.jsonl"])
+    .stderr_regex("(?s)error: call to function `error` failed: bar\n.*")
+    .failure();
+
+  let events = events(&output);
+
+  assert_eq!(events[2]["event"], "recipe_finish");
+  assert_eq!(events[2]["code"], 1);
+  assert_eq!(events[2]["success"], false);
+}
+
+#[test]
+fn cache_hit_and_miss() {
+  let justfile = "
+    [cache]
+    foo:
+      @echo foo
+  ";
+
+  let output = Test::new()
+    .justfile(justfile)
+    .unstable()
+    .args(["--events-file", "events.jsonl"])
+    .stdout("foo\n")
+    .success();
+
+  let first = events(&output);
+
+  assert_eq!(
+    first[1],
+    json!({
+      "event": "recipe_start",
+      "arguments": [],
+      "dependency": false,
+      "recipe": "foo",
+    }),
+  );
+
+  assert_eq!(
+    first[2],
+    json!({
+      "event": "cache",
+      "hit": false,
+      "recipe": "foo",
+    }),
+  );
+
+  assert_eq!(first[4]["event"], "recipe_finish");
+  assert_eq!(first[4]["cache"], "miss");
+
+  let output = output
+    .test()
+    .justfile(justfile)
+    .unstable()
+    .args(["--events-file", "events.jsonl"])
+    .success();
+
+  let second = events(&output);
+
+  assert_eq!(
+    second[2],
+    json!({
+      "event": "cache",
+      "hit": true,
+      "recipe": "foo",
+    }),
+  );
+
+  assert_eq!(second[3]["event"], "recipe_finish");
+  assert_eq!(second[3]["cache"], "hit");
+}
+
+#[test]
+fn script_recipes_do_not_emit_command_events() {
+  let output = Test::new()
+    .justfile(
+      "
+        [script('sh')]
+        foo:
+          echo foo
+      ",
+    )
+    .args(["--events-file", "events.jsonl"])
+    .stdout("foo\n")
+    .success();
+
+  assert!(
+    events(&output)
+      .iter()
+      .all(|event| event["event"] != "command_start")
+  );
+}
+
+#[test]
+fn dry_run_does_not_emit_command_events() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo:
+          echo foo
+      ",
+    )
+    .args(["--events-file", "events.jsonl", "--dry-run"])
+    .stderr("echo foo\n")
+    .success();
+
+  let events = events(&output)
+    .into_iter()
+    .map(|event| event["event"].as_str().unwrap().to_owned())
+    .collect::<Vec<String>>();
+
+  assert_eq!(
+    events,
+    ["run_start", "recipe_start", "recipe_finish", "run_finish"],
+  );
+}
+
+#[test]
+fn events_to_stderr() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          @echo foo
+      ",
+    )
+    .args(["--events", "json"])
+    .stdout("foo\n")
+    .stderr_regex(
+      r#"\{"event":"run_start",.*\}
+\{"event":"recipe_start",.*\}
+\{"event":"command_start",.*\}
+\{"event":"recipe_finish",.*\}
+\{"event":"run_finish",.*\}
+"#,
+    )
+    .success();
+}
+
+#[test]
+fn events_file_overrides_events() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo:
+      ",
+    )
+    .args(["--events", "json", "--events-file", "events.jsonl"])
+    .success();
+
+  assert_eq!(events(&output).len(), 4);
+}
+
+#[test]
+fn events_file_from_environment() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo:
+      ",
+    )
+    .env("JUST_EVENTS_FILE", "events.jsonl")
+    .success();
+
+  assert_eq!(events(&output).len(), 4);
+}
+
+#[test]
+fn events_file_error() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+      ",
+    )
+    .args(["--events-file", "missing/events.jsonl"])
+    .stderr_regex("error: I/O error at `missing/events.jsonl`: .*\n")
+    .failure();
+}
+
+#[test]
+#[cfg(unix)]
+fn signal_events() {
+  let tempdir = tempdir();
+
+  fs::write(
+    tempdir.path().join("justfile"),
+    "foo:\n  @kill -TERM $PPID\n  @sleep 1\n",
+  )
+  .unwrap();
+
+  let output = Command::new(JUST)
+    .args(["--events-file", "events.jsonl"])
+    .current_dir(tempdir.path())
+    .output()
+    .unwrap();
+
+  assert!(!output.status.success());
+
+  let events = fs::read_to_string(tempdir.path().join("events.jsonl")).unwrap();
+
+  assert!(
+    events
+      .lines()
+      .any(|line| line.starts_with(r#"{"event":"signal","signal":"SIGTERM","#)),
+    "{events}",
+  );
+}
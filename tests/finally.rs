@@ -0,0 +1,297 @@
+use super::*;
+
+#[test]
+fn finally_dependencies_run_after_success() {
+  Test::new()
+    .justfile(
+      "
+        foo: bar && baz &&! teardown
+          @echo foo
+
+        bar:
+          @echo bar
+
+        baz:
+          @echo baz
+
+        teardown:
+          @echo teardown
+      ",
+    )
+    .stdout("bar\nfoo\nbaz\nteardown\n")
+    .success();
+}
+
+#[test]
+fn finally_dependencies_run_after_recipe_failure() {
+  Test::new()
+    .justfile(
+      "
+        foo: &&! teardown
+          @exit 3
+
+        teardown:
+          @echo teardown
+      ",
+    )
+    .stdout("teardown\n")
+    .stderr("error: recipe `foo` failed on line 2 with exit code 3\n")
+    .status(3);
+}
+
+#[test]
+fn finally_dependencies_run_after_prior_failure() {
+  Test::new()
+    .justfile(
+      "
+        foo: setup &&! teardown
+          @echo foo
+
+        setup:
+          @exit 3
+
+        teardown:
+          @echo teardown
+      ",
+    )
+    .stdout("teardown\n")
+    .stderr("error: recipe `setup` failed on line 5 with exit code 3\n")
+    .status(3);
+}
+
+#[test]
+fn finally_dependencies_run_after_subsequent_failure() {
+  Test::new()
+    .justfile(
+      "
+        foo: && check &&! teardown
+          @echo foo
+
+        check:
+          @exit 3
+
+        teardown:
+          @echo teardown
+      ",
+    )
+    .stdout("foo\nteardown\n")
+    .stderr("error: recipe `check` failed on line 5 with exit code 3\n")
+    .status(3);
+}
+
+#[test]
+fn finally_dependencies_take_arguments() {
+  Test::new()
+    .justfile(
+      "
+        foo name: &&! (teardown name)
+
+        teardown name:
+          @echo teardown {{ name }}
+      ",
+    )
+    .args(["foo", "db"])
+    .stdout("teardown db\n")
+    .success();
+}
+
+#[test]
+fn multiple_finally_dependencies_run_in_order() {
+  Test::new()
+    .justfile(
+      "
+        foo: &&! a b
+
+        a:
+          @echo a
+
+        b:
+          @echo b
+      ",
+    )
+    .stdout("a\nb\n")
+    .success();
+}
+
+#[test]
+fn finally_dependency_failure_is_reported_after_success() {
+  Test::new()
+    .justfile(
+      "
+        foo: &&! teardown
+          @echo foo
+
+        teardown:
+          @exit 4
+      ",
+    )
+    .stdout("foo\n")
+    .stderr("error: recipe `teardown` failed on line 5 with exit code 4\n")
+    .status(4);
+}
+
+#[test]
+fn finally_dependency_failure_does_not_mask_recipe_failure() {
+  Test::new()
+    .justfile(
+      "
+        foo: &&! teardown
+          @exit 3
+
+        teardown:
+          @exit 4
+      ",
+    )
+    .stderr(
+      "
+        error: recipe `teardown` failed on line 5 with exit code 4
+        error: recipe `foo` failed on line 2 with exit code 3
+      ",
+    )
+    .status(3);
+}
+
+#[test]
+fn finally_dependency_failure_is_reported_with_keep_going() {
+  Test::new()
+    .arg("--keep-going")
+    .justfile(
+      "
+        foo: &&! teardown
+          @exit 3
+
+        teardown:
+          @exit 4
+      ",
+    )
+    .stderr(
+      "
+        error: recipe `foo` failed on line 2 with exit code 3
+        error: recipe `teardown` failed on line 5 with exit code 4
+        error: 2 recipes failed:
+          `foo` with exit code 3
+          `teardown` with exit code 4
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn finally_dependencies_run_each_time() {
+  Test::new()
+    .justfile(
+      "
+        all: a b
+
+        a: &&! teardown
+
+        b: &&! teardown
+
+        teardown:
+          @echo teardown
+      ",
+    )
+    .stdout("teardown\nteardown\n")
+    .success();
+}
+
+#[test]
+fn finally_dependencies_run_after_parallel_invocation_fails() {
+  Test::new()
+    .args(["--parallel", "a", "b"])
+    .justfile(
+      "
+        a:
+          @exit 3
+
+        b: &&! teardown
+          @sleep 0.5
+
+        teardown:
+          @echo teardown
+      ",
+    )
+    .stdout("teardown\n")
+    .stderr("error: recipe `a` failed on line 2 with exit code 3\n")
+    .status(3);
+}
+
+#[test]
+fn finally_dependencies_are_not_run_if_recipe_is_not_confirmed() {
+  Test::new()
+    .justfile(
+      "
+        [confirm]
+        foo: &&! teardown
+
+        teardown:
+          @echo teardown
+      ",
+    )
+    .stdin("n")
+    .stderr("Run recipe `foo`? error: recipe `foo` was not confirmed\n")
+    .failure();
+}
+
+#[test]
+fn finally_dependencies_require_recipes() {
+  Test::new()
+    .justfile(
+      "
+        foo: &&!
+      ",
+    )
+    .stderr(
+      "
+        error: expected '*', identifier, or '(', but found end of line
+         ——▶ justfile:1:9
+          │
+        1 │ foo: &&!
+          │         ^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn unknown_finally_dependency() {
+  Test::new()
+    .justfile(
+      "
+        foo: &&! teardown
+      ",
+    )
+    .stderr(
+      "
+        error: recipe `foo` has unknown dependency `teardown`
+         ——▶ justfile:1:10
+          │
+        1 │ foo: &&! teardown
+          │          ^^^^^^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn finally_dependencies_are_formatted() {
+  Test::new()
+    .arg("--dump")
+    .justfile(
+      "
+        foo: bar && baz &&!  teardown
+        bar:
+        baz:
+        teardown:
+      ",
+    )
+    .stdout(
+      "
+        foo: bar && baz &&! teardown
+        bar:
+        baz:
+        teardown:
+      ",
+    )
+    .success();
+}
@@ -59,7 +59,7 @@ fn comment_in_interopolation() {
     )
     .stderr(
       "
-        error: expected backtick, '!', '[', identifier, '(', '/', or string, but found comment
+        error: expected backtick, '!', '{', '[', identifier, '(', '/', or string, but found comment
          ——▶ justfile:2:11
           │
         2 │   echo {{ # hello
@@ -50,7 +50,7 @@ fn test_invocation_directory() {
 
   let output = Command::new(JUST)
     .current_dir(&subdir)
-    .args(["--shell", "sh"])
+    .args(["--ci-format", "none", "--shell", "sh"])
     .output()
     .expect("just invocation failed");
 
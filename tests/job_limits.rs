@@ -0,0 +1,402 @@
+use super::*;
+
+#[test]
+fn group_recipes_are_limited() {
+  Test::new()
+    .justfile(
+      "
+        set job-limits := { 'integration': '1' }
+
+        [parallel]
+        foo: a b c
+
+        [group('integration')]
+        a:
+          @test ! -f running
+          @touch running
+          @sleep 0.1
+          @rm running
+
+        [group('integration')]
+        b:
+          @test ! -f running
+          @touch running
+          @sleep 0.1
+          @rm running
+
+        [group('integration')]
+        c:
+          @test ! -f running
+          @touch running
+          @sleep 0.1
+          @rm running
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn limits_allow_multiple_recipes() {
+  Test::new()
+    .justfile(
+      "
+        set job-limits := { 'integration': '2' }
+
+        [parallel]
+        foo: a b
+
+        [group('integration')]
+        [timeout('5s')]
+        a:
+          @touch a
+          @while [ ! -f b ]; do sleep 0.01; done
+
+        [group('integration')]
+        [timeout('5s')]
+        b:
+          @touch b
+          @while [ ! -f a ]; do sleep 0.01; done
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn other_groups_are_not_limited() {
+  Test::new()
+    .justfile(
+      "
+        set job-limits := { 'integration': '1' }
+
+        [parallel]
+        foo: a b
+
+        [group('unit')]
+        [timeout('5s')]
+        a:
+          @touch a
+          @while [ ! -f b ]; do sleep 0.01; done
+
+        [group('unit')]
+        [timeout('5s')]
+        b:
+          @touch b
+          @while [ ! -f a ]; do sleep 0.01; done
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn recipes_in_multiple_groups_are_limited_by_each() {
+  Test::new()
+    .justfile(
+      "
+        set job-limits := { 'database': '1', 'integration': '4' }
+
+        [parallel]
+        foo: a b
+
+        [group('database')]
+        [group('integration')]
+        a:
+          @test ! -f running
+          @touch running
+          @sleep 0.1
+          @rm running
+
+        [group('database')]
+        b:
+          @test ! -f running
+          @touch running
+          @sleep 0.1
+          @rm running
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn parallel_invocations_are_limited() {
+  Test::new()
+    .args(["--parallel", "a", "b"])
+    .justfile(
+      "
+        set job-limits := { 'integration': '1' }
+
+        [group('integration')]
+        a:
+          @test ! -f running
+          @touch running
+          @sleep 0.1
+          @rm running
+
+        [group('integration')]
+        b:
+          @test ! -f running
+          @touch running
+          @sleep 0.1
+          @rm running
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn recipes_waiting_for_group_do_not_hold_jobs() {
+  Test::new()
+    .args(["--jobs", "2", "--parallel", "a", "b", "c", "d", "e"])
+    .justfile(
+      "
+        set job-limits := { 'integration': '1' }
+
+        [group('integration')]
+        [timeout('5s')]
+        a:
+          @touch a
+          @while [ ! -f e ]; do sleep 0.01; done
+
+        [group('integration')]
+        b:
+
+        [group('integration')]
+        c:
+
+        [group('integration')]
+        d:
+
+        [timeout('5s')]
+        e:
+          @touch e
+          @while [ ! -f a ]; do sleep 0.01; done
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn limits_apply_to_groups_in_same_module() {
+  Test::new()
+    .write(
+      "foo.just",
+      "
+set job-limits := { 'integration': '1' }
+
+[parallel]
+all: a b
+
+[group('integration')]
+a:
+  @test ! -f running
+  @touch running
+  @sleep 0.1
+  @rm running
+
+[group('integration')]
+b:
+  @test ! -f running
+  @touch running
+  @sleep 0.1
+  @rm running
+",
+    )
+    .justfile(
+      "
+        mod foo
+
+        [parallel]
+        bar: c d
+
+        [group('integration')]
+        [timeout('5s')]
+        c:
+          @touch c
+          @while [ ! -f d ]; do sleep 0.01; done
+
+        [group('integration')]
+        [timeout('5s')]
+        d:
+          @touch d
+          @while [ ! -f c ]; do sleep 0.01; done
+      ",
+    )
+    .args(["foo::all", "bar"])
+    .success();
+}
+
+#[test]
+fn trailing_comma() {
+  Test::new()
+    .justfile(
+      "
+        set job-limits := {
+          'integration': '2',
+          'unit': '4',
+        }
+
+        foo:
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn empty() {
+  Test::new()
+    .justfile(
+      "
+        set job-limits := {}
+
+        foo:
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn limit_must_be_positive() {
+  Test::new()
+    .justfile(
+      "
+        set job-limits := { 'integration': '0' }
+      ",
+    )
+    .stderr(
+      "
+        error: `job-limits` setting has invalid limit `0` for group `integration`, expected a positive integer
+         ——▶ justfile:1:5
+          │
+        1 │ set job-limits := { 'integration': '0' }
+          │     ^^^^^^^^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn groups_may_not_be_repeated() {
+  Test::new()
+    .justfile(
+      "
+        set job-limits := { 'integration': '1', 'integration': '2' }
+      ",
+    )
+    .stderr(
+      r#"
+        error: map has duplicate key "integration"
+         ——▶ justfile:1:19
+          │
+        1 │ set job-limits := { 'integration': '1', 'integration': '2' }
+          │                   ^
+      "#,
+    )
+    .failure();
+}
+
+#[test]
+fn limits_may_be_expressions() {
+  Test::new()
+    .justfile(
+      "
+        group := 'integration'
+        limit := '1'
+
+        set job-limits := { group: limit }
+
+        [parallel]
+        foo: a b
+
+        [group('integration')]
+        a:
+          @test ! -f running
+          @touch running
+          @sleep 0.1
+          @rm running
+
+        [group('integration')]
+        b:
+          @test ! -f running
+          @touch running
+          @sleep 0.1
+          @rm running
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn limits_must_be_map() {
+  Test::new()
+    .justfile(
+      "
+        set job-limits := '2'
+      ",
+    )
+    .stderr(
+      r#"
+        error: `job-limits` setting must be a map but evaluated to "2"
+         ——▶ justfile:1:5
+          │
+        1 │ set job-limits := '2'
+          │     ^^^^^^^^^^
+      "#,
+    )
+    .failure();
+}
+
+#[test]
+fn limit_must_be_integer() {
+  Test::new()
+    .justfile(
+      "
+        set job-limits := { 'integration': '+2' }
+      ",
+    )
+    .stderr(
+      "
+        error: `job-limits` setting has invalid limit `+2` for group `integration`, expected a positive integer
+         ——▶ justfile:1:5
+          │
+        1 │ set job-limits := { 'integration': '+2' }
+          │     ^^^^^^^^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn nested_list_features_require_lists_setting() {
+  Test::new()
+    .justfile(
+      "
+        set job-limits := { 'integration': ['1'] }
+      ",
+    )
+    .stderr(
+      "
+        error: list literals require `set lists`
+         ——▶ justfile:1:36
+          │
+        1 │ set job-limits := { 'integration': ['1'] }
+          │                                    ^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn job_limits_are_formatted() {
+  Test::new()
+    .arg("--dump")
+    .justfile(
+      "
+        set job-limits := {'integration':'2','unit':'4'}
+      ",
+    )
+    .stdout(
+      "
+        set job-limits := {'integration': '2', 'unit': '4'}
+      ",
+    )
+    .success();
+}
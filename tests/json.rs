@@ -75,6 +75,7 @@ struct Recipe<'a> {
   body: Vec<Value>,
   dependencies: Vec<Dependency<'a>>,
   doc: Option<&'a str>,
+  finally: u32,
   name: &'a str,
   namepath: &'a str,
   parameters: Vec<Parameter<'a>>,
@@ -96,6 +97,7 @@ enum Dotenv<'a> {
 struct Settings<'a> {
   allow_duplicate_recipes: bool,
   allow_duplicate_variables: bool,
+  cache_directory: Option<&'a str>,
   default_list: bool,
   default_script: bool,
   dotenv_command: Vec<String>,
@@ -109,10 +111,12 @@ struct Settings<'a> {
   guards: bool,
   ignore_comments: bool,
   indentation: Option<&'a str>,
+  job_limits: BTreeMap<&'a str, u64>,
   lazy: bool,
   lists: bool,
   no_cd: bool,
   no_exit_message: bool,
+  output: Option<&'a str>,
   positional_arguments: bool,
   quiet: bool,
   shell: Option<Interpreter<'a>>,
@@ -468,6 +472,48 @@ fn duplicate_variables() {
   );
 }
 
+#[test]
+fn cache_directory() {
+  case(
+    "set cache-directory := 'foo'",
+    Module {
+      settings: Settings {
+        cache_directory: Some("foo"),
+        ..default()
+      },
+      ..default()
+    },
+  );
+}
+
+#[test]
+fn job_limits() {
+  case(
+    "set job-limits := { 'integration': '2' }",
+    Module {
+      settings: Settings {
+        job_limits: [("integration", 2)].into(),
+        ..default()
+      },
+      ..default()
+    },
+  );
+}
+
+#[test]
+fn output() {
+  case(
+    "set output := 'grouped'",
+    Module {
+      settings: Settings {
+        output: Some("grouped"),
+        ..default()
+      },
+      ..default()
+    },
+  );
+}
+
 #[test]
 fn doc_comment() {
   case(
@@ -595,6 +641,61 @@ fn parameters() {
   );
 }
 
+#[test]
+fn finally() {
+  case(
+    "
+      a:
+      b: a &&! c
+      c:
+    ",
+    Module {
+      first: Some("a"),
+      recipes: [
+        (
+          "a",
+          Recipe {
+            name: "a",
+            namepath: "a",
+            ..default()
+          },
+        ),
+        (
+          "b",
+          Recipe {
+            dependencies: [
+              Dependency {
+                recipe: "a",
+                ..default()
+              },
+              Dependency {
+                recipe: "c",
+                ..default()
+              },
+            ]
+            .into(),
+            finally: 1,
+            name: "b",
+            namepath: "b",
+            priors: 1,
+            ..default()
+          },
+        ),
+        (
+          "c",
+          Recipe {
+            name: "c",
+            namepath: "c",
+            ..default()
+          },
+        ),
+      ]
+      .into(),
+      ..default()
+    },
+  );
+}
+
 #[test]
 fn priors() {
   case(
@@ -868,6 +969,61 @@ fn list_concatenation() {
   pretty_assertions::assert_eq!(actual, expected);
 }
 
+#[test]
+fn map() {
+  let test = Test::new()
+    .justfile(
+      "
+        set lists
+
+        foo := {'a': 'b', 'c': bar}['c']
+
+        bar := 'baz'
+      ",
+    )
+    .unstable()
+    .args(["--dump", "--dump-format", "json"])
+    .stdout_regex(".*");
+
+  let mut expected = Module {
+    assignments: [
+      (
+        "bar",
+        Assignment {
+          name: "bar",
+          value: json!("baz"),
+          ..default()
+        },
+      ),
+      (
+        "foo",
+        Assignment {
+          name: "foo",
+          value: json!([
+            "index",
+            ["map", ["a", "b"], ["c", ["variable", "bar"]]],
+            "c",
+          ]),
+          ..default()
+        },
+      ),
+    ]
+    .into(),
+    settings: Settings {
+      lists: true,
+      ..default()
+    },
+    ..default()
+  };
+
+  fix_source(test.tempdir.path(), &mut expected);
+
+  let stdout = test.success().stdout;
+  let actual = serde_json::from_str::<Module>(&stdout).unwrap();
+
+  pretty_assertions::assert_eq!(actual, expected);
+}
+
 #[test]
 fn shebang() {
   case(
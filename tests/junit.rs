@@ -0,0 +1,368 @@
+use super::*;
+
+// Read the report written to `report.xml`, replacing durations and the
+// timestamp, which vary between runs.
+#[track_caller]
+fn report(output: &Output) -> String {
+  read_normalized(
+    output,
+    "report.xml",
+    &[
+      (r#"time="[0-9]+\.[0-9]{3}""#, r#"time="0.000""#),
+      (
+        r#"timestamp="[0-9]{4}-[0-9]{2}-[0-9]{2}T[0-9]{2}:[0-9]{2}:[0-9]{2}""#,
+        r#"timestamp="""#,
+      ),
+    ],
+  )
+}
+
+#[test]
+fn successful_recipes() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo a: bar
+          @echo {{ a }}
+
+        bar:
+          @echo bar
+      ",
+    )
+    .args(["--junit", "report.xml", "foo", "baz"])
+    .stdout("bar\nbaz\n")
+    .success();
+
+  assert_eq!(
+    report(&output),
+    r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="just" tests="2" failures="0" errors="0" skipped="0" time="0.000">
+  <testsuite name="just" tests="2" failures="0" errors="0" skipped="0" time="0.000" timestamp="">
+    <testcase name="bar" classname="just" time="0.000"/>
+    <testcase name="foo baz" classname="just" time="0.000"/>
+  </testsuite>
+</testsuites>
+"#,
+  );
+}
+
+#[test]
+fn failed_recipe() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo:
+          @echo 'bad things' >&2
+          @exit 3
+      ",
+    )
+    .args(["--junit", "report.xml", "--output", "grouped"])
+    .stderr("bad things\nerror: recipe `foo` failed on line 3 with exit code 3\n")
+    .status(3);
+
+  assert_eq!(
+    report(&output),
+    r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="just" tests="1" failures="1" errors="0" skipped="0" time="0.000">
+  <testsuite name="just" tests="1" failures="1" errors="0" skipped="0" time="0.000" timestamp="">
+    <testcase name="foo" classname="just" time="0.000">
+      <failure message="error: recipe `foo` failed on line 3 with exit code 3">error: recipe `foo` failed on line 3 with exit code 3</failure>
+      <system-err>bad things
+</system-err>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#,
+  );
+}
+
+#[test]
+fn failed_script_recipe() {
+  let output = Test::new()
+    .justfile(
+      "
+        [script('sh')]
+        foo:
+          echo 'bad things' >&2
+          exit 3
+      ",
+    )
+    .args(["--junit", "report.xml", "--output", "grouped"])
+    .stderr("bad things\nerror: recipe `foo` failed with exit code 3\n")
+    .status(3);
+
+  let report = report(&output);
+
+  assert!(report.contains(r#"<failure message="error: recipe `foo` failed with exit code 3">"#));
+  assert!(report.contains("<system-err>bad things\n</system-err>"));
+}
+
+#[test]
+fn echoed_commands_are_not_captured() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo:
+          echo foo
+      ",
+    )
+    .args(["--junit", "report.xml"])
+    .stdout("foo\n")
+    .stderr("echo foo\n")
+    .success();
+
+  assert!(report(&output).contains(r#"<testcase name="foo" classname="just" time="0.000"/>"#));
+}
+
+#[test]
+fn interleaved_stderr_is_captured() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo:
+          @echo 'bad things' >&2
+          @exit 3
+      ",
+    )
+    .args(["--junit", "report.xml"])
+    .stderr("bad things\nerror: recipe `foo` failed on line 3 with exit code 3\n")
+    .status(3);
+
+  let report = report(&output);
+
+  assert!(report.contains("<failure "));
+  assert!(report.contains("<system-err>bad things\n</system-err>"));
+}
+
+#[test]
+fn stderr_is_truncated() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo:
+          @for i in $(seq 1 2000); do echo \"line $i\" >&2; done
+      ",
+    )
+    .args(["--junit", "report.xml", "--output", "grouped"])
+    .stderr_regex("line 1\n(?s).*line 2000\n")
+    .success();
+
+  let report = report(&output);
+
+  let stderr = report
+    .split_once("<system-err>")
+    .unwrap()
+    .1
+    .split_once("</system-err>")
+    .unwrap()
+    .0;
+
+  assert!(stderr.len() <= 8192);
+  assert!(stderr.starts_with("line "));
+  assert!(stderr.ends_with("line 2000\n"));
+  assert!(!stderr.contains("line 1\n"));
+}
+
+#[test]
+fn failure_messages_are_escaped() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo:
+          @echo '<&\"\x1b>' >&2
+          @exit 1
+      ",
+    )
+    .args(["--junit", "report.xml", "--output", "grouped"])
+    .stderr_regex("(?s).*")
+    .failure();
+
+  assert!(report(&output).contains("<system-err>&lt;&amp;&quot;&gt;\n</system-err>"));
+}
+
+#[test]
+fn cache_hits_are_skipped() {
+  let justfile = "
+    [cache]
+    foo:
+      @echo foo
+  ";
+
+  let output = Test::new()
+    .justfile(justfile)
+    .unstable()
+    .args(["--junit", "report.xml"])
+    .stdout("foo\n")
+    .success();
+
+  assert!(report(&output).contains(r#"<testcase name="foo" classname="just" time="0.000"/>"#));
+
+  let output = output
+    .test()
+    .justfile(justfile)
+    .unstable()
+    .args(["--junit", "report.xml"])
+    .success();
+
+  assert_eq!(
+    report(&output),
+    r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="just" tests="1" failures="0" errors="0" skipped="1" time="0.000">
+  <testsuite name="just" tests="1" failures="0" errors="0" skipped="1" time="0.000" timestamp="">
+    <testcase name="foo" classname="just" time="0.000">
+      <skipped message="cache hit"/>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#,
+  );
+}
+
+#[test]
+fn up_to_date_recipes_are_skipped() {
+  let output = Test::new()
+    .justfile(
+      "
+        [stale(sources = 'src', targets = 'out')]
+        foo:
+          @echo foo
+      ",
+    )
+    .write("src", "a")
+    .modified("src", 1000)
+    .write("out", "a")
+    .modified("out", 2000)
+    .args(["--junit", "report.xml"])
+    .success();
+
+  assert!(report(&output).contains(
+    r#"<testcase name="foo" classname="just" time="0.000">
+      <skipped message="up to date"/>
+    </testcase>"#
+  ));
+}
+
+#[test]
+fn dry_run_recipes_are_skipped() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo:
+          @echo foo
+      ",
+    )
+    .args(["--junit", "report.xml", "--dry-run"])
+    .stderr("echo foo\n")
+    .success();
+
+  assert_eq!(
+    report(&output),
+    r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="just" tests="1" failures="0" errors="0" skipped="1" time="0.000">
+  <testsuite name="just" tests="1" failures="0" errors="0" skipped="1" time="0.000" timestamp="">
+    <testcase name="foo" classname="just" time="0.000">
+      <skipped message="dry run"/>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#,
+  );
+}
+
+#[test]
+fn platform_disabled_recipes_are_skipped() {
+  let output = Test::new()
+    .write(
+      "bar.just",
+      "
+[openbsd]
+[netbsd]
+baz:
+",
+    )
+    .justfile(
+      "
+        mod bar
+
+        foo:
+
+        [openbsd]
+        [netbsd]
+        qux:
+
+        [openbsd]
+        [netbsd]
+        foo:
+      ",
+    )
+    .args(["--junit", "report.xml", "foo"])
+    .success();
+
+  if cfg!(any(target_os = "openbsd", target_os = "netbsd")) {
+    return;
+  }
+
+  assert_eq!(
+    report(&output),
+    r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="just" tests="2" failures="0" errors="0" skipped="1" time="0.000">
+  <testsuite name="just" tests="2" failures="0" errors="0" skipped="1" time="0.000" timestamp="">
+    <testcase name="qux" classname="just" time="0.000">
+      <skipped message="disabled on this platform"/>
+    </testcase>
+    <testcase name="foo" classname="just" time="0.000"/>
+  </testsuite>
+</testsuites>
+"#,
+  );
+}
+
+#[test]
+fn recipes_which_do_not_run_are_not_reported() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo: bar
+          @echo foo
+
+        bar:
+          @exit 1
+      ",
+    )
+    .args(["--junit", "report.xml"])
+    .stderr("error: recipe `bar` failed on line 5 with exit code 1\n")
+    .failure();
+
+  let report = report(&output);
+
+  assert!(report.contains(r#"<testcase name="bar""#));
+  assert!(!report.contains(r#"<testcase name="foo""#));
+}
+
+#[test]
+fn submodule_recipes() {
+  let output = Test::new()
+    .write("foo.just", "bar:")
+    .justfile(
+      "
+        mod foo
+      ",
+    )
+    .args(["--junit", "report.xml", "foo::bar"])
+    .success();
+
+  assert!(report(&output).contains(r#"<testcase name="foo::bar" classname="just""#));
+}
+
+#[test]
+fn report_write_error() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+      ",
+    )
+    .args(["--junit", "missing/report.xml"])
+    .stderr_regex("error: I/O error at `missing/report.xml`: .*\n")
+    .failure();
+}
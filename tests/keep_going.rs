@@ -0,0 +1,305 @@
+use super::*;
+
+#[test]
+fn without_keep_going_first_failure_stops_run() {
+  Test::new()
+    .justfile(
+      "
+        foo: a b
+
+        a:
+          @exit 1
+
+        b:
+          @echo b
+      ",
+    )
+    .stderr("error: recipe `a` failed on line 4 with exit code 1\n")
+    .failure();
+}
+
+#[test]
+fn dependencies_after_failure_run() {
+  Test::new()
+    .arg("--keep-going")
+    .justfile(
+      "
+        foo: a b
+          @echo foo
+
+        a:
+          @exit 1
+
+        b:
+          @echo b
+      ",
+    )
+    .stdout("b\n")
+    .stderr("error: recipe `a` failed on line 5 with exit code 1\n")
+    .failure();
+}
+
+#[test]
+fn single_failure_exit_code_is_preserved() {
+  Test::new()
+    .arg("--keep-going")
+    .justfile(
+      "
+        foo: a b
+
+        a:
+          @exit 7
+
+        b:
+      ",
+    )
+    .stderr("error: recipe `a` failed on line 4 with exit code 7\n")
+    .status(7);
+}
+
+#[test]
+fn failures_are_reported_together() {
+  Test::new()
+    .arg("--keep-going")
+    .justfile(
+      "
+        foo: a b c
+
+        a:
+          @exit 2
+
+        b:
+          @echo b
+
+        c:
+          @exit 3
+      ",
+    )
+    .stdout("b\n")
+    .stderr(
+      "
+        error: recipe `a` failed on line 4 with exit code 2
+        error: recipe `c` failed on line 10 with exit code 3
+        error: 2 recipes failed:
+          `a` with exit code 2
+          `c` with exit code 3
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn dependents_of_failed_recipes_are_skipped() {
+  Test::new()
+    .arg("--keep-going")
+    .justfile(
+      "
+        foo: bar baz
+          @echo foo
+
+        bar: a
+          @echo bar
+
+        baz:
+          @echo baz
+
+        a:
+          @exit 1
+      ",
+    )
+    .stdout("baz\n")
+    .stderr("error: recipe `a` failed on line 11 with exit code 1\n")
+    .failure();
+}
+
+#[test]
+fn failed_shared_dependencies_run_once() {
+  Test::new()
+    .arg("--keep-going")
+    .justfile(
+      "
+        foo: a b
+
+        a: x
+          @echo a
+
+        b: x
+          @echo b
+
+        x:
+          @echo x
+          @exit 1
+      ",
+    )
+    .stdout("x\n")
+    .stderr("error: recipe `x` failed on line 11 with exit code 1\n")
+    .failure();
+}
+
+#[test]
+fn invocations_after_failure_run() {
+  Test::new()
+    .args(["--keep-going", "a", "b", "c"])
+    .justfile(
+      "
+        a:
+          @exit 1
+
+        b:
+          @echo b
+
+        c:
+          @exit 2
+      ",
+    )
+    .stdout("b\n")
+    .stderr(
+      "
+        error: recipe `a` failed on line 2 with exit code 1
+        error: recipe `c` failed on line 8 with exit code 2
+        error: 2 recipes failed:
+          `a` with exit code 1
+          `c` with exit code 2
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn parallel_dependency_failures_are_reported_together() {
+  Test::new()
+    .arg("--keep-going")
+    .justfile(
+      "
+        [parallel]
+        foo: a b c
+          @echo foo
+
+        a:
+          @exit 1
+
+        b:
+          @echo b
+
+        c:
+          @exit 2
+      ",
+    )
+    .stdout("b\n")
+    .stderr(
+      "
+        error: recipe `a` failed on line 6 with exit code 1
+        error: recipe `c` failed on line 12 with exit code 2
+        error: 2 recipes failed:
+          `a` with exit code 1
+          `c` with exit code 2
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn subsequent_failures_are_reported_together() {
+  Test::new()
+    .arg("--keep-going")
+    .justfile(
+      "
+        foo: && a b
+
+        a:
+          @exit 1
+
+        b:
+          @exit 2
+      ",
+    )
+    .stderr(
+      "
+        error: recipe `a` failed on line 4 with exit code 1
+        error: recipe `b` failed on line 7 with exit code 2
+        error: 2 recipes failed:
+          `a` with exit code 1
+          `b` with exit code 2
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn evaluation_failures_are_summarized() {
+  Test::new()
+    .arg("--keep-going")
+    .justfile(
+      "
+        foo: a b
+
+        a:
+          @exit 1
+
+        b x=`exit 3`:
+      ",
+    )
+    .stderr_regex(
+      "error: recipe `a` failed on line 4 with exit code 1
+error: .*
+(.*\n)*error: 2 recipes failed:
+  `a` with exit code 1
+  `b` with exit code 3
+",
+    )
+    .failure();
+}
+
+#[test]
+fn suppressed_exit_messages_are_summarized() {
+  Test::new()
+    .arg("--keep-going")
+    .justfile(
+      "
+        foo: a b
+
+        [no-exit-message]
+        a:
+          @exit 1
+
+        b:
+          @exit 2
+      ",
+    )
+    .stderr(
+      "
+        error: recipe `b` failed on line 8 with exit code 2
+        error: 2 recipes failed:
+          `a` with exit code 1
+          `b` with exit code 2
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn submodule_recipe_paths_are_summarized() {
+  Test::new()
+    .arg("--keep-going")
+    .write("foo.just", "bar:\n  @exit 2\n")
+    .justfile(
+      "
+        mod foo
+
+        baz: foo::bar a
+
+        a:
+          @exit 1
+      ",
+    )
+    .stderr(
+      "
+        error: recipe `bar` failed on line 2 with exit code 2
+        error: recipe `a` failed on line 6 with exit code 1
+        error: 2 recipes failed:
+          `foo::bar` with exit code 2
+          `a` with exit code 1
+      ",
+    )
+    .failure();
+}
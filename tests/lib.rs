@@ -1,5 +1,9 @@
 use {
-  crate::{output::Output, tempdir::tempdir, test::Test},
+  crate::{
+    output::Output,
+    tempdir::tempdir,
+    test::{Test, read_normalized},
+  },
   just::{Response, unindent},
   pretty_assertions::Comparison,
   regex::Regex,
@@ -16,7 +20,7 @@ use {
     path::{MAIN_SEPARATOR, MAIN_SEPARATOR_STR, Path, PathBuf},
     process::{Command, Stdio},
     str, thread,
-    time::{Duration, Instant},
+    time::{Duration, Instant, UNIX_EPOCH},
   },
   tempfile::TempDir,
   which::which,
@@ -94,6 +98,7 @@ mod cache;
 mod ceiling;
 mod changelog;
 mod choose;
+mod ci_format;
 mod command;
 mod comparison;
 mod completions;
@@ -111,10 +116,12 @@ mod edit;
 mod equals;
 mod error_messages;
 mod evaluate;
+mod events;
 mod examples;
 mod explain;
 mod export;
 mod fallback;
+mod finally;
 mod format;
 mod format_string;
 mod function_definitions;
@@ -128,16 +135,22 @@ mod imports;
 mod init;
 mod interpolation;
 mod invocation_directory;
+mod job_limits;
 mod json;
+mod junit;
 mod justfile_from_stdin;
+mod keep_going;
 mod lazy;
 mod line_prefixes;
 mod list;
 mod list_literals;
 mod lists;
+mod lock;
 mod logical_operators;
+mod lsp;
 mod man;
 mod mapped_dependencies;
+mod maps;
 mod markdown;
 mod minimum_version;
 mod misc;
@@ -154,6 +167,7 @@ mod non_unicode;
 mod options;
 mod os_attributes;
 mod output;
+mod output_mode;
 mod overrides;
 mod parallel;
 mod parameters;
@@ -167,6 +181,7 @@ mod recursion_limit;
 mod regexes;
 mod request;
 mod resolve;
+mod retry;
 mod run;
 mod scope;
 mod script;
@@ -181,16 +196,22 @@ mod show;
 #[cfg(unix)]
 mod signals;
 mod slash_operator;
+mod stale;
 mod string;
 mod style;
 mod subsequents;
 mod summary;
 mod tempdir;
+mod timeout;
 mod timestamps;
+mod trace;
 mod undefined_variables;
 mod unexport;
 mod unstable;
 mod usage;
+mod warnings;
+#[cfg(unix)]
+mod watch;
 mod which_function;
 #[cfg(windows)]
 mod windows;
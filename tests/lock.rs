@@ -0,0 +1,324 @@
+use super::*;
+
+#[test]
+fn exclusive_locks_serialize_recipes() {
+  Test::new()
+    .justfile(
+      "
+        [parallel]
+        foo: a b c
+
+        [lock('db')]
+        a:
+          @test ! -f running
+          @touch running
+          @sleep 0.1
+          @rm running
+
+        [lock('db')]
+        b:
+          @test ! -f running
+          @touch running
+          @sleep 0.1
+          @rm running
+
+        [lock('db')]
+        c:
+          @test ! -f running
+          @touch running
+          @sleep 0.1
+          @rm running
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn different_locks_do_not_serialize_recipes() {
+  Test::new()
+    .justfile(
+      "
+        [parallel]
+        foo: a b
+
+        [lock('db')]
+        [timeout('5s')]
+        a:
+          @touch a
+          @while [ ! -f b ]; do sleep 0.01; done
+
+        [lock('port')]
+        [timeout('5s')]
+        b:
+          @touch b
+          @while [ ! -f a ]; do sleep 0.01; done
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn shared_locks_do_not_serialize_recipes() {
+  Test::new()
+    .justfile(
+      "
+        [parallel]
+        foo: a b
+
+        [lock('db', shared)]
+        [timeout('5s')]
+        a:
+          @touch a
+          @while [ ! -f b ]; do sleep 0.01; done
+
+        [lock('db', shared)]
+        [timeout('5s')]
+        b:
+          @touch b
+          @while [ ! -f a ]; do sleep 0.01; done
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn shared_locks_exclude_exclusive_locks() {
+  Test::new()
+    .justfile(
+      "
+        [parallel]
+        foo: reader writer
+
+        [lock('db', shared)]
+        reader:
+          @test ! -f writing
+          @touch reading
+          @sleep 0.1
+          @rm reading
+
+        [lock('db')]
+        writer:
+          @test ! -f reading
+          @touch writing
+          @sleep 0.1
+          @rm writing
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn recipes_may_hold_multiple_locks() {
+  Test::new()
+    .justfile(
+      "
+        [parallel]
+        foo: a b
+
+        [lock('db')]
+        [lock('port')]
+        a:
+          @test ! -f running
+          @touch running
+          @sleep 0.1
+          @rm running
+
+        [lock('port')]
+        b:
+          @test ! -f running
+          @touch running
+          @sleep 0.1
+          @rm running
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn locks_serialize_parallel_invocations() {
+  Test::new()
+    .arg("--parallel")
+    .args(["a", "b"])
+    .justfile(
+      "
+        [lock('db')]
+        a:
+          @test ! -f running
+          @touch running
+          @sleep 0.1
+          @rm running
+
+        [lock('db')]
+        b:
+          @test ! -f running
+          @touch running
+          @sleep 0.1
+          @rm running
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn locks_are_released_on_failure() {
+  Test::new()
+    .args(["--keep-going", "--parallel", "a", "b"])
+    .justfile(
+      "
+        [lock('db')]
+        a:
+          @exit 1
+
+        [lock('db')]
+        b:
+          @exit 1
+      ",
+    )
+    .stderr(
+      "
+        error: recipe `a` failed on line 3 with exit code 1
+        error: recipe `b` failed on line 7 with exit code 1
+        error: 2 recipes failed:
+          `a` with exit code 1
+          `b` with exit code 1
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn global_locks_serialize_processes() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          @{{ quote(just_executable()) }} bar & {{ quote(just_executable()) }} bar; wait
+
+        [lock('db', global)]
+        bar:
+          @test ! -f running
+          @touch running
+          @sleep 0.1
+          @rm running
+          @echo done
+      ",
+    )
+    .stdout("done\ndone\n")
+    .success();
+}
+
+#[test]
+fn global_lock_files_are_stored_in_cache_directory() {
+  let tempdir = tempdir();
+
+  Test::with_tempdir(tempdir)
+    .justfile(
+      "
+        [lock('db', global)]
+        foo:
+          @test -d .justcache/locks
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn locks_are_not_acquired_in_dry_run() {
+  Test::new()
+    .arg("--dry-run")
+    .justfile(
+      "
+        [lock('db', global)]
+        foo:
+          echo foo
+      ",
+    )
+    .stderr("echo foo\n")
+    .success();
+}
+
+#[test]
+fn lock_name_is_required() {
+  Test::new()
+    .justfile(
+      "
+        [lock]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: attribute `lock` got 0 arguments but takes 1 argument
+         ——▶ justfile:1:2
+          │
+        1 │ [lock]
+          │  ^^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn lock_flags_take_no_value() {
+  Test::new()
+    .justfile(
+      "
+        [lock('db', shared='true')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: attribute key `shared` takes no value
+         ——▶ justfile:1:13
+          │
+        1 │ [lock('db', shared='true')]
+          │             ^^^^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn unknown_lock_key() {
+  Test::new()
+    .justfile(
+      "
+        [lock('db', exclusive)]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: unknown key `exclusive` for `lock` attribute
+         ——▶ justfile:1:13
+          │
+        1 │ [lock('db', exclusive)]
+          │             ^^^^^^^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn lock_attribute_is_formatted() {
+  Test::new()
+    .arg("--dump")
+    .justfile(
+      "
+        [lock('db', shared, global)]
+        [lock('port')]
+        foo:
+          echo foo
+      ",
+    )
+    .stdout(
+      "
+        [lock('port')]
+        [lock('db', global, shared)]
+        foo:
+            echo foo
+      ",
+    )
+    .success();
+}
@@ -0,0 +1,901 @@
+use super::*;
+
+fn uri(test: &Test, path: &str) -> String {
+  let path = test.tempdir.path().join(path);
+
+  if cfg!(windows) {
+    format!("file:///{}", path.display().to_string().replace('\\', "/"))
+  } else {
+    format!("file://{}", path.display())
+  }
+}
+
+fn request(id: u64, method: &str, params: Value) -> Value {
+  json!({
+    "id": id,
+    "jsonrpc": "2.0",
+    "method": method,
+    "params": params,
+  })
+}
+
+fn notification(method: &str, params: Value) -> Value {
+  json!({
+    "jsonrpc": "2.0",
+    "method": method,
+    "params": params,
+  })
+}
+
+fn position(uri: &str, line: u64, character: u64) -> Value {
+  json!({
+    "position": {
+      "character": character,
+      "line": line,
+    },
+    "textDocument": {
+      "uri": uri,
+    },
+  })
+}
+
+fn range(start: (u64, u64), end: (u64, u64)) -> Value {
+  json!({
+    "end": {
+      "character": end.1,
+      "line": end.0,
+    },
+    "start": {
+      "character": start.1,
+      "line": start.0,
+    },
+  })
+}
+
+// Send `messages` to `just --lsp` and return the messages it sends back.
+#[track_caller]
+fn session(test: Test, messages: &[Value]) -> Vec<Value> {
+  let mut stdin = String::new();
+
+  for message in messages {
+    let content = message.to_string();
+    stdin.push_str(&format!(
+      "Content-Length: {}\r\n\r\n{content}",
+      content.len()
+    ));
+  }
+
+  let output = test
+    .arg("--lsp")
+    .stdin(stdin)
+    .stdout_regex("(?s).*")
+    .success();
+
+  let mut responses = Vec::new();
+  let mut rest = output.stdout.as_str();
+
+  while !rest.is_empty() {
+    let (header, body) = rest.split_once("\r\n\r\n").unwrap();
+    let length = header
+      .strip_prefix("Content-Length: ")
+      .unwrap()
+      .parse::<usize>()
+      .unwrap();
+    responses.push(serde_json::from_str(&body[..length]).unwrap());
+    rest = &body[length..];
+  }
+
+  responses
+}
+
+#[track_caller]
+fn result(test: Test, method: &str, params: Value) -> Value {
+  let mut responses = session(test, &[request(1, method, params)]);
+  assert_eq!(responses.len(), 1);
+  let mut response = responses.pop().unwrap();
+  assert_eq!(response["id"], 1);
+  response["result"].take()
+}
+
+#[test]
+fn initialize() {
+  let responses = session(
+    Test::new(),
+    &[
+      request(1, "initialize", json!({})),
+      notification("initialized", json!({})),
+      request(2, "shutdown", Value::Null),
+      notification("exit", Value::Null),
+    ],
+  );
+
+  assert_eq!(
+    responses,
+    [
+      json!({
+        "id": 1,
+        "jsonrpc": "2.0",
+        "result": {
+          "capabilities": {
+            "completionProvider": {},
+            "definitionProvider": true,
+            "documentSymbolProvider": true,
+            "hoverProvider": true,
+            "textDocumentSync": {
+              "change": 1,
+              "openClose": true,
+              "save": {
+                "includeText": true,
+              },
+            },
+          },
+          "serverInfo": {
+            "name": "just",
+            "version": env!("CARGO_PKG_VERSION"),
+          },
+        },
+      }),
+      json!({
+        "id": 2,
+        "jsonrpc": "2.0",
+        "result": null,
+      }),
+    ],
+  );
+}
+
+#[test]
+fn unknown_method() {
+  let responses = session(Test::new(), &[request(1, "foo", json!({}))]);
+
+  assert_eq!(
+    responses,
+    [json!({
+      "error": {
+        "code": -32601,
+        "message": "unknown method `foo`",
+      },
+      "id": 1,
+      "jsonrpc": "2.0",
+    })],
+  );
+}
+
+#[test]
+fn unknown_notifications_are_ignored() {
+  let responses = session(Test::new(), &[notification("foo", json!({}))]);
+
+  assert_eq!(responses, [] as [Value; 0]);
+}
+
+#[test]
+fn parse_error() {
+  let responses = Test::new()
+    .arg("--lsp")
+    .stdin("Content-Length: 3\r\n\r\n{{{")
+    .stdout_regex("(?s).*")
+    .success()
+    .stdout;
+
+  let (_header, body) = responses.split_once("\r\n\r\n").unwrap();
+
+  let response = serde_json::from_str::<Value>(body).unwrap();
+
+  assert_eq!(response["id"], Value::Null);
+  assert_eq!(response["error"]["code"], -32700);
+}
+
+#[test]
+fn diagnostics_on_open() {
+  let test = Test::new().justfile("foo: bar\n");
+  let uri = uri(&test, "justfile");
+
+  let responses = session(
+    test,
+    &[notification(
+      "textDocument/didOpen",
+      json!({
+        "textDocument": {
+          "languageId": "just",
+          "text": "foo: bar\n",
+          "uri": uri,
+          "version": 1,
+        },
+      }),
+    )],
+  );
+
+  assert_eq!(
+    responses,
+    [json!({
+      "jsonrpc": "2.0",
+      "method": "textDocument/publishDiagnostics",
+      "params": {
+        "diagnostics": [
+          {
+            "message": "recipe `foo` has unknown dependency `bar`",
+            "range": range((0, 5), (0, 8)),
+            "severity": 1,
+            "source": "just",
+          },
+        ],
+        "uri": uri,
+      },
+    })],
+  );
+}
+
+#[test]
+fn diagnostics_on_save() {
+  let test = Test::new().justfile(
+    "
+      foo:
+
+      _bar:
+    ",
+  );
+  let uri = uri(&test, "justfile");
+
+  let responses = session(
+    test,
+    &[notification(
+      "textDocument/didSave",
+      json!({
+        "textDocument": {
+          "uri": uri,
+        },
+      }),
+    )],
+  );
+
+  assert_eq!(
+    responses,
+    [json!({
+      "jsonrpc": "2.0",
+      "method": "textDocument/publishDiagnostics",
+      "params": {
+        "diagnostics": [
+          {
+            "code": "unused-private-recipe",
+            "message": "private recipe `_bar` is never used",
+            "range": range((2, 0), (2, 4)),
+            "severity": 2,
+            "source": "just",
+          },
+        ],
+        "uri": uri,
+      },
+    })],
+  );
+}
+
+#[test]
+fn fixed_diagnostics_are_cleared() {
+  let test = Test::new().justfile("foo: bar\n");
+  let uri = uri(&test, "justfile");
+
+  let responses = session(
+    test,
+    &[
+      notification(
+        "textDocument/didSave",
+        json!({
+          "textDocument": {
+            "uri": uri,
+          },
+        }),
+      ),
+      notification(
+        "textDocument/didSave",
+        json!({
+          "text": "foo:\n",
+          "textDocument": {
+            "uri": uri,
+          },
+        }),
+      ),
+    ],
+  );
+
+  assert_eq!(responses.len(), 2);
+  assert_eq!(
+    responses[0]["params"]["diagnostics"]
+      .as_array()
+      .unwrap()
+      .len(),
+    1
+  );
+  assert_eq!(responses[1]["params"]["diagnostics"], json!([]));
+  assert_eq!(responses[1]["params"]["uri"], uri);
+}
+
+#[test]
+fn diagnostics_in_imported_files() {
+  let test = Test::new()
+    .justfile("import 'foo.just'\n")
+    .write("foo.just", "bar: baz\n");
+  let justfile = uri(&test, "justfile");
+  let foo = uri(&test, "foo.just");
+
+  let responses = session(
+    test,
+    &[notification(
+      "textDocument/didSave",
+      json!({
+        "textDocument": {
+          "uri": justfile,
+        },
+      }),
+    )],
+  );
+
+  assert_eq!(
+    responses,
+    [
+      json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": {
+          "diagnostics": [
+            {
+              "message": "recipe `bar` has unknown dependency `baz`",
+              "range": range((0, 5), (0, 8)),
+              "severity": 1,
+              "source": "just",
+            },
+          ],
+          "uri": foo,
+        },
+      }),
+      json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": {
+          "diagnostics": [],
+          "uri": justfile,
+        },
+      }),
+    ],
+  );
+}
+
+#[test]
+fn imported_files_are_compiled_with_their_justfile() {
+  let test = Test::new()
+    .justfile(
+      "
+        import 'foo.just'
+
+        bar := 'bar'
+      ",
+    )
+    .write("foo.just", "baz:\n  echo {{ bar }}\n");
+  let foo = uri(&test, "foo.just");
+
+  let responses = session(
+    test,
+    &[notification(
+      "textDocument/didSave",
+      json!({
+        "textDocument": {
+          "uri": foo,
+        },
+      }),
+    )],
+  );
+
+  assert_eq!(
+    responses,
+    [json!({
+      "jsonrpc": "2.0",
+      "method": "textDocument/publishDiagnostics",
+      "params": {
+        "diagnostics": [],
+        "uri": foo,
+      },
+    })],
+  );
+}
+
+#[test]
+fn definition_of_dependency() {
+  let test = Test::new().justfile(
+    "
+      foo: bar
+
+      bar:
+    ",
+  );
+  let uri = uri(&test, "justfile");
+
+  assert_eq!(
+    result(test, "textDocument/definition", position(&uri, 0, 6)),
+    json!({
+      "range": range((2, 0), (2, 3)),
+      "uri": uri,
+    }),
+  );
+}
+
+#[test]
+fn definition_of_recipe_in_submodule() {
+  let test = Test::new()
+    .justfile(
+      "
+        mod foo
+
+        bar: foo::baz
+      ",
+    )
+    .write("foo.just", "baz:\n");
+  let justfile = uri(&test, "justfile");
+  let foo = uri(&test, "foo.just");
+
+  let responses = session(
+    test,
+    &[
+      request(1, "textDocument/definition", position(&justfile, 2, 11)),
+      request(2, "textDocument/definition", position(&justfile, 2, 6)),
+      request(3, "textDocument/definition", position(&justfile, 0, 5)),
+    ],
+  );
+
+  assert_eq!(
+    responses[0]["result"],
+    json!({
+      "range": range((0, 0), (0, 3)),
+      "uri": foo,
+    }),
+  );
+
+  for response in &responses[1..] {
+    assert_eq!(
+      response["result"],
+      json!({
+        "range": range((0, 0), (0, 0)),
+        "uri": foo,
+      }),
+    );
+  }
+}
+
+#[test]
+fn definition_of_import() {
+  let test = Test::new()
+    .justfile("import 'foo.just'\n")
+    .write("foo.just", "");
+  let justfile = uri(&test, "justfile");
+  let foo = uri(&test, "foo.just");
+
+  assert_eq!(
+    result(test, "textDocument/definition", position(&justfile, 0, 10)),
+    json!({
+      "range": range((0, 0), (0, 0)),
+      "uri": foo,
+    }),
+  );
+}
+
+#[test]
+fn definition_of_variable() {
+  let test = Test::new().justfile(
+    "
+      foo := 'foo'
+
+      bar := foo + 'bar'
+
+      baz:
+        echo {{ bar }}
+    ",
+  );
+  let uri = uri(&test, "justfile");
+
+  let responses = session(
+    test,
+    &[
+      request(1, "textDocument/definition", position(&uri, 2, 7)),
+      request(2, "textDocument/definition", position(&uri, 5, 10)),
+    ],
+  );
+
+  assert_eq!(
+    responses[0]["result"],
+    json!({
+      "range": range((0, 0), (0, 3)),
+      "uri": uri,
+    }),
+  );
+
+  assert_eq!(
+    responses[1]["result"],
+    json!({
+      "range": range((2, 0), (2, 3)),
+      "uri": uri,
+    }),
+  );
+}
+
+#[test]
+fn definition_of_variable_in_imported_file() {
+  let test = Test::new()
+    .justfile(
+      "
+        import 'foo.just'
+
+        bar:
+          echo {{ foo }}
+      ",
+    )
+    .write("foo.just", "foo := 'foo'\n");
+  let justfile = uri(&test, "justfile");
+  let foo = uri(&test, "foo.just");
+
+  assert_eq!(
+    result(test, "textDocument/definition", position(&justfile, 3, 11)),
+    json!({
+      "range": range((0, 0), (0, 3)),
+      "uri": foo,
+    }),
+  );
+}
+
+#[test]
+fn definition_of_parameter() {
+  let test = Test::new().justfile(
+    "
+      foo := 'foo'
+
+      bar foo: (baz foo)
+        echo {{ foo }}
+
+      baz x:
+        echo {{ x }}
+    ",
+  );
+  let uri = uri(&test, "justfile");
+
+  let responses = session(
+    test,
+    &[
+      request(1, "textDocument/definition", position(&uri, 2, 15)),
+      request(2, "textDocument/definition", position(&uri, 3, 10)),
+    ],
+  );
+
+  for response in responses {
+    assert_eq!(
+      response["result"],
+      json!({
+        "range": range((2, 4), (2, 7)),
+        "uri": uri,
+      }),
+    );
+  }
+}
+
+#[test]
+fn definition_of_function() {
+  let test = Test::new().justfile(
+    "
+      set unstable
+
+      foo(x) := x + 'foo'
+
+      bar := foo('bar') + uppercase('bar')
+    ",
+  );
+  let uri = uri(&test, "justfile");
+
+  let responses = session(
+    test,
+    &[
+      request(1, "textDocument/definition", position(&uri, 4, 8)),
+      request(2, "textDocument/definition", position(&uri, 2, 10)),
+      request(3, "textDocument/definition", position(&uri, 4, 22)),
+    ],
+  );
+
+  assert_eq!(
+    responses[0]["result"],
+    json!({
+      "range": range((2, 0), (2, 3)),
+      "uri": uri,
+    }),
+  );
+
+  assert_eq!(
+    responses[1]["result"],
+    json!({
+      "range": range((2, 4), (2, 5)),
+      "uri": uri,
+    }),
+  );
+
+  assert_eq!(responses[2]["result"], Value::Null);
+}
+
+#[test]
+fn definition_uses_unsaved_changes() {
+  let test = Test::new().justfile("foo:\n");
+  let uri = uri(&test, "justfile");
+
+  let responses = session(
+    test,
+    &[
+      notification(
+        "textDocument/didOpen",
+        json!({
+          "textDocument": {
+            "languageId": "just",
+            "text": "foo:\n",
+            "uri": uri,
+            "version": 1,
+          },
+        }),
+      ),
+      notification(
+        "textDocument/didChange",
+        json!({
+          "contentChanges": [
+            {
+              "text": "foo: bar\n\nbar:\n",
+            },
+          ],
+          "textDocument": {
+            "uri": uri,
+            "version": 2,
+          },
+        }),
+      ),
+      request(1, "textDocument/definition", position(&uri, 0, 6)),
+    ],
+  );
+
+  assert_eq!(
+    responses[1]["result"],
+    json!({
+      "range": range((2, 0), (2, 3)),
+      "uri": uri,
+    }),
+  );
+}
+
+#[test]
+fn definition_outside_of_name() {
+  let test = Test::new().justfile("foo:\n  echo foo\n");
+  let uri = uri(&test, "justfile");
+
+  assert_eq!(
+    result(test, "textDocument/definition", position(&uri, 1, 4)),
+    Value::Null,
+  );
+}
+
+#[test]
+fn positions_are_utf16() {
+  let test = Test::new().justfile(
+    "
+      foo := '😀' + bar
+
+      bar := 'bar'
+    ",
+  );
+  let uri = uri(&test, "justfile");
+
+  assert_eq!(
+    result(test, "textDocument/hover", position(&uri, 0, 15)),
+    json!({
+      "contents": {
+        "kind": "markdown",
+        "value": "```just\nbar := 'bar'\n```",
+      },
+      "range": range((0, 14), (0, 17)),
+    }),
+  );
+}
+
+#[test]
+fn hover_recipe() {
+  let test = Test::new().justfile(
+    "
+      # build the project
+      build target='debug' *flags:
+
+      [doc('test the project')]
+      test: build
+
+      bench: test
+    ",
+  );
+  let uri = uri(&test, "justfile");
+
+  let responses = session(
+    test,
+    &[
+      request(1, "textDocument/hover", position(&uri, 1, 2)),
+      request(2, "textDocument/hover", position(&uri, 4, 8)),
+      request(3, "textDocument/hover", position(&uri, 6, 8)),
+    ],
+  );
+
+  assert_eq!(
+    responses[0]["result"]["contents"]["value"],
+    "```just\nbuild target='debug' *flags\n```\n\nbuild the project",
+  );
+
+  assert_eq!(
+    responses[1]["result"]["contents"]["value"],
+    "```just\nbuild target='debug' *flags\n```\n\nbuild the project",
+  );
+
+  assert_eq!(
+    responses[2]["result"]["contents"]["value"],
+    "```just\ntest\n```\n\ntest the project",
+  );
+}
+
+#[test]
+fn hover_builtin_function() {
+  let test = Test::new().justfile("foo := uppercase('foo')\n");
+  let uri = uri(&test, "justfile");
+
+  assert_eq!(
+    result(test, "textDocument/hover", position(&uri, 0, 10)),
+    json!({
+      "contents": {
+        "kind": "markdown",
+        "value": "```just\nuppercase()\n```\n\nbuilt-in function",
+      },
+      "range": range((0, 7), (0, 16)),
+    }),
+  );
+}
+
+#[test]
+fn hover_module() {
+  let test = Test::new()
+    .justfile(
+      "
+        # tools for foo
+        mod foo
+      ",
+    )
+    .write("foo.just", "");
+  let uri = uri(&test, "justfile");
+
+  assert_eq!(
+    result(test, "textDocument/hover", position(&uri, 1, 5))["contents"]["value"],
+    "```just\nmod foo\n```\n\ntools for foo",
+  );
+}
+
+#[test]
+fn completion_of_functions() {
+  let test = Test::new().justfile("foo := \n");
+  let uri = uri(&test, "justfile");
+
+  let items = result(test, "textDocument/completion", position(&uri, 0, 7));
+  let items = items.as_array().unwrap();
+
+  assert!(items.contains(&json!({
+    "detail": "built-in function",
+    "kind": 3,
+    "label": "uppercase",
+  })));
+
+  assert!(items.iter().all(|item| item["kind"] == 3));
+}
+
+#[test]
+fn completion_of_attributes() {
+  let test = Test::new().justfile("[pri\nfoo:\n");
+  let uri = uri(&test, "justfile");
+
+  let items = result(test, "textDocument/completion", position(&uri, 0, 4));
+  let items = items.as_array().unwrap();
+
+  assert!(items.contains(&json!({
+    "detail": "attribute",
+    "kind": 14,
+    "label": "private",
+  })));
+
+  assert!(items.contains(&json!({
+    "detail": "attribute",
+    "kind": 14,
+    "label": "working-directory",
+  })));
+
+  assert!(items.iter().all(|item| item["kind"] == 14));
+}
+
+#[test]
+fn completion_of_settings() {
+  let test = Test::new().justfile("set \n");
+  let uri = uri(&test, "justfile");
+
+  let items = result(test, "textDocument/completion", position(&uri, 0, 4));
+  let items = items.as_array().unwrap();
+
+  assert!(items.contains(&json!({
+    "detail": "setting",
+    "kind": 10,
+    "label": "dotenv-load",
+  })));
+
+  assert!(items.iter().all(|item| item["kind"] == 10));
+}
+
+#[test]
+fn document_symbols() {
+  let test = Test::new()
+    .justfile(
+      "
+        mod foo
+
+        bar := 'bar'
+
+        baz:
+
+        alias b := baz
+      ",
+    )
+    .write("foo.just", "");
+  let uri = uri(&test, "justfile");
+
+  assert_eq!(
+    result(
+      test,
+      "textDocument/documentSymbol",
+      json!({"textDocument": {"uri": uri}}),
+    ),
+    json!([
+      {
+        "detail": "module",
+        "kind": 2,
+        "name": "foo",
+        "range": range((0, 4), (0, 7)),
+        "selectionRange": range((0, 4), (0, 7)),
+      },
+      {
+        "detail": "assignment",
+        "kind": 13,
+        "name": "bar",
+        "range": range((2, 0), (2, 3)),
+        "selectionRange": range((2, 0), (2, 3)),
+      },
+      {
+        "detail": "recipe",
+        "kind": 12,
+        "name": "baz",
+        "range": range((4, 0), (4, 3)),
+        "selectionRange": range((4, 0), (4, 3)),
+      },
+      {
+        "detail": "alias",
+        "kind": 12,
+        "name": "b",
+        "range": range((6, 6), (6, 7)),
+        "selectionRange": range((6, 6), (6, 7)),
+      },
+    ]),
+  );
+}
+
+#[test]
+fn document_symbols_do_not_require_valid_justfile() {
+  let test = Test::new().justfile("foo: bar\n");
+  let uri = uri(&test, "justfile");
+
+  assert_eq!(
+    result(
+      test,
+      "textDocument/documentSymbol",
+      json!({"textDocument": {"uri": uri}}),
+    )
+    .as_array()
+    .unwrap()
+    .len(),
+    1,
+  );
+}
@@ -0,0 +1,642 @@
+use super::*;
+
+#[test]
+fn map_literals_are_maps() {
+  assert_list(r#"{"a": "b", "c": "d"}"#, r#"{"a": "b", "c": "d"}"#);
+}
+
+#[test]
+fn map_literals_are_sorted_by_key() {
+  assert_list(r#"{"c": "d", "a": "b"}"#, r#"{"a": "b", "c": "d"}"#);
+}
+
+#[test]
+fn map_literals_may_have_trailing_comma() {
+  assert_list(r#"{"a": "b",}"#, r#"{"a": "b"}"#);
+}
+
+#[test]
+fn map_literals_may_span_lines() {
+  Test::new()
+    .justfile(
+      r#"
+        set lists
+
+        x := {
+          "a": "b",
+          "c": "d",
+        }
+      "#,
+    )
+    .unstable()
+    .args(["--evaluate", "x"])
+    .stdout(r#"{"a": "b", "c": "d"}"#)
+    .unindent_stdout(false)
+    .success();
+}
+
+#[test]
+fn map_keys_and_values_may_be_expressions() {
+  assert_list(
+    r#"{"a" + "b": uppercase("c"), join("d", "e"): 'f' / 'g'}"#,
+    r#"{"ab": "C", "d/e": "f/g"}"#,
+  );
+}
+
+#[test]
+fn empty_map_literal_is_falsy() {
+  Test::new()
+    .justfile(
+      r#"
+        set lists
+
+        foo:
+          @echo {{ {} || "fallback" }}
+      "#,
+    )
+    .unstable()
+    .stdout("fallback\n")
+    .success();
+}
+
+#[test]
+fn map_literals_require_lists_setting() {
+  Test::new()
+    .justfile(
+      r#"
+        x := {"a": "b"}
+      "#,
+    )
+    .stderr(
+      r#"
+        error: map literals require `set lists`
+         ——▶ justfile:1:6
+          │
+        1 │ x := {"a": "b"}
+          │      ^
+      "#,
+    )
+    .failure();
+}
+
+#[test]
+fn duplicate_keys_are_an_error() {
+  Test::new()
+    .justfile(
+      r#"
+        set lists
+
+        x := {"a": "b", "a": "c"}
+
+        foo:
+          @echo {{ x }}
+      "#,
+    )
+    .unstable()
+    .stderr(
+      r#"
+        error: map has duplicate key "a"
+         ——▶ justfile:3:6
+          │
+        3 │ x := {"a": "b", "a": "c"}
+          │      ^
+      "#,
+    )
+    .failure();
+}
+
+#[test]
+fn map_values_must_be_strings() {
+  Test::new()
+    .justfile(
+      r#"
+        set lists
+
+        x := {"a": ["b", "c"]}
+
+        foo:
+          @echo {{ x }}
+      "#,
+    )
+    .unstable()
+    .stderr(
+      r#"
+        error: list value ["b", "c"] used as map value
+         ——▶ justfile:3:6
+          │
+        3 │ x := {"a": ["b", "c"]}
+          │      ^
+      "#,
+    )
+    .failure();
+}
+
+#[test]
+fn map_keys_must_be_strings() {
+  Test::new()
+    .justfile(
+      r#"
+        set lists
+
+        x := {[]: "a"}
+
+        foo:
+          @echo {{ x }}
+      "#,
+    )
+    .unstable()
+    .stderr(
+      r#"
+        error: list value [] used as map key
+         ——▶ justfile:3:6
+          │
+        3 │ x := {[]: "a"}
+          │      ^
+      "#,
+    )
+    .failure();
+}
+
+#[test]
+fn index() {
+  assert_list(r#"{"a": "b", "c": "d"}["c"]"#, r#""d""#);
+}
+
+#[test]
+fn index_with_expression() {
+  Test::new()
+    .justfile(
+      r#"
+        set lists
+
+        triples := {
+          "linux": "x86_64-unknown-linux-gnu",
+          "macos": "aarch64-apple-darwin",
+          "windows": "x86_64-pc-windows-msvc",
+        }
+
+        target := "mac"
+
+        foo:
+          @echo {{ triples[target + "os"] }}
+      "#,
+    )
+    .unstable()
+    .stdout("aarch64-apple-darwin\n")
+    .success();
+}
+
+#[test]
+fn index_with_function_call() {
+  assert_list(r#"{os(): "a", "b": "c"}[os()]"#, r#""a""#);
+}
+
+#[test]
+fn index_in_parameter_default() {
+  Test::new()
+    .justfile(
+      r#"
+        set lists
+
+        triples := {"a": "b"}
+
+        foo target=triples["a"]:
+          @echo {{ target }}
+      "#,
+    )
+    .unstable()
+    .stdout("b\n")
+    .success();
+}
+
+#[test]
+fn index_must_immediately_follow_value() {
+  Test::new()
+    .justfile(
+      r#"
+        set lists
+
+        foo: (bar "a" ["b"])
+
+        bar x y:
+          @echo {{ x }} {{ y }}
+      "#,
+    )
+    .unstable()
+    .stdout("a b\n")
+    .success();
+}
+
+#[test]
+fn index_requires_lists_setting() {
+  Test::new()
+    .justfile(
+      r#"
+        x := "a"["b"]
+      "#,
+    )
+    .stderr(
+      r#"
+        error: index operator requires `set lists`
+         ——▶ justfile:1:9
+          │
+        1 │ x := "a"["b"]
+          │         ^
+      "#,
+    )
+    .failure();
+}
+
+#[test]
+fn missing_key_is_an_error() {
+  Test::new()
+    .justfile(
+      r#"
+        set lists
+
+        x := {"a": "b"}
+
+        foo:
+          @echo {{ x["c"] }}
+      "#,
+    )
+    .unstable()
+    .stderr(
+      r#"
+        error: map has no key "c"
+         ——▶ justfile:6:13
+          │
+        6 │   @echo {{ x["c"] }}
+          │             ^
+      "#,
+    )
+    .failure();
+}
+
+#[test]
+fn indexing_lists_is_an_error() {
+  Test::new()
+    .justfile(
+      r#"
+        set lists
+
+        foo:
+          @echo {{ ["a", "b"]["a"] }}
+      "#,
+    )
+    .unstable()
+    .stderr(
+      r#"
+        error: list value ["a", "b"] cannot be indexed
+         ——▶ justfile:4:22
+          │
+        4 │   @echo {{ ["a", "b"]["a"] }}
+          │                      ^
+      "#,
+    )
+    .failure();
+}
+
+#[test]
+fn index_must_be_string() {
+  Test::new()
+    .justfile(
+      r#"
+        set lists
+
+        foo:
+          @echo {{ {"a": "b"}[["a", "b"]] }}
+      "#,
+    )
+    .unstable()
+    .stderr(
+      r#"
+        error: list value ["a", "b"] used as index
+         ——▶ justfile:4:22
+          │
+        4 │   @echo {{ {"a": "b"}[["a", "b"]] }}
+          │                      ^
+      "#,
+    )
+    .failure();
+}
+
+#[test]
+fn maps_may_not_be_used_as_strings() {
+  Test::new()
+    .justfile(
+      r#"
+        set lists
+
+        foo:
+          @echo {{ uppercase({"a": "b"}) }}
+      "#,
+    )
+    .unstable()
+    .stderr(
+      r#"
+        error: map value {"a": "b"} passed to `uppercase()`
+        the behavior of lists with many built-in functions is undecided
+        see https://github.com/casey/just#lists
+         ——▶ justfile:4:12
+          │
+        4 │   @echo {{ uppercase({"a": "b"}) }}
+          │            ^^^^^^^^^
+      "#,
+    )
+    .failure();
+}
+
+#[test]
+fn keys() {
+  assert_list(r#"keys({"c": "d", "a": "b"})"#, r#"["a", "c"]"#);
+}
+
+#[test]
+fn values() {
+  assert_list(r#"values({"c": "d", "a": "b"})"#, r#"["b", "d"]"#);
+}
+
+#[test]
+fn keys_and_values_of_empty_map() {
+  assert_list("keys({}) ++ values({})", "[]");
+}
+
+#[test]
+fn keys_of_list_is_an_error() {
+  Test::new()
+    .justfile(
+      r#"
+        set lists
+
+        foo:
+          @echo {{ keys(["a"]) }}
+      "#,
+    )
+    .unstable()
+    .stderr(
+      r#"
+        error: call to function `keys` failed: value "a" is not a map
+         ——▶ justfile:4:12
+          │
+        4 │   @echo {{ keys(["a"]) }}
+          │            ^^^^
+      "#,
+    )
+    .failure();
+}
+
+#[test]
+fn keys_requires_lists_setting() {
+  Test::new()
+    .justfile(
+      "
+        x := keys('a')
+      ",
+    )
+    .stderr(
+      "
+        error: the `keys()` function requires `set lists`
+         ——▶ justfile:1:6
+          │
+        1 │ x := keys('a')
+          │      ^^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn values_requires_lists_setting() {
+  Test::new()
+    .justfile(
+      "
+        x := values('a')
+      ",
+    )
+    .stderr(
+      "
+        error: the `values()` function requires `set lists`
+         ——▶ justfile:1:6
+          │
+        1 │ x := values('a')
+          │      ^^^^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn len_of_map_is_number_of_entries() {
+  assert_list(r#"len({"a": "b", "c": "d"})"#, r#""2""#);
+}
+
+#[test]
+fn maps_may_not_be_list_concatenated() {
+  Test::new()
+    .justfile(
+      r#"
+        set lists
+
+        foo:
+          @echo {{ ["x"] ++ {"a": "b"} }}
+      "#,
+    )
+    .unstable()
+    .stderr(
+      r#"
+        error: operator `++` cannot be applied to map value {"a": "b"}
+         ——▶ justfile:4:18
+          │
+        4 │   @echo {{ ["x"] ++ {"a": "b"} }}
+          │                  ^^
+      "#,
+    )
+    .failure();
+}
+
+#[test]
+fn maps_may_not_be_concatenated() {
+  Test::new()
+    .justfile(
+      r#"
+        set lists
+
+        foo:
+          @echo {{ {"a": "b"} + "c" }}
+      "#,
+    )
+    .unstable()
+    .stderr(
+      r#"
+        error: operator `+` cannot be applied to map value {"a": "b"}
+         ——▶ justfile:4:23
+          │
+        4 │   @echo {{ {"a": "b"} + "c" }}
+          │                       ^
+      "#,
+    )
+    .failure();
+}
+
+#[test]
+fn maps_may_not_be_joined() {
+  Test::new()
+    .justfile(
+      r#"
+        set lists
+
+        foo:
+          @echo {{ / {"a": "b"} }}
+      "#,
+    )
+    .unstable()
+    .stderr(
+      r#"
+        error: operator `/` cannot be applied to map value {"a": "b"}
+         ——▶ justfile:4:12
+          │
+        4 │   @echo {{ / {"a": "b"} }}
+          │            ^
+      "#,
+    )
+    .failure();
+}
+
+#[test]
+fn maps_are_skipped_when_evaluated_as_shell() {
+  Test::new()
+    .justfile(
+      r#"
+        set lists
+
+        w := 'a'
+        x := {"a": "b"}
+        y := 'c'
+      "#,
+    )
+    .unstable()
+    .args(["--evaluate", "--evaluate-format", "shell"])
+    .stdout("w=\"a\"\ny=\"c\"\n")
+    .success();
+}
+
+#[test]
+fn maps_are_interpolated_as_keys() {
+  Test::new()
+    .justfile(
+      r#"
+        set lists
+
+        foo:
+          @echo {{ {"b": "c", "a": "d"} }}
+      "#,
+    )
+    .unstable()
+    .stdout("a b\n")
+    .success();
+}
+
+#[test]
+fn maps_are_compared_by_entries() {
+  assert_list(r#"{"a": "b"} == {"a": "b"}"#, r#""true""#);
+  assert_list(r#"{"a": "b"} == {"a": "c"}"#, "[]");
+  assert_list(r#"{"a": "b"} == ["a"]"#, "[]");
+}
+
+#[test]
+fn mapped_dependencies_iterate_over_keys() {
+  Test::new()
+    .justfile(
+      r#"
+        set lists
+
+        triples := {
+          "linux": "x86_64-unknown-linux-gnu",
+          "macos": "aarch64-apple-darwin",
+        }
+
+        foo: *(build *triples)
+
+        build target:
+          @echo {{ target }}: {{ triples[target] }}
+      "#,
+    )
+    .unstable()
+    .stdout(
+      "
+        linux: x86_64-unknown-linux-gnu
+        macos: aarch64-apple-darwin
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn maps_may_be_passed_to_dependencies() {
+  Test::new()
+    .justfile(
+      r#"
+        set lists
+
+        foo: (bar {"a": "b"})
+
+        bar map:
+          @echo {{ map["a"] }}
+      "#,
+    )
+    .unstable()
+    .stdout("b\n")
+    .success();
+}
+
+#[test]
+fn evaluate_prints_maps() {
+  Test::new()
+    .justfile(
+      r#"
+        set lists
+
+        x := {"b": "c", "a": "d"}
+        y := "foo"
+      "#,
+    )
+    .unstable()
+    .arg("--evaluate")
+    .stdout(
+      r#"
+        x := {"a": "d", "b": "c"}
+        y := "foo"
+      "#,
+    )
+    .success();
+}
+
+#[test]
+fn maps_round_trip_through_dump() {
+  Test::new()
+    .justfile(
+      r#"
+        set lists
+
+        x := {
+          "a": "b",
+          "c" + "d": 'e',
+        }
+
+        y := x["a"]
+      "#,
+    )
+    .unstable()
+    .arg("--dump")
+    .stdout(
+      r#"
+        set lists
+
+        x := {"a": "b", "c" + "d": 'e'}
+
+        y := x["a"]
+      "#,
+    )
+    .success();
+}
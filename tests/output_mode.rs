@@ -0,0 +1,317 @@
+use super::*;
+
+#[test]
+fn interleaved_by_default() {
+  Test::new()
+    .justfile(
+      "
+        [parallel]
+        foo: a b
+
+        a:
+          @echo a1
+          @sleep 0.2
+          @echo a2
+
+        b:
+          @sleep 0.1
+          @echo b1
+      ",
+    )
+    .stdout("a1\nb1\na2\n")
+    .success();
+}
+
+#[test]
+fn prefixed() {
+  Test::new()
+    .justfile(
+      "
+        set output := 'prefixed'
+
+        foo:
+          echo foo
+          @echo bar >&2
+      ",
+    )
+    .stdout("[foo] foo\n")
+    .stderr("[foo] echo foo\n[foo] bar\n")
+    .success();
+}
+
+#[test]
+fn prefixed_parallel() {
+  Test::new()
+    .justfile(
+      "
+        set output := 'prefixed'
+
+        [parallel]
+        foo: a b
+
+        a:
+          @echo a1
+          @sleep 0.2
+          @echo a2
+
+        b:
+          @sleep 0.1
+          @echo b1
+      ",
+    )
+    .stdout("[a] a1\n[b] b1\n[a] a2\n")
+    .success();
+}
+
+#[test]
+fn prefixed_partial_lines_are_terminated() {
+  Test::new()
+    .justfile(
+      "
+        set output := 'prefixed'
+
+        foo:
+          @printf 'foo\\nbar'
+      ",
+    )
+    .stdout("[foo] foo\n[foo] bar\n")
+    .success();
+}
+
+#[test]
+fn prefixed_script_recipe() {
+  Test::new()
+    .justfile(
+      "
+        set output := 'prefixed'
+
+        [script('sh')]
+        foo:
+          echo foo
+          echo bar
+      ",
+    )
+    .stdout("[foo] foo\n[foo] bar\n")
+    .success();
+}
+
+#[test]
+fn prefixed_submodule_recipe() {
+  Test::new()
+    .write("foo.just", "set output := 'prefixed'\nbar:\n  @echo bar")
+    .justfile(
+      "
+        mod foo
+      ",
+    )
+    .args(["foo", "bar"])
+    .stdout("[foo::bar] bar\n")
+    .success();
+}
+
+#[test]
+fn prefixed_labels_are_colored() {
+  Test::new()
+    .justfile(
+      "
+        set output := 'prefixed'
+
+        foo:
+          @echo foo
+      ",
+    )
+    .args(["--color", "always"])
+    .stdout("\u{1b}[1;35m[foo]\u{1b}[0m foo\n")
+    .success();
+}
+
+#[test]
+fn grouped() {
+  Test::new()
+    .justfile(
+      "
+        set output := 'grouped'
+
+        [parallel]
+        foo: a b
+
+        a:
+          @echo a1
+          @sleep 0.2
+          @echo a2
+
+        b:
+          @sleep 0.1
+          @echo b1
+      ",
+    )
+    .stdout("b1\na1\na2\n")
+    .success();
+}
+
+#[test]
+fn grouped_includes_echoed_commands() {
+  Test::new()
+    .justfile(
+      "
+        set output := 'grouped'
+
+        [parallel]
+        foo: a b
+
+        a:
+          echo a1
+          @sleep 0.2
+          echo a2 >&2
+
+        b:
+          @sleep 0.1
+          echo b1
+      ",
+    )
+    .stdout("b1\na1\n")
+    .stderr("echo b1\necho a1\necho a2 >&2\na2\n")
+    .success();
+}
+
+#[test]
+fn grouped_output_is_written_when_recipe_fails() {
+  Test::new()
+    .justfile(
+      "
+        set output := 'grouped'
+
+        foo:
+          @echo foo
+          @exit 1
+      ",
+    )
+    .stdout("foo\n")
+    .stderr("error: recipe `foo` failed on line 5 with exit code 1\n")
+    .failure();
+}
+
+#[test]
+fn flag_overrides_setting() {
+  Test::new()
+    .justfile(
+      "
+        set output := 'grouped'
+
+        foo:
+          @echo foo
+      ",
+    )
+    .args(["--output", "prefixed"])
+    .stdout("[foo] foo\n")
+    .success();
+}
+
+#[test]
+fn flag_from_environment() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          @echo foo
+      ",
+    )
+    .env("JUST_OUTPUT", "prefixed")
+    .stdout("[foo] foo\n")
+    .success();
+}
+
+#[test]
+fn quiet_output_is_suppressed() {
+  Test::new()
+    .justfile(
+      "
+        set output := 'prefixed'
+
+        foo:
+          @echo foo
+          @echo bar >&2
+      ",
+    )
+    .arg("--quiet")
+    .success();
+}
+
+#[test]
+fn junit_reports_do_not_include_labels() {
+  let output = Test::new()
+    .justfile(
+      "
+        set output := 'prefixed'
+
+        foo:
+          @echo foo >&2
+      ",
+    )
+    .args(["--junit", "report.xml"])
+    .stderr("[foo] foo\n")
+    .success();
+
+  assert!(
+    fs::read_to_string(output.tempdir.path().join("report.xml"))
+      .unwrap()
+      .contains("<system-err>foo\n</system-err>"),
+  );
+}
+
+#[test]
+fn invalid_mode() {
+  Test::new()
+    .justfile(
+      "
+        set output := 'foo'
+      ",
+    )
+    .stderr(
+      "
+        error: `output` setting has invalid mode `foo`, expected `grouped`, `interleaved`, or `prefixed`
+         ——▶ justfile:1:15
+          │
+        1 │ set output := 'foo'
+          │               ^^^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn mode_must_be_string_literal() {
+  Test::new()
+    .justfile(
+      "
+        set output := 'pre' + 'fixed'
+      ",
+    )
+    .stderr(
+      "
+        error: `output` setting must be a plain string literal
+         ——▶ justfile:1:5
+          │
+        1 │ set output := 'pre' + 'fixed'
+          │     ^^^^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn mode_is_formatted() {
+  Test::new()
+    .arg("--dump")
+    .justfile(
+      "
+        set output:='grouped'
+      ",
+    )
+    .stdout(
+      "
+        set output := 'grouped'
+      ",
+    )
+    .success();
+}
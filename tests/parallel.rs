@@ -210,3 +210,143 @@ fn zero_jobs_is_an_error() {
     )
     .status(2);
 }
+
+#[test]
+fn invocations_run_in_parallel() {
+  Test::new()
+    .args(["--parallel", "a", "b"])
+    .justfile(
+      "
+        set quiet
+
+        a:
+          for i in $(seq 100); do [ -f b ] && break; sleep 0.1; done
+          test -f b
+          echo a
+
+        b:
+          echo b
+          touch b
+      ",
+    )
+    .stdout("b\na\n")
+    .success();
+}
+
+#[test]
+fn invocations_share_dependencies() {
+  Test::new()
+    .args(["--parallel", "a", "b"])
+    .justfile(
+      "
+        set quiet
+
+        a: c
+
+        b: c
+
+        c:
+          echo c
+      ",
+    )
+    .stdout("c\n")
+    .success();
+}
+
+#[test]
+fn jobs_limits_parallel_invocations() {
+  Test::new()
+    .args(["--parallel", "--jobs", "1", "a", "b"])
+    .justfile(
+      "
+        set quiet
+
+        a:
+          echo a
+          sleep 0.1
+          echo a
+
+        b:
+          echo b
+          sleep 0.1
+          echo b
+      ",
+    )
+    .stdout_regex("(a\na\nb\nb\n|b\nb\na\na\n)")
+    .success();
+}
+
+#[test]
+fn parallel_invocation_failure_stops_other_invocations() {
+  Test::new()
+    .args(["--parallel", "a", "b"])
+    .justfile(
+      "
+        set quiet
+
+        a:
+          touch failed
+          exit 1
+
+        b: c
+          echo b
+
+        c:
+          for i in $(seq 100); do [ -f failed ] && break; sleep 0.1; done
+          sleep 0.5
+      ",
+    )
+    .stdout("")
+    .stderr("error: recipe `a` failed on line 5 with exit code 1\n")
+    .failure();
+}
+
+#[test]
+fn parallel_invocation_failure_with_keep_going() {
+  Test::new()
+    .args(["--parallel", "--keep-going", "a", "b"])
+    .justfile(
+      "
+        set quiet
+
+        a:
+          touch failed
+          exit 1
+
+        b: c
+          echo b
+
+        c:
+          for i in $(seq 100); do [ -f failed ] && break; sleep 0.1; done
+          sleep 0.5
+      ",
+    )
+    .stdout("b\n")
+    .stderr("error: recipe `a` failed on line 5 with exit code 1\n")
+    .failure();
+}
+
+#[test]
+fn parallel_invocation_failures_are_reported_together() {
+  Test::new()
+    .args(["--parallel", "--keep-going", "a", "b"])
+    .justfile(
+      "
+        a:
+          @exit 2
+
+        b:
+          @exit 3
+      ",
+    )
+    .stderr(
+      "
+        error: recipe `a` failed on line 2 with exit code 2
+        error: recipe `b` failed on line 5 with exit code 3
+        error: 2 recipes failed:
+          `a` with exit code 2
+          `b` with exit code 3
+      ",
+    )
+    .failure();
+}
@@ -0,0 +1,558 @@
+use super::*;
+
+#[test]
+fn recipe_is_retried_until_success() {
+  Test::new()
+    .justfile(
+      "
+        [retry('3')]
+        foo:
+          @echo attempt >> attempts
+          @test $(wc -l < attempts) -ge 3
+      ",
+    )
+    .stderr(
+      "
+        error: recipe `foo` failed on line 4 with exit code 1
+        ===> retrying recipe `foo` (attempt 2 of 3)
+        error: recipe `foo` failed on line 4 with exit code 1
+        ===> retrying recipe `foo` (attempt 3 of 3)
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn recipe_fails_after_last_attempt() {
+  Test::new()
+    .justfile(
+      "
+        [retry('2')]
+        foo:
+          @echo foo
+          @exit 3
+      ",
+    )
+    .stdout("foo\nfoo\n")
+    .stderr(
+      "
+        error: recipe `foo` failed on line 4 with exit code 3
+        ===> retrying recipe `foo` (attempt 2 of 2)
+        error: recipe `foo` failed on line 4 with exit code 3
+      ",
+    )
+    .status(3);
+}
+
+#[test]
+fn single_attempt_is_not_retried() {
+  Test::new()
+    .justfile(
+      "
+        [retry('1')]
+        foo:
+          @exit 1
+      ",
+    )
+    .stderr("error: recipe `foo` failed on line 3 with exit code 1\n")
+    .failure();
+}
+
+#[test]
+fn attempts_keyword() {
+  Test::new()
+    .justfile(
+      "
+        [retry(attempts='2')]
+        foo:
+          @exit 1
+      ",
+    )
+    .stderr(
+      "
+        error: recipe `foo` failed on line 3 with exit code 1
+        ===> retrying recipe `foo` (attempt 2 of 2)
+        error: recipe `foo` failed on line 3 with exit code 1
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn delay_and_backoff() {
+  let start = Instant::now();
+
+  Test::new()
+    .justfile(
+      "
+        [retry('3', delay='100ms', backoff='2')]
+        foo:
+          @exit 1
+      ",
+    )
+    .stderr(
+      "
+        error: recipe `foo` failed on line 3 with exit code 1
+        ===> retrying recipe `foo` in 100ms (attempt 2 of 3)
+        error: recipe `foo` failed on line 3 with exit code 1
+        ===> retrying recipe `foo` in 200ms (attempt 3 of 3)
+        error: recipe `foo` failed on line 3 with exit code 1
+      ",
+    )
+    .failure();
+
+  assert!(start.elapsed() >= Duration::from_millis(300));
+}
+
+#[test]
+fn exit_codes_not_in_on_are_not_retried() {
+  Test::new()
+    .justfile(
+      "
+        [retry('3', on='75')]
+        foo:
+          @exit 1
+      ",
+    )
+    .stderr("error: recipe `foo` failed on line 3 with exit code 1\n")
+    .failure();
+}
+
+#[test]
+fn exit_codes_in_on_are_retried() {
+  Test::new()
+    .justfile(
+      "
+        set lists
+        set unstable
+
+        [retry('2', on=['75', '111'])]
+        foo:
+          @exit 111
+      ",
+    )
+    .stderr(
+      "
+        error: recipe `foo` failed on line 6 with exit code 111
+        ===> retrying recipe `foo` (attempt 2 of 2)
+        error: recipe `foo` failed on line 6 with exit code 111
+      ",
+    )
+    .status(111);
+}
+
+#[test]
+fn linewise_recipes_restart_from_first_line() {
+  Test::new()
+    .justfile(
+      "
+        [retry('2')]
+        foo:
+          @echo first
+          @test -f failed || { touch failed; exit 1; }
+          @echo last
+      ",
+    )
+    .stdout("first\nfirst\nlast\n")
+    .stderr(
+      "
+        error: recipe `foo` failed on line 4 with exit code 1
+        ===> retrying recipe `foo` (attempt 2 of 2)
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn linewise_recipes_resume_from_failing_line() {
+  Test::new()
+    .justfile(
+      "
+        [retry('2', resume)]
+        foo:
+          @echo first
+          @test -f failed || { touch failed; exit 1; }
+          @echo last
+      ",
+    )
+    .stdout("first\nlast\n")
+    .stderr(
+      "
+        error: recipe `foo` failed on line 4 with exit code 1
+        ===> retrying recipe `foo` from line 4 (attempt 2 of 2)
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn resumed_lines_are_not_evaluated_again() {
+  Test::new()
+    .justfile(
+      "
+        [retry('2', resume)]
+        foo:
+          @echo {{ `echo x >> evaluated; echo first` }}
+          @test -f failed || { touch failed; exit 1; }
+          @echo last
+      ",
+    )
+    .stdout("first\nlast\n")
+    .stderr(
+      "
+        error: recipe `foo` failed on line 4 with exit code 1
+        ===> retrying recipe `foo` from line 4 (attempt 2 of 2)
+      ",
+    )
+    .expect_file("evaluated", "x\n")
+    .success();
+}
+
+#[test]
+fn resumed_lines_of_cached_recipes_are_not_evaluated_again() {
+  Test::new()
+    .justfile(
+      "
+        [cache]
+        [retry('2', resume)]
+        foo:
+          @echo {{ `echo x >> evaluated; echo first` }}
+          @test -f failed || { touch failed; exit 1; }
+          @echo {{ `echo y >> evaluated; echo last` }}
+      ",
+    )
+    .unstable()
+    .stdout("first\nlast\n")
+    .stderr(
+      "
+        error: recipe `foo` failed on line 5 with exit code 1
+        ===> retrying recipe `foo` from line 5 (attempt 2 of 2)
+      ",
+    )
+    .expect_file("evaluated", "x\ny\ny\n")
+    .success();
+}
+
+#[test]
+fn delay_is_interrupted_when_parallel_invocation_fails() {
+  let start = Instant::now();
+
+  Test::new()
+    .args(["--parallel", "a", "b"])
+    .justfile(
+      "
+        a:
+          @sleep 0.5
+          @exit 2
+
+        [retry('2', delay='1h')]
+        b:
+          @exit 1
+      ",
+    )
+    .stderr(
+      "
+        error: recipe `b` failed on line 7 with exit code 1
+        ===> retrying recipe `b` in 1h (attempt 2 of 2)
+        error: recipe `a` failed on line 3 with exit code 2
+      ",
+    )
+    .status(2);
+
+  assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn interrupted_commands_are_not_retried() {
+  for (signal, status) in [("HUP", 129), ("INT", 130)] {
+    Test::new()
+      .justfile(format!(
+        "
+          [retry('3')]
+          foo:
+            @kill -{signal} $$
+        "
+      ))
+      .stderr(format!(
+        "error: recipe `foo` was terminated on line 3 by signal {}\n",
+        status - 128,
+      ))
+      .status(status);
+  }
+}
+
+#[test]
+fn terminated_commands_are_retried() {
+  Test::new()
+    .justfile(
+      "
+        [retry('2')]
+        foo:
+          @kill -TERM $$
+      ",
+    )
+    .stderr(
+      "
+        error: recipe `foo` was terminated on line 3 by signal 15
+        ===> retrying recipe `foo` (attempt 2 of 2)
+        error: recipe `foo` was terminated on line 3 by signal 15
+      ",
+    )
+    .status(143);
+}
+
+#[test]
+fn script_recipes_are_retried() {
+  Test::new()
+    .justfile(
+      "
+        [retry('2')]
+        foo:
+          #!/usr/bin/env sh
+          echo attempt
+          test -f failed || { touch failed; exit 1; }
+      ",
+    )
+    .stdout("attempt\nattempt\n")
+    .stderr(
+      "
+        error: recipe `foo` failed with exit code 1
+        ===> retrying recipe `foo` (attempt 2 of 2)
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn timed_out_recipes_are_retried() {
+  Test::new()
+    .justfile(
+      "
+        [retry('2')]
+        [timeout('100ms')]
+        foo:
+          @sleep 10
+      ",
+    )
+    .stderr(
+      "
+        error: recipe `foo` timed out after 100ms
+        ===> retrying recipe `foo` (attempt 2 of 2)
+        error: recipe `foo` timed out after 100ms
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn evaluation_errors_are_not_retried() {
+  Test::new()
+    .justfile(
+      "
+        [retry('3')]
+        foo:
+          @echo {{ `exit 1` }}
+      ",
+    )
+    .stderr(
+      "
+        error: backtick failed with exit code 1
+         ——▶ justfile:3:12
+          │
+        3 │   @echo {{ `exit 1` }}
+          │            ^^^^^^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn quiet_suppresses_retry_banners() {
+  Test::new()
+    .arg("--quiet")
+    .justfile(
+      "
+        [retry('2')]
+        foo:
+          @exit 1
+      ",
+    )
+    .stderr("")
+    .failure();
+}
+
+#[test]
+fn resume_is_not_allowed_on_script_recipes() {
+  Test::new()
+    .justfile(
+      "
+        [retry('2', resume)]
+        foo:
+          #!/usr/bin/env sh
+          exit 1
+      ",
+    )
+    .stderr(
+      "
+        error: script recipe `foo` cannot resume retries from the failing line
+         ——▶ justfile:2:1
+          │
+        2 │ foo:
+          │ ^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn attempts_are_required() {
+  Test::new()
+    .justfile(
+      "
+        [retry(delay='1s')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: attribute `retry` requires key `attempts`
+         ——▶ justfile:1:2
+          │
+        1 │ [retry(delay='1s')]
+          │  ^^^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn attempts_may_not_be_given_twice() {
+  Test::new()
+    .justfile(
+      "
+        [retry('2', attempts='3')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: duplicate key `attempts` for `retry` attribute
+         ——▶ justfile:1:13
+          │
+        1 │ [retry('2', attempts='3')]
+          │             ^^^^^^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn attempts_must_be_positive() {
+  Test::new()
+    .justfile(
+      "
+        [retry('0')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: invalid `retry` value `0`
+         ——▶ justfile:1:8
+          │
+        1 │ [retry('0')]
+          │        ^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn backoff_must_be_at_least_one() {
+  Test::new()
+    .justfile(
+      "
+        [retry('2', backoff='0.5')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: invalid `backoff` value `0.5`
+         ——▶ justfile:1:21
+          │
+        1 │ [retry('2', backoff='0.5')]
+          │                     ^^^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn delay_must_be_duration() {
+  Test::new()
+    .justfile(
+      "
+        [retry('2', delay='1')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: invalid duration `1`, expected numbers followed by units ms, s, m, h, d, or w
+         ——▶ justfile:1:19
+          │
+        1 │ [retry('2', delay='1')]
+          │                   ^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn on_must_be_exit_codes() {
+  Test::new()
+    .justfile(
+      "
+        [retry('2', on='foo')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: invalid `on` value `foo`
+         ——▶ justfile:1:16
+          │
+        1 │ [retry('2', on='foo')]
+          │                ^^^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn retry_attribute_is_formatted() {
+  Test::new()
+    .arg("--dump")
+    .justfile(
+      "
+        set lists
+        set unstable
+
+        [retry(attempts='3', delay='1s', backoff='2', on=['2', '1'], resume)]
+        foo:
+          exit 1
+      ",
+    )
+    .stdout(
+      "
+        set lists
+        set unstable
+
+        [retry('3', backoff='2', delay='1s', on=['1', '2'], resume)]
+        foo:
+            exit 1
+      ",
+    )
+    .success();
+}
@@ -167,6 +167,44 @@ fn continue_runs_subsequents() {
   );
 }
 
+#[test]
+#[ignore]
+fn interrupt_runs_finally_dependencies() {
+  signal_test(
+    &[],
+    "
+        default: &&! cleanup
+          @sleep 1
+
+        cleanup:
+          @echo cleanup
+          @echo done
+      ",
+    Signal::SIGINT,
+    130,
+    "cleanup\ndone\n",
+  );
+}
+
+#[test]
+#[ignore]
+fn interrupt_retry_delay() {
+  interrupt_test(
+    &[],
+    "
+        [parallel]
+        default: a b
+
+        a:
+          @sleep 1
+
+        [retry('2', delay='1h')]
+        b:
+          @exit 1
+      ",
+  );
+}
+
 #[test]
 #[ignore]
 fn continue_default_excludes_hangup() {
@@ -27,7 +27,7 @@ fn no_rhs_once() {
     .justfile("x := 'a' /")
     .stderr(
       "
-        error: expected backtick, '!', '[', identifier, '(', '/', or string, but found end of file
+        error: expected backtick, '!', '{', '[', identifier, '(', '/', or string, but found end of file
          ——▶ justfile:1:11
           │
         1 │ x := 'a' /
@@ -69,7 +69,7 @@ fn no_lhs_un_parenthesized() {
     )
     .stderr(
       "
-        error: expected backtick, '!', '[', identifier, '(', or string, but found '/'
+        error: expected backtick, '!', '{', '[', identifier, '(', or string, but found '/'
          ——▶ justfile:1:7
           │
         1 │ foo x=/ 'a' / 'b':
@@ -0,0 +1,499 @@
+use super::*;
+
+#[test]
+fn runs_when_target_is_missing() {
+  Test::new()
+    .justfile(
+      "
+        [stale(sources = 'src', targets = 'out')]
+        build:
+          @cp src out
+          @echo built
+      ",
+    )
+    .write("src", "a")
+    .stdout("built\n")
+    .expect_file("out", "a")
+    .success();
+}
+
+#[test]
+fn skips_when_targets_are_newer_than_sources() {
+  Test::new()
+    .justfile(
+      "
+        [stale(sources = 'src', targets = 'out')]
+        build:
+          @echo built
+      ",
+    )
+    .write("src", "a")
+    .modified("src", 1000)
+    .write("out", "a")
+    .modified("out", 2000)
+    .success();
+}
+
+#[test]
+fn runs_when_source_is_newer_than_target() {
+  Test::new()
+    .justfile(
+      "
+        [stale(sources = 'src', targets = 'out')]
+        build:
+          @echo built
+      ",
+    )
+    .write("src", "a")
+    .modified("src", 2000)
+    .write("out", "a")
+    .modified("out", 1000)
+    .stdout("built\n")
+    .success();
+}
+
+#[test]
+fn runs_when_source_and_target_have_same_modification_time() {
+  Test::new()
+    .justfile(
+      "
+        [stale(sources = 'src', targets = 'out')]
+        build:
+          @echo built
+      ",
+    )
+    .write("src", "a")
+    .modified("src", 1000)
+    .write("out", "a")
+    .modified("out", 1000)
+    .stdout("built\n")
+    .success();
+}
+
+#[test]
+fn runs_when_any_target_is_older_than_a_source() {
+  Test::new()
+    .justfile(
+      "
+        set unstable
+        set lists
+
+        [stale(sources = 'src', targets = ['a', 'b'])]
+        build:
+          @echo built
+      ",
+    )
+    .write("src", "a")
+    .modified("src", 2000)
+    .write("a", "a")
+    .modified("a", 3000)
+    .write("b", "b")
+    .modified("b", 1000)
+    .stdout("built\n")
+    .success();
+}
+
+#[test]
+fn runs_when_any_target_is_missing() {
+  Test::new()
+    .justfile(
+      "
+        set unstable
+        set lists
+
+        [stale(sources = 'src', targets = ['a', 'b'])]
+        build:
+          @echo built
+      ",
+    )
+    .write("src", "a")
+    .modified("src", 1000)
+    .write("a", "a")
+    .modified("a", 2000)
+    .stdout("built\n")
+    .success();
+}
+
+#[test]
+fn sources_are_optional() {
+  Test::new()
+    .justfile(
+      "
+        [stale(targets = 'out')]
+        build:
+          @echo built
+          @touch out
+      ",
+    )
+    .stdout("built\n")
+    .success()
+    .test()
+    .justfile(
+      "
+        [stale(targets = 'out')]
+        build:
+          @echo built
+          @touch out
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn source_globs_are_expanded() {
+  Test::new()
+    .justfile(
+      "
+        [stale(sources = 'src/**/*.c', targets = 'out')]
+        build:
+          @echo built
+      ",
+    )
+    .write("src/a.c", "a")
+    .modified("src/a.c", 1000)
+    .write("src/b/c.c", "c")
+    .modified("src/b/c.c", 3000)
+    .write("src/d.h", "d")
+    .modified("src/d.h", 4000)
+    .write("out", "a")
+    .modified("out", 2000)
+    .stdout("built\n")
+    .success()
+    .test()
+    .justfile(
+      "
+        [stale(sources = 'src/**/*.c', targets = 'out')]
+        build:
+          @echo built
+      ",
+    )
+    .modified("src/b/c.c", 1000)
+    .success();
+}
+
+#[test]
+fn source_globs_matching_nothing_are_an_error() {
+  Test::new()
+    .justfile(
+      "
+        [stale(sources = '*.c', targets = 'out')]
+        build:
+          @echo built
+      ",
+    )
+    .write("out", "a")
+    .stderr("error: `[stale]` source pattern `*.c` matches no files\n")
+    .failure();
+}
+
+#[test]
+fn target_globs_are_expanded() {
+  Test::new()
+    .justfile(
+      "
+        [stale(sources = 'src', targets = '*.o')]
+        build:
+          @echo built
+      ",
+    )
+    .write("src", "a")
+    .modified("src", 2000)
+    .write("a.o", "a")
+    .modified("a.o", 3000)
+    .write("b.o", "b")
+    .modified("b.o", 1000)
+    .stdout("built\n")
+    .success();
+}
+
+#[test]
+fn target_globs_matching_nothing_are_stale() {
+  Test::new()
+    .justfile(
+      "
+        [stale(sources = 'src', targets = '*.o')]
+        build:
+          @echo built
+      ",
+    )
+    .write("src", "a")
+    .stdout("built\n")
+    .success();
+}
+
+#[test]
+fn source_directories_are_walked() {
+  Test::new()
+    .justfile(
+      "
+        [stale(sources = 'src', targets = 'out')]
+        build:
+          @echo built
+      ",
+    )
+    .write("src/a/b", "b")
+    .modified("src/a/b", 3000)
+    .write("out", "a")
+    .modified("out", 2000)
+    .stdout("built\n")
+    .success();
+}
+
+#[test]
+fn missing_source_is_an_error() {
+  Test::new()
+    .justfile(
+      "
+        [stale(sources = 'src', targets = 'out')]
+        build:
+          @echo built
+      ",
+    )
+    .write("out", "a")
+    .stderr_regex(r"error: stale source does not exist: `.*src`\n")
+    .failure();
+}
+
+#[test]
+fn invalid_pattern_is_an_error() {
+  Test::new()
+    .justfile(
+      "
+        [stale(sources = '[', targets = 'out')]
+        build:
+          @echo built
+      ",
+    )
+    .write("out", "a")
+    .stderr_regex(r"error: invalid stale pattern `\[`: .*\n")
+    .failure();
+}
+
+#[test]
+fn attribute_expressions_have_parameters_in_scope() {
+  Test::new()
+    .justfile(
+      "
+        [stale(sources = name + '.c', targets = name + '.o')]
+        build name:
+          @echo built {{ name }}
+      ",
+    )
+    .write("a.c", "a")
+    .modified("a.c", 1000)
+    .write("a.o", "a")
+    .modified("a.o", 2000)
+    .write("b.c", "b")
+    .modified("b.c", 2000)
+    .write("b.o", "b")
+    .modified("b.o", 1000)
+    .args(["build", "a"])
+    .success()
+    .test()
+    .justfile(
+      "
+        [stale(sources = name + '.c', targets = name + '.o')]
+        build name:
+          @echo built {{ name }}
+      ",
+    )
+    .args(["build", "b"])
+    .stdout("built b\n")
+    .success();
+}
+
+#[test]
+fn paths_are_relative_to_working_directory() {
+  Test::new()
+    .justfile(
+      "
+        [stale(sources = 'src', targets = 'out')]
+        [working-directory('foo')]
+        build:
+          @echo built
+      ",
+    )
+    .write("foo/src", "a")
+    .modified("foo/src", 1000)
+    .write("foo/out", "a")
+    .modified("foo/out", 2000)
+    .success();
+}
+
+#[test]
+fn dependencies_run_when_up_to_date() {
+  Test::new()
+    .justfile(
+      "
+        [stale(sources = 'src', targets = 'out')]
+        build: dep
+          @echo built
+
+        dep:
+          @echo dep
+      ",
+    )
+    .write("src", "a")
+    .modified("src", 1000)
+    .write("out", "a")
+    .modified("out", 2000)
+    .stdout("dep\n")
+    .success();
+}
+
+#[test]
+fn dry_run_reports_up_to_date() {
+  Test::new()
+    .justfile(
+      "
+        [stale(sources = 'src', targets = 'out')]
+        build:
+          echo built
+      ",
+    )
+    .write("src", "a")
+    .modified("src", 1000)
+    .write("out", "a")
+    .modified("out", 2000)
+    .arg("--dry-run")
+    .stderr("===> recipe `build` is up to date\n")
+    .success();
+}
+
+#[test]
+fn dry_run_prints_commands_when_stale() {
+  Test::new()
+    .justfile(
+      "
+        [stale(sources = 'src', targets = 'out')]
+        build:
+          echo built
+      ",
+    )
+    .write("src", "a")
+    .arg("--dry-run")
+    .stderr("echo built\n")
+    .success();
+}
+
+#[test]
+fn explain_reports_up_to_date() {
+  Test::new()
+    .justfile(
+      "
+        # build it
+        [stale(sources = 'src', targets = 'out')]
+        build:
+          @echo built
+      ",
+    )
+    .write("src", "a")
+    .modified("src", 1000)
+    .write("out", "a")
+    .modified("out", 2000)
+    .arg("--explain")
+    .stderr("===> recipe `build` is up to date\n")
+    .success();
+}
+
+#[test]
+fn explain_reports_why_recipe_is_stale() {
+  Test::new()
+    .justfile(
+      "
+        [stale(sources = 'src', targets = 'out')]
+        build:
+          @echo built
+      ",
+    )
+    .write("src", "a")
+    .modified("src", 2000)
+    .write("out", "a")
+    .modified("out", 1000)
+    .arg("--explain")
+    .stdout("built\n")
+    .stderr("===> recipe `build` is stale, source `src` is newer than target `out`\n")
+    .success();
+}
+
+#[test]
+fn explain_reports_missing_target() {
+  Test::new()
+    .justfile(
+      "
+        [stale(sources = 'src', targets = 'out')]
+        build:
+          @echo built
+      ",
+    )
+    .write("src", "a")
+    .arg("--explain")
+    .stdout("built\n")
+    .stderr("===> recipe `build` is stale, target `out` does not exist\n")
+    .success();
+}
+
+#[test]
+fn targets_are_required() {
+  Test::new()
+    .justfile(
+      "
+        [stale(sources = 'src')]
+        build:
+          @echo built
+      ",
+    )
+    .stderr(
+      "
+        error: attribute `stale` requires key `targets`
+         ——▶ justfile:1:2
+          │
+        1 │ [stale(sources = 'src')]
+          │  ^^^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn unknown_key() {
+  Test::new()
+    .justfile(
+      "
+        [stale(inputs = 'src', targets = 'out')]
+        build:
+          @echo built
+      ",
+    )
+    .stderr(
+      "
+        error: unknown key `inputs` for `stale` attribute
+         ——▶ justfile:1:8
+          │
+        1 │ [stale(inputs = 'src', targets = 'out')]
+          │        ^^^^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn dump() {
+  Test::new()
+    .justfile(
+      "
+        [stale(targets = 'out', sources = 'src')]
+        build:
+          echo built
+      ",
+    )
+    .arg("--dump")
+    .stdout(
+      "
+        [stale(sources='src', targets='out')]
+        build:
+            echo built
+      ",
+    )
+    .success();
+}
@@ -155,6 +155,16 @@ impl Test {
     self
   }
 
+  pub(crate) fn modified(self, path: impl AsRef<Path>, seconds: u64) -> Self {
+    fs::File::options()
+      .write(true)
+      .open(self.tempdir.path().join(path))
+      .unwrap()
+      .set_modified(UNIX_EPOCH + Duration::from_secs(seconds))
+      .unwrap();
+    self
+  }
+
   pub(crate) fn write_executable(self, path: impl AsRef<Path> + Copy, content: &str) -> Self {
     self.write(path, content).make_executable(path)
   }
@@ -238,8 +248,12 @@ impl Test {
       command.args(["--shell", "bash"]);
     }
 
+    // Remove variables set by CI services, which would enable `--ci-format`
+    // when tests are run in CI.
     let mut child = command
       .args(&self.args)
+      .env_remove("GITHUB_ACTIONS")
+      .env_remove("GITLAB_CI")
       .envs(&self.env)
       .current_dir(self.tempdir.path().join(&self.current_dir))
       .stdin(Stdio::piped())
@@ -312,3 +326,23 @@ impl Test {
     }
   }
 }
+
+// Read `path` from the tempdir of `output`, replacing matches of each pattern
+// in `replacements`, for reports which contain values that vary between runs.
+#[track_caller]
+pub(crate) fn read_normalized(
+  output: &Output,
+  path: &str,
+  replacements: &[(&str, &str)],
+) -> String {
+  let mut content = fs::read_to_string(output.tempdir.path().join(path)).unwrap();
+
+  for (pattern, replacement) in replacements {
+    content = Regex::new(pattern)
+      .unwrap()
+      .replace_all(&content, *replacement)
+      .into_owned();
+  }
+
+  content
+}
@@ -0,0 +1,404 @@
+use super::*;
+
+#[test]
+fn recipe_times_out() {
+  let start = Instant::now();
+
+  Test::new()
+    .justfile(
+      "
+        [timeout('100ms')]
+        foo:
+          @sleep 10
+      ",
+    )
+    .stderr("error: recipe `foo` timed out after 100ms\n")
+    .failure();
+
+  assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn recipe_finishes_before_timeout() {
+  Test::new()
+    .justfile(
+      "
+        [timeout('10s')]
+        foo:
+          @echo foo
+      ",
+    )
+    .stdout("foo\n")
+    .success();
+}
+
+#[test]
+fn timeout_spans_all_lines() {
+  Test::new()
+    .justfile(
+      "
+        [timeout('500ms')]
+        foo:
+          @sleep 0.3
+          @echo bar
+          @sleep 0.3
+          @echo baz
+      ",
+    )
+    .stdout("bar\n")
+    .stderr("error: recipe `foo` timed out after 500ms\n")
+    .failure();
+}
+
+#[test]
+fn script_recipe_times_out() {
+  let start = Instant::now();
+
+  Test::new()
+    .justfile(
+      "
+        [timeout('100ms')]
+        foo:
+          #!/usr/bin/env sh
+          sleep 10
+      ",
+    )
+    .stderr("error: recipe `foo` timed out after 100ms\n")
+    .failure();
+
+  assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+#[cfg(unix)]
+fn process_group_is_terminated() {
+  let start = Instant::now();
+
+  Test::new()
+    .justfile(
+      "
+        [timeout('100ms')]
+        foo:
+          @sleep 10; echo done
+      ",
+    )
+    .stderr("error: recipe `foo` timed out after 100ms\n")
+    .failure();
+
+  assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+#[cfg(unix)]
+fn process_group_is_killed_after_grace_period() {
+  let start = Instant::now();
+
+  Test::new()
+    .justfile(
+      "
+        [timeout('100ms')]
+        foo:
+          @trap '' TERM; sleep 10
+      ",
+    )
+    .stderr("error: recipe `foo` timed out after 100ms\n")
+    .failure();
+
+  let elapsed = start.elapsed();
+  assert!(elapsed >= Duration::from_secs(5));
+  assert!(elapsed < Duration::from_secs(10));
+}
+
+#[test]
+fn recipe_with_timeout_can_read_stdin() {
+  Test::new()
+    .justfile(
+      "
+        [timeout('10s')]
+        foo:
+          @read line && echo $line
+      ",
+    )
+    .stdin("bar\n")
+    .stdout("bar\n")
+    .success();
+}
+
+// Run `just` in `dir` with a new pseudoterminal as its controlling terminal
+// and standard input, after writing `input` to the terminal.
+#[cfg(unix)]
+fn run_in_terminal(dir: &Path, input: &[u8]) -> std::process::Output {
+  let (mut terminal, child) = spawn_in_terminal(dir);
+
+  terminal.write_all(input).unwrap();
+
+  child.wait_with_output().unwrap()
+}
+
+// Spawn `just` in `dir` with a new pseudoterminal as its controlling terminal
+// and standard input, returning the pseudoterminal's master and the child.
+#[cfg(unix)]
+fn spawn_in_terminal(dir: &Path) -> (std::fs::File, std::process::Child) {
+  use std::{
+    fs::File,
+    io,
+    os::{
+      fd::{FromRawFd, OwnedFd},
+      unix::process::CommandExt,
+    },
+    ptr,
+  };
+
+  let mut master = 0;
+  let mut slave = 0;
+
+  // SAFETY: `master` and `slave` are valid pointers, and the remaining
+  // arguments may be null.
+  let result = unsafe {
+    libc::openpty(
+      &raw mut master,
+      &raw mut slave,
+      ptr::null_mut(),
+      ptr::null(),
+      ptr::null(),
+    )
+  };
+
+  assert_eq!(result, 0);
+
+  // SAFETY: `openpty` succeeded, so `master` and `slave` are open file
+  // descriptors which nothing else owns.
+  let (master, slave) = unsafe {
+    (
+      File::from(OwnedFd::from_raw_fd(master)),
+      OwnedFd::from_raw_fd(slave),
+    )
+  };
+
+  let mut command = Command::new(JUST);
+
+  command
+    .current_dir(dir)
+    .stdin(slave)
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped());
+
+  // SAFETY: `setsid` and `ioctl` are async-signal-safe.
+  unsafe {
+    command.pre_exec(|| {
+      // Start a new session with the terminal as its controlling terminal,
+      // so that `just` is in the terminal's foreground process group.
+      if libc::setsid() == -1 || libc::ioctl(0, libc::TIOCSCTTY, 0) == -1 {
+        return Err(io::Error::last_os_error());
+      }
+      Ok(())
+    });
+  }
+
+  (master, command.spawn().unwrap())
+}
+
+#[test]
+#[cfg(unix)]
+fn recipe_with_timeout_can_read_from_terminal() {
+  let tempdir = tempdir();
+
+  fs::write(
+    tempdir.path().join("justfile"),
+    "[timeout('10s')]\nfoo:\n  @read line && echo $line\n",
+  )
+  .unwrap();
+
+  let output = run_in_terminal(tempdir.path(), b"bar\n");
+
+  assert_stdout(&output, "bar\n");
+}
+
+#[test]
+#[cfg(unix)]
+fn terminal_interrupt_stops_just() {
+  let tempdir = tempdir();
+
+  fs::write(
+    tempdir.path().join("justfile"),
+    "[retry('3')]\n[timeout('1m')]\nfoo:\n  @sleep 5\n",
+  )
+  .unwrap();
+
+  let start = Instant::now();
+
+  let (mut terminal, child) = spawn_in_terminal(tempdir.path());
+
+  thread::sleep(Duration::from_millis(500));
+
+  terminal.write_all(b"\x03").unwrap();
+
+  let output = child.wait_with_output().unwrap();
+
+  assert!(start.elapsed() < Duration::from_secs(5));
+
+  assert_eq!(output.status.code(), Some(130));
+
+  assert!(
+    !String::from_utf8_lossy(&output.stderr).contains("retrying"),
+    "{}",
+    String::from_utf8_lossy(&output.stderr),
+  );
+}
+
+#[test]
+#[cfg(unix)]
+fn background_processes_are_terminated() {
+  let output = Test::new()
+    .justfile(
+      "
+        [timeout('100ms')]
+        foo:
+          @(sleep 1 && touch done) & sleep 10
+      ",
+    )
+    .stderr("error: recipe `foo` timed out after 100ms\n")
+    .failure();
+
+  thread::sleep(Duration::from_secs(2));
+
+  assert!(!output.tempdir.path().join("done").exists());
+}
+
+#[test]
+#[cfg(unix)]
+fn background_processes_are_terminated_with_terminal() {
+  let tempdir = tempdir();
+
+  // `SIGHUP` is ignored, since it is sent to the terminal's foreground process
+  // group when `just`, the session leader, exits
+  fs::write(
+    tempdir.path().join("justfile"),
+    "[timeout('100ms')]\nfoo:\n  @(trap '' HUP; sleep 1 && touch done) & sleep 10\n",
+  )
+  .unwrap();
+
+  let output = run_in_terminal(tempdir.path(), b"");
+
+  assert_eq!(
+    String::from_utf8_lossy(&output.stderr),
+    "error: recipe `foo` timed out after 100ms\n",
+  );
+
+  thread::sleep(Duration::from_secs(2));
+
+  assert!(!tempdir.path().join("done").exists());
+}
+
+#[test]
+fn timeout_flag_applies_to_all_recipes() {
+  Test::new()
+    .args(["--timeout", "100ms", "foo"])
+    .justfile(
+      "
+        foo: bar
+          @sleep 10
+
+        bar:
+          @echo bar
+      ",
+    )
+    .stdout("bar\n")
+    .stderr("error: recipe `foo` timed out after 100ms\n")
+    .failure();
+}
+
+#[test]
+fn timeout_attribute_overrides_timeout_flag() {
+  Test::new()
+    .args(["--timeout", "100ms"])
+    .justfile(
+      "
+        [timeout('10s')]
+        foo:
+          @sleep 0.5
+          @echo foo
+      ",
+    )
+    .stdout("foo\n")
+    .success();
+}
+
+#[test]
+fn timeout_flag_requires_valid_duration() {
+  Test::new()
+    .args(["--timeout", "10"])
+    .justfile("foo:")
+    .stderr(
+      "
+        error: invalid value '10' for '--timeout <DURATION>': invalid duration `10`, expected numbers \
+        followed by units ms, s, m, h, d, or w
+
+        For more information, try '--help'.
+      ",
+    )
+    .status(2);
+}
+
+#[test]
+fn timeout_attribute_requires_valid_duration() {
+  Test::new()
+    .justfile(
+      "
+        [timeout('10 minutes')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: invalid duration `10 minutes`, expected numbers followed by units ms, s, m, h, d, or w
+         ——▶ justfile:1:10
+          │
+        1 │ [timeout('10 minutes')]
+          │          ^^^^^^^^^^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn timeout_attribute_requires_argument() {
+  Test::new()
+    .justfile(
+      "
+        [timeout]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: attribute `timeout` got 0 arguments but takes 1 argument
+         ——▶ justfile:1:2
+          │
+        1 │ [timeout]
+          │  ^^^^^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn timeout_attribute_is_formatted() {
+  Test::new()
+    .arg("--dump")
+    .justfile(
+      "
+        [timeout('1h30m')]
+        foo:
+          sleep 1
+      ",
+    )
+    .stdout(
+      "
+        [timeout('1h30m')]
+        foo:
+            sleep 1
+      ",
+    )
+    .success();
+}
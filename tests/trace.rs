@@ -0,0 +1,274 @@
+use super::*;
+
+#[track_caller]
+fn trace(output: &Output) -> Vec<Value> {
+  let trace = serde_json::from_str::<Value>(&read_normalized(output, "trace.json", &[])).unwrap();
+
+  assert_eq!(trace["displayTimeUnit"], "ms");
+
+  trace["traceEvents"].as_array().unwrap().clone()
+}
+
+fn phase<'a>(events: &'a [Value], phase: &str) -> Vec<&'a Value> {
+  events.iter().filter(|event| event["ph"] == phase).collect()
+}
+
+#[test]
+fn recipes_are_spans() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo a: bar
+          @echo {{ a }}
+
+        bar:
+          @echo bar
+      ",
+    )
+    .args(["--trace", "trace.json", "foo", "baz"])
+    .stdout("bar\nbaz\n")
+    .success();
+
+  let events = trace(&output);
+
+  let spans = phase(&events, "X");
+
+  assert_eq!(spans.len(), 2);
+
+  let bar = spans[0];
+  assert_eq!(bar["name"], "bar");
+  assert_eq!(bar["cat"], "recipe");
+  assert_eq!(bar["pid"], output.pid);
+  assert_eq!(
+    bar["args"],
+    json!({
+      "arguments": [],
+      "cache": null,
+      "code": 0,
+      "dependency": true,
+    }),
+  );
+
+  let foo = spans[1];
+  assert_eq!(foo["name"], "foo");
+  assert_eq!(
+    foo["args"],
+    json!({
+      "arguments": ["baz"],
+      "cache": null,
+      "code": 0,
+      "dependency": false,
+    }),
+  );
+
+  let bar_end = bar["ts"].as_f64().unwrap() + bar["dur"].as_f64().unwrap();
+  assert!(foo["ts"].as_f64().unwrap() >= bar_end);
+  assert_eq!(foo["tid"], bar["tid"]);
+
+  assert_eq!(
+    phase(&events, "M"),
+    [&json!({
+      "ph": "M",
+      "args": { "name": "main" },
+      "name": "thread_name",
+      "pid": output.pid,
+      "tid": 0,
+    })],
+  );
+}
+
+#[test]
+fn dependency_edges() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo: bar && baz
+
+        bar:
+
+        baz:
+      ",
+    )
+    .args(["--trace", "trace.json"])
+    .success();
+
+  let events = trace(&output);
+
+  let starts = phase(&events, "s");
+  let finishes = phase(&events, "f");
+
+  assert_eq!(starts.len(), 2);
+  assert_eq!(finishes.len(), 2);
+
+  let spans = phase(&events, "X");
+
+  let ts = |name: &str| {
+    spans.iter().find(|span| span["name"] == name).unwrap()["ts"]
+      .as_f64()
+      .unwrap()
+  };
+
+  for (start, finish) in starts.iter().zip(&finishes) {
+    assert_eq!(start["id"], finish["id"]);
+    assert_eq!(start["cat"], "dependency");
+    assert_eq!(finish["bp"], "e");
+    assert!(start["ts"].as_f64().unwrap() <= finish["ts"].as_f64().unwrap());
+  }
+
+  // bar ran before foo, and foo before baz
+  let mut finish_times = finishes
+    .iter()
+    .map(|finish| finish["ts"].as_f64().unwrap())
+    .collect::<Vec<f64>>();
+  finish_times.sort_by(f64::total_cmp);
+  assert_eq!(finish_times, [ts("foo"), ts("baz")]);
+}
+
+#[test]
+fn parallel_dependencies_run_on_separate_threads() {
+  let output = Test::new()
+    .justfile(
+      "
+        [parallel]
+        foo: bar baz
+
+        bar:
+          @sleep 0.1
+
+        baz:
+          @sleep 0.1
+      ",
+    )
+    .args(["--trace", "trace.json"])
+    .success();
+
+  let events = trace(&output);
+
+  let spans = phase(&events, "X");
+
+  let tid = |name: &str| spans.iter().find(|span| span["name"] == name).unwrap()["tid"].clone();
+
+  assert_ne!(tid("bar"), tid("baz"));
+  assert_ne!(tid("bar"), tid("foo"));
+  assert_ne!(tid("baz"), tid("foo"));
+
+  assert_eq!(phase(&events, "M").len(), 3);
+  assert_eq!(phase(&events, "s").len(), 2);
+  assert_eq!(phase(&events, "f").len(), 2);
+}
+
+#[test]
+fn cache_hits_are_marked() {
+  let justfile = "
+    [cache]
+    foo:
+      @echo foo
+  ";
+
+  let output = Test::new()
+    .justfile(justfile)
+    .unstable()
+    .args(["--trace", "trace.json"])
+    .stdout("foo\n")
+    .success();
+
+  let events = trace(&output);
+  let span = phase(&events, "X")[0];
+  assert_eq!(span["cat"], "recipe");
+  assert_eq!(span["args"]["cache"], "miss");
+
+  let output = output
+    .test()
+    .justfile(justfile)
+    .unstable()
+    .args(["--trace", "trace.json"])
+    .success();
+
+  let events = trace(&output);
+  let span = phase(&events, "X")[0];
+  assert_eq!(span["cat"], "recipe,cache_hit");
+  assert_eq!(span["args"]["cache"], "hit");
+}
+
+#[test]
+fn failed_recipes_are_recorded() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo:
+          @exit 3
+      ",
+    )
+    .args(["--trace", "trace.json"])
+    .stderr("error: recipe `foo` failed on line 2 with exit code 3\n")
+    .status(3);
+
+  let events = trace(&output);
+  let span = phase(&events, "X")[0];
+  assert_eq!(span["name"], "foo");
+  assert_eq!(span["args"]["code"], 3);
+}
+
+#[test]
+fn skipped_recipes_are_not_recorded() {
+  let output = Test::new()
+    .justfile(
+      "
+        set quiet
+
+        a:
+          touch failed
+          exit 1
+
+        b: c && d
+
+        c:
+          for i in $(seq 100); do [ -f failed ] && break; sleep 0.1; done
+          sleep 0.5
+
+        d:
+      ",
+    )
+    .args(["--trace", "trace.json", "--parallel", "a", "b"])
+    .stderr("error: recipe `a` failed on line 5 with exit code 1\n")
+    .failure();
+
+  let events = trace(&output);
+
+  // `c` may or may not start before `a` fails, but `b` and `d` never run
+  for span in phase(&events, "X") {
+    assert!(span["name"] == "a" || span["name"] == "c");
+  }
+
+  assert!(phase(&events, "s").is_empty());
+  assert!(phase(&events, "f").is_empty());
+}
+
+#[test]
+fn submodule_recipes_are_named_by_path() {
+  let output = Test::new()
+    .write("foo.just", "bar:")
+    .justfile(
+      "
+        mod foo
+      ",
+    )
+    .args(["--trace", "trace.json", "foo::bar"])
+    .success();
+
+  let events = trace(&output);
+  assert_eq!(phase(&events, "X")[0]["name"], "foo::bar");
+}
+
+#[test]
+fn trace_write_error() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+      ",
+    )
+    .args(["--trace", "missing/trace.json"])
+    .stderr_regex("error: I/O error at `missing/trace.json`: .*\n")
+    .failure();
+}
@@ -0,0 +1,771 @@
+use super::*;
+
+#[test]
+fn unused_private_variable() {
+  Test::new()
+    .justfile(
+      "
+        _foo := 'foo'
+
+        bar:
+      ",
+    )
+    .arg("--lint")
+    .stderr(
+      "
+        warning: private variable `_foo` is never used
+         ——▶ justfile:1:1
+          │
+        1 │ _foo := 'foo'
+          │ ^^^^
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn unused_public_variables_are_not_reported() {
+  Test::new()
+    .justfile(
+      "
+        foo := 'foo'
+
+        bar:
+      ",
+    )
+    .arg("--lint")
+    .success();
+}
+
+#[test]
+fn unused_exported_variables_are_not_reported() {
+  Test::new()
+    .justfile(
+      "
+        [private]
+        export foo := 'foo'
+
+        bar:
+      ",
+    )
+    .arg("--lint")
+    .success();
+}
+
+#[test]
+fn variables_may_be_used_by_recipes_variables_functions_and_settings() {
+  Test::new()
+    .justfile(
+      "
+        _a := 'a'
+        _b := 'b'
+        _c := 'c'
+        _d := 'd'
+        _e := _d
+
+        set tempdir := _c
+        set unstable
+
+        f() := _b
+
+        foo:
+          @echo {{ _a }} {{ f() }} {{ _e }}
+      ",
+    )
+    .arg("--lint")
+    .stdout("a b d\n")
+    .success();
+}
+
+#[test]
+fn variables_used_by_disabled_recipes_are_not_reported() {
+  Test::new()
+    .justfile(
+      "
+        _foo := 'foo'
+
+        bar:
+
+        [linux]
+        baz:
+          echo {{ _foo }}
+
+        [windows]
+        baz:
+          echo {{ _foo }}
+      ",
+    )
+    .arg("--lint")
+    .arg("bar")
+    .success();
+}
+
+#[test]
+fn unused_parameter() {
+  Test::new()
+    .justfile(
+      "
+        foo bar baz:
+          @echo {{ bar }}
+      ",
+    )
+    .arg("--lint")
+    .args(["foo", "a", "b"])
+    .stdout("a\n")
+    .stderr(
+      "
+        warning: parameter `baz` of recipe `foo` is never used
+         ——▶ justfile:1:9
+          │
+        1 │ foo bar baz:
+          │         ^^^
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn parameters_may_be_used_by_defaults_dependencies_and_attributes() {
+  Test::new()
+    .justfile(
+      "
+        [confirm(c)]
+        foo a b=a c='' d='': (bar d)
+          @echo {{ b }}
+
+        bar x:
+          @echo {{ x }}
+      ",
+    )
+    .arg("--lint")
+    .args(["--yes", "foo", "a", "b", "c", "d"])
+    .stdout("d\nb\n")
+    .success();
+}
+
+#[test]
+fn exported_parameters_are_not_reported() {
+  Test::new()
+    .justfile(
+      "
+        foo $bar:
+          @echo $bar
+      ",
+    )
+    .arg("--lint")
+    .args(["foo", "a"])
+    .stdout("a\n")
+    .success();
+}
+
+#[test]
+fn parameters_of_recipes_with_export_setting_are_not_reported() {
+  Test::new()
+    .justfile(
+      "
+        set export
+
+        foo bar:
+          @echo $bar
+      ",
+    )
+    .arg("--lint")
+    .args(["foo", "a"])
+    .stdout("a\n")
+    .success();
+}
+
+#[test]
+fn parameters_of_recipes_with_positional_arguments_are_not_reported() {
+  Test::new()
+    .justfile(
+      "
+        [positional-arguments]
+        foo bar:
+          @echo $1
+      ",
+    )
+    .arg("--lint")
+    .args(["foo", "a"])
+    .stdout("a\n")
+    .success();
+}
+
+#[test]
+fn parameters_of_recipes_without_body_are_not_reported() {
+  Test::new()
+    .justfile(
+      "
+        foo bar:
+      ",
+    )
+    .arg("--lint")
+    .args(["foo", "a"])
+    .success();
+}
+
+#[test]
+fn unused_private_recipe() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+
+        _bar:
+
+        [private]
+        baz:
+      ",
+    )
+    .arg("--lint")
+    .stderr(
+      "
+        warning: private recipe `_bar` is never used
+         ——▶ justfile:3:1
+          │
+        3 │ _bar:
+          │ ^^^^
+        warning: private recipe `baz` is never used
+         ——▶ justfile:6:1
+          │
+        6 │ baz:
+          │ ^^^
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn private_recipes_may_be_used_by_dependencies_aliases_and_default() {
+  Test::new()
+    .justfile(
+      "
+        _foo: _bar && _baz
+
+        _bar:
+
+        _baz:
+
+        [private]
+        alias qux := _qux
+
+        _qux:
+      ",
+    )
+    .arg("--lint")
+    .success();
+}
+
+#[test]
+fn private_recipes_in_submodules_may_be_used_by_parent_dependencies() {
+  Test::new()
+    .write("foo.just", "bar:\n\n_baz:\n\n_qux:")
+    .justfile(
+      "
+        mod foo
+
+        bar: foo::_baz
+      ",
+    )
+    .arg("--lint")
+    .arg("bar")
+    .stderr(
+      "
+        warning: private recipe `_qux` is never used
+         ——▶ foo.just:5:1
+          │
+        5 │ _qux:
+          │ ^^^^
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn private_recipes_used_by_disabled_recipes_are_not_reported() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+
+        [linux]
+        bar: _baz
+
+        [windows]
+        bar: _baz
+
+        _baz:
+      ",
+    )
+    .arg("--lint")
+    .success();
+}
+
+#[test]
+fn parameter_shadows_variable() {
+  Test::new()
+    .justfile(
+      "
+        bar := 'bar'
+
+        foo bar:
+          @echo {{ bar }}
+      ",
+    )
+    .arg("--lint")
+    .args(["foo", "baz"])
+    .stdout("baz\n")
+    .stderr(
+      "
+        warning: parameter `bar` of recipe `foo` shadows variable `bar`
+         ——▶ justfile:3:5
+          │
+        3 │ foo bar:
+          │     ^^^
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn redundant_setting() {
+  Test::new()
+    .justfile(
+      "
+        set quiet := false
+        set output := 'interleaved'
+
+        foo:
+      ",
+    )
+    .arg("--lint")
+    .stderr(
+      "
+        warning: setting `quiet` is set to its default value and has no effect
+         ——▶ justfile:1:5
+          │
+        1 │ set quiet := false
+          │     ^^^^^
+        warning: setting `output` is set to its default value and has no effect
+         ——▶ justfile:2:5
+          │
+        2 │ set output := 'interleaved'
+          │     ^^^^^^
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn duplicate_dependency() {
+  Test::new()
+    .justfile(
+      "
+        foo: (bar 'a') (bar 'b') (bar 'a')
+
+        bar x:
+          @echo {{ x }}
+      ",
+    )
+    .arg("--lint")
+    .stdout("a\nb\n")
+    .stderr(
+      "
+        warning: recipe `foo` depends on `bar` more than once
+         ——▶ justfile:1:27
+          │
+        1 │ foo: (bar 'a') (bar 'b') (bar 'a')
+          │                           ^^^
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn dependencies_in_different_groups_are_not_duplicates() {
+  Test::new()
+    .justfile(
+      "
+        foo: bar && bar
+
+        bar:
+          @echo bar
+      ",
+    )
+    .arg("--lint")
+    .stdout("bar\nbar\n")
+    .success();
+}
+
+#[test]
+fn allow() {
+  Test::new()
+    .justfile(
+      "
+        [allow('redundant-setting')]
+        set quiet := false
+
+        [allow('unused-variable')]
+        _foo := 'foo'
+
+        bar := 'bar'
+
+        [allow('duplicate-dependency', 'parameter-shadows-variable', 'unused-parameter')]
+        baz bar: _qux _qux
+          echo baz
+
+        _qux:
+
+        [allow('unused-private-recipe')]
+        _quux:
+      ",
+    )
+    .arg("--lint")
+    .args(["baz", "a"])
+    .stdout("baz\n")
+    .stderr("echo baz\n")
+    .success();
+}
+
+#[test]
+fn allow_only_suppresses_listed_lints() {
+  Test::new()
+    .justfile(
+      "
+        [allow('unused-parameter')]
+        foo bar:
+          echo foo
+
+        _baz:
+      ",
+    )
+    .arg("--lint")
+    .arg("foo")
+    .arg("a")
+    .stdout("foo\n")
+    .stderr(
+      "
+        warning: private recipe `_baz` is never used
+         ——▶ justfile:5:1
+          │
+        5 │ _baz:
+          │ ^^^^
+        echo foo
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn allow_unknown_lint() {
+  Test::new()
+    .justfile(
+      "
+        [allow('foo')]
+        bar:
+      ",
+    )
+    .stderr(
+      "
+        error: unknown lint `foo`
+         ——▶ justfile:1:8
+          │
+        1 │ [allow('foo')]
+          │        ^^^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn allow_requires_arguments() {
+  Test::new()
+    .justfile(
+      "
+        [allow]
+        bar:
+      ",
+    )
+    .stderr(
+      "
+        error: attribute `allow` got 0 arguments but takes at least 1 argument
+         ——▶ justfile:1:2
+          │
+        1 │ [allow]
+          │  ^^^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn allow_is_invalid_on_aliases() {
+  Test::new()
+    .justfile(
+      "
+        [allow('unused-private-recipe')]
+        alias foo := bar
+
+        bar:
+      ",
+    )
+    .stderr(
+      "
+        error: alias `foo` has invalid attribute `allow`
+         ——▶ justfile:2:7
+          │
+        2 │ alias foo := bar
+          │       ^^^
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn allow_is_formatted() {
+  Test::new()
+    .justfile(
+      "
+        [allow(\"unused-parameter\",'duplicate-dependency')]
+        foo bar:
+          echo foo
+      ",
+    )
+    .arg("--dump")
+    .stdout(
+      "
+        [allow('duplicate-dependency', 'unused-parameter')]
+        foo bar:
+            echo foo
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn warnings_in_submodules_are_printed() {
+  Test::new()
+    .write("foo.just", "bar:\n\n_baz:")
+    .justfile(
+      "
+        mod foo
+      ",
+    )
+    .arg("--lint")
+    .args(["foo", "bar"])
+    .stderr(
+      "
+        warning: private recipe `_baz` is never used
+         ——▶ foo.just:3:1
+          │
+        3 │ _baz:
+          │ ^^^^
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn warnings_are_not_printed_when_quiet() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+
+        _bar:
+      ",
+    )
+    .arg("--lint")
+    .arg("--quiet")
+    .success();
+}
+
+#[test]
+fn warnings_are_not_printed_by_dump() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+
+        _bar:
+      ",
+    )
+    .arg("--lint")
+    .arg("--dump")
+    .stdout(
+      "
+        foo:
+
+        _bar:
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn deny_warnings() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          echo foo
+
+        _bar:
+      ",
+    )
+    .arg("--deny-warnings")
+    .stderr(
+      "
+        warning: private recipe `_bar` is never used
+         ——▶ justfile:4:1
+          │
+        4 │ _bar:
+          │ ^^^^
+        error: 1 warning denied by `--deny-warnings`
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn deny_warnings_from_environment() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+
+        _bar:
+
+        _baz:
+      ",
+    )
+    .env("JUST_DENY_WARNINGS", "true")
+    .arg("--quiet")
+    .failure();
+}
+
+#[test]
+fn deny_warnings_without_warnings() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          @echo foo
+      ",
+    )
+    .arg("--deny-warnings")
+    .stdout("foo\n")
+    .success();
+}
+
+#[test]
+fn warnings_are_not_printed_by_default() {
+  Test::new()
+    .justfile(
+      "
+        set quiet := false
+
+        _foo := 'foo'
+
+        bar baz:
+
+        _qux:
+      ",
+    )
+    .args(["bar", "a"])
+    .success();
+}
+
+#[test]
+fn lint_from_environment() {
+  Test::new()
+    .justfile(
+      "
+        _foo := 'foo'
+
+        bar:
+      ",
+    )
+    .env("JUST_LINT", "true")
+    .stderr(
+      "
+        warning: private variable `_foo` is never used
+         ——▶ justfile:1:1
+          │
+        1 │ _foo := 'foo'
+          │ ^^^^
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn json_dump_only_includes_warnings_when_linting() {
+  Test::new()
+    .justfile(
+      "
+        _foo := 'foo'
+      ",
+    )
+    .args(["--dump", "--dump-format", "json"])
+    .stdout_regex(r#".*"warnings":\[\].*"#)
+    .success();
+
+  Test::new()
+    .justfile(
+      "
+        _foo := 'foo'
+      ",
+    )
+    .args(["--lint", "--dump", "--dump-format", "json"])
+    .stdout_regex(
+      r#".*"warnings":\[\{"lint":"unused-variable","message":"private variable `_foo` is never used"\}\].*"#,
+    )
+    .success();
+}
+
+#[test]
+fn private_recipes_mentioned_in_bodies_are_not_reported() {
+  Test::new()
+    .write("foo.just", "_baz:\n  @echo baz")
+    .justfile(
+      "
+        mod foo
+
+        bar:
+          @{{ just_executable() }} _helper
+          @{{ just_executable() }} foo::_baz
+
+        _helper:
+          @echo helper
+
+        _helper-two:
+      ",
+    )
+    .args(["--lint", "bar"])
+    .stdout("helper\nbaz\n")
+    .stderr(
+      "
+        warning: private recipe `_helper-two` is never used
+          ——▶ justfile:10:1
+           │
+        10 │ _helper-two:
+           │ ^^^^^^^^^^^
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn private_recipes_run_from_the_command_line_are_not_reported() {
+  Test::new()
+    .justfile(
+      "
+        _foo:
+          @echo foo
+      ",
+    )
+    .args(["--lint", "_foo"])
+    .stdout("foo\n")
+    .success();
+}
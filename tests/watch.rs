@@ -0,0 +1,340 @@
+use {
+  super::*,
+  nix::{sys::signal::Signal, unistd::Pid},
+  std::{
+    io::{BufRead, BufReader},
+    process::Child,
+    sync::mpsc::{self, Receiver},
+  },
+};
+
+struct Watcher {
+  child: Child,
+  lines: Receiver<String>,
+  tempdir: TempDir,
+}
+
+impl Watcher {
+  fn new(justfile: &str, files: &[(&str, &str)], arguments: &[&str]) -> Self {
+    let tempdir = tempdir();
+
+    fs::write(tempdir.path().join("justfile"), unindent(justfile)).unwrap();
+
+    for (path, content) in files {
+      let path = tempdir.path().join(path);
+      fs::create_dir_all(path.parent().unwrap()).unwrap();
+      fs::write(path, content).unwrap();
+    }
+
+    let mut child = Command::new(JUST)
+      .current_dir(tempdir.path())
+      .env("JUST_UNSTABLE", "1")
+      .arg("--watch")
+      .args(arguments)
+      .stdout(Stdio::piped())
+      .stderr(Stdio::null())
+      .spawn()
+      .unwrap();
+
+    let stdout = BufReader::new(child.stdout.take().unwrap());
+
+    let (tx, lines) = mpsc::channel();
+
+    thread::spawn(move || {
+      for line in stdout.lines() {
+        if tx.send(line.unwrap()).is_err() {
+          break;
+        }
+      }
+    });
+
+    Self {
+      child,
+      lines,
+      tempdir,
+    }
+  }
+
+  #[track_caller]
+  fn expect(&self, expected: &str) {
+    let line = self
+      .lines
+      .recv_timeout(Duration::from_secs(10))
+      .unwrap_or_else(|_| panic!("timed out waiting for `{expected}`"));
+    assert_eq!(line, expected);
+  }
+
+  fn write(&self, path: &str, content: &str) {
+    // Wait for the watcher to take a snapshot of the current state before
+    // making changes.
+    thread::sleep(Duration::from_millis(500));
+    fs::write(self.tempdir.path().join(path), content).unwrap();
+  }
+}
+
+impl Drop for Watcher {
+  fn drop(&mut self) {
+    nix::sys::signal::kill(
+      Pid::from_raw(self.child.id().try_into().unwrap()),
+      Signal::SIGTERM,
+    )
+    .unwrap();
+    self.child.wait().unwrap();
+  }
+}
+
+#[test]
+fn reruns_when_cache_input_changes() {
+  let watcher = Watcher::new(
+    "
+      [cache(inputs = 'src')]
+      build:
+        @cat src
+    ",
+    &[("src", "a\n")],
+    &["build"],
+  );
+
+  watcher.expect("a");
+  watcher.write("src", "b\n");
+  watcher.expect("b");
+  watcher.write("src", "c\n");
+  watcher.expect("c");
+}
+
+#[test]
+fn reruns_when_hidden_cache_input_changes() {
+  let watcher = Watcher::new(
+    "
+      [cache(inputs = 'src')]
+      build:
+        @cat src/.hidden
+    ",
+    &[("src/.hidden", "a\n")],
+    &["build"],
+  );
+
+  watcher.expect("a");
+  watcher.write("src/.hidden", "b\n");
+  watcher.expect("b");
+}
+
+#[test]
+fn reruns_when_stale_source_changes() {
+  let watcher = Watcher::new(
+    "
+      [stale(sources = 'src', targets = 'out')]
+      build:
+        @cat src
+        @cp src out
+    ",
+    &[("src", "a\n")],
+    &["build"],
+  );
+
+  watcher.expect("a");
+  watcher.write("src", "b\n");
+  watcher.expect("b");
+}
+
+#[test]
+fn reruns_when_watch_path_changes() {
+  let watcher = Watcher::new(
+    "
+      build:
+        @cat foo/bar
+    ",
+    &[],
+    &["--watch-path", "foo", "build"],
+  );
+
+  fs::create_dir(watcher.tempdir.path().join("foo")).unwrap();
+  watcher.write("foo/bar", "a\n");
+  watcher.expect("a");
+}
+
+#[test]
+fn reruns_when_file_is_added_to_unchanged_directory() {
+  let watcher = Watcher::new(
+    "
+      build:
+        @ls foo
+    ",
+    &[("foo/a", "")],
+    &["--watch-path", "foo", "--watch-interval", "50ms", "build"],
+  );
+
+  watcher.expect("a");
+
+  // Wait until directory listings are reused.
+  thread::sleep(Duration::from_secs(3));
+
+  watcher.write("foo/b", "");
+  watcher.expect("a");
+  watcher.expect("b");
+
+  watcher.write("foo/a", "a");
+  watcher.expect("a");
+  watcher.expect("b");
+}
+
+#[test]
+fn reruns_after_failure() {
+  let watcher = Watcher::new(
+    "
+      build:
+        @cat src
+        @test -s src
+        @echo ok
+    ",
+    &[("src", "")],
+    &["--watch-path", "src", "build"],
+  );
+
+  watcher.write("src", "a\n");
+  watcher.expect("a");
+  watcher.expect("ok");
+}
+
+#[test]
+fn dependency_inputs_are_watched() {
+  let watcher = Watcher::new(
+    "
+      [cache(inputs = 'src')]
+      dep:
+        @cat src
+
+      build: dep
+        @echo build
+    ",
+    &[("src", "a\n")],
+    &["build"],
+  );
+
+  watcher.expect("a");
+  watcher.expect("build");
+  watcher.write("src", "b\n");
+  watcher.expect("b");
+  watcher.expect("build");
+}
+
+#[test]
+fn running_recipe_is_terminated_on_change() {
+  let watcher = Watcher::new(
+    "
+      serve:
+        @echo start $(cat src)
+        @exec sleep 100
+    ",
+    &[("src", "a")],
+    &["--watch-path", "src", "serve"],
+  );
+
+  watcher.expect("start a");
+  watcher.write("src", "b");
+  watcher.expect("start b");
+}
+
+#[test]
+fn finally_dependencies_are_not_terminated_on_change() {
+  let watcher = Watcher::new(
+    "
+      serve: &&! teardown
+        @echo start $(cat src)
+        @exec sleep 100
+
+      teardown:
+        @sleep 0.5
+        @echo teardown
+    ",
+    &[("src", "a")],
+    &["--watch-path", "src", "serve"],
+  );
+
+  watcher.expect("start a");
+  watcher.write("src", "b");
+  watcher.expect("teardown");
+  watcher.expect("start b");
+}
+
+#[test]
+fn justfile_is_reloaded() {
+  let watcher = Watcher::new(
+    "
+      build:
+        @cat src
+    ",
+    &[("src", "a\n")],
+    &["--watch-path", "src", "build"],
+  );
+
+  watcher.expect("a");
+  watcher.write("justfile", "build:\n  @echo changed $(cat src)\n");
+  watcher.write("src", "b\n");
+  watcher.expect("changed b");
+}
+
+#[test]
+fn paths_are_watched_after_reload_error() {
+  let watcher = Watcher::new(
+    "
+      [cache(inputs = 'src')]
+      build:
+        @cat src
+    ",
+    &[("src", "a\n")],
+    &["build"],
+  );
+
+  watcher.expect("a");
+  watcher.write("justfile", "build: missing\n");
+  watcher.write("src", "b\n");
+  watcher.write(
+    "justfile",
+    "[cache(inputs = 'src')]\nbuild:\n  @echo fixed $(cat src)\n",
+  );
+  watcher.write("src", "c\n");
+  watcher.expect("fixed c");
+}
+
+#[test]
+fn no_paths_to_watch() {
+  Test::new()
+    .justfile(
+      "
+        build:
+          @echo build
+      ",
+    )
+    .args(["--watch", "build"])
+    .stdout("build\n")
+    .stderr(
+      "
+        error: no paths to watch, pass `--watch-path` or invoke recipes with `[cache(inputs = ...)]` or `[stale(sources = ...)]`
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn no_paths_to_watch_after_failure() {
+  Test::new()
+    .justfile(
+      "
+        build:
+          @exit 1
+      ",
+    )
+    .args(["--watch", "build"])
+    .stderr("error: recipe `build` failed on line 2 with exit code 1\n")
+    .failure();
+}
+
+#[test]
+fn conflicts_with_subcommands() {
+  Test::new()
+    .justfile("build:")
+    .args(["--watch", "--list"])
+    .stderr_regex("error: the argument '--watch' cannot be used with:\n.*  --list .*")
+    .status(2);
+}